@@ -1,10 +1,10 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 use tx_rs::Tx;
 
 use crate::error::UsecaseError;
 use dao::{EmployeeDao, HaveEmployeeDao};
 use payroll_domain::{Employee, EmployeeId, PaymentClassification, PaymentSchedule};
-use payroll_impl::{affiliation::NoAffiliation, method::HoldMethod};
+use payroll_impl::method::HoldMethod;
 
 pub trait AddEmployeeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
     fn execute<'a>(
@@ -12,14 +12,13 @@ pub trait AddEmployeeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
         emp_id: EmployeeId,
         name: &str,
         address: &str,
-        classification: Rc<RefCell<dyn PaymentClassification>>,
-        schedule: Rc<RefCell<dyn PaymentSchedule>>,
+        classification: Arc<RwLock<dyn PaymentClassification>>,
+        schedule: Arc<RwLock<dyn PaymentSchedule>>,
     ) -> impl tx_rs::Tx<Ctx, Item = EmployeeId, Err = UsecaseError>
     where
         Ctx: 'a,
     {
-        let method = Rc::new(RefCell::new(HoldMethod));
-        let affiliation = Rc::new(RefCell::new(NoAffiliation));
+        let method = Arc::new(RwLock::new(HoldMethod));
         let emp = Employee::new(
             emp_id,
             name,
@@ -27,7 +26,7 @@ pub trait AddEmployeeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
             classification,
             schedule,
             method,
-            affiliation,
+            vec![],
         );
         self.dao()
             .insert(emp)
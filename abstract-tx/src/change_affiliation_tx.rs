@@ -1,23 +1,20 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use crate::change_employee_tx::ChangeEmployeeTransaction;
 use crate::error::UsecaseError;
-use payroll_domain::{Affiliation, Employee, EmployeeId};
+use payroll_domain::{Affiliation, EmployeeId};
 
 pub trait ChangeAffiliationTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
-    fn execute<'a, F>(
+    fn execute<'a>(
         &'a self,
         emp_id: EmployeeId,
-        record_membership: F,
-        affiliation: Rc<RefCell<dyn Affiliation>>,
+        affiliation: Arc<RwLock<dyn Affiliation>>,
     ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
     where
-        F: FnOnce(&mut Ctx, &mut Employee) -> Result<(), UsecaseError>,
         Ctx: 'a,
     {
-        ChangeEmployeeTransaction::<Ctx>::execute(self, emp_id, |ctx, emp| {
-            record_membership(ctx, emp)?;
-            emp.set_affiliation(affiliation);
+        ChangeEmployeeTransaction::<Ctx>::execute(self, emp_id, |_ctx, emp| {
+            emp.add_affiliation(affiliation);
             Ok(())
         })
     }
@@ -1,20 +1,28 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use crate::change_employee_tx::ChangeEmployeeTransaction;
 use crate::error::UsecaseError;
 use payroll_domain::{EmployeeId, PaymentClassification, PaymentSchedule};
+use payroll_impl::tax::{IncomeTaxWithholding, MonthlyWithholding};
 
 pub trait ChangeClassificationTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
     fn execute<'a>(
         &'a self,
         emp_id: EmployeeId,
-        classification: Rc<RefCell<dyn PaymentClassification>>,
-        schedule: Rc<RefCell<dyn PaymentSchedule>>,
+        classification: Arc<RwLock<dyn PaymentClassification>>,
+        schedule: Arc<RwLock<dyn PaymentSchedule>>,
     ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
     where
         Ctx: 'a,
     {
-        ChangeEmployeeTransaction::<Ctx>::execute(self, emp_id, |_ctx, emp| {
+        ChangeEmployeeTransaction::<Ctx>::execute(self, emp_id, move |_ctx, emp| {
+            // withheld by the monthly table, the employee is to stay paid monthly
+            let monthly_withheld = emp
+                .get_affiliation(IncomeTaxWithholding::NAME)
+                .is_some_and(|a| a.read().unwrap().as_any().is::<MonthlyWithholding>());
+            if monthly_withheld && !MonthlyWithholding::is_applicable(&*schedule.read().unwrap()) {
+                return Err(UsecaseError::NotPaidMonthly { emp_id });
+            }
             emp.set_classification(classification);
             emp.set_schedule(schedule);
             Ok(())
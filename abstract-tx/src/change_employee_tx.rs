@@ -19,12 +19,12 @@ pub trait ChangeEmployeeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
                 .dao()
                 .fetch(emp_id)
                 .run(ctx)
-                .map_err(UsecaseError::NotFound)?;
+                .map_err(UsecaseError::fetch_failed)?;
             f(ctx, &mut emp)?;
             self.dao()
                 .update(emp)
                 .run(ctx)
-                .map_err(UsecaseError::UpdateEmployeeFailed)
+                .map_err(UsecaseError::update_failed)
         })
     }
 }
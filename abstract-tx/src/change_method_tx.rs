@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use crate::change_employee_tx::ChangeEmployeeTransaction;
 use crate::error::UsecaseError;
@@ -8,7 +8,7 @@ pub trait ChangeMethodTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
     fn execute<'a>(
         &'a self,
         emp_id: EmployeeId,
-        method: Rc<RefCell<dyn PaymentMethod>>,
+        method: Arc<RwLock<dyn PaymentMethod>>,
     ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
     where
         Ctx: 'a,
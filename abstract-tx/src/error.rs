@@ -1,27 +1,161 @@
 use thiserror::Error;
 
-use dao::DaoError;
+use dao::{CommitError, DaoError};
+use payroll_domain::{EmployeeId, MemberId, ServiceChargeId};
 
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub enum UsecaseError {
-    #[error("register employee failed: {0}")]
-    RegisterEmployeeFailed(DaoError),
-    #[error("unregister employee failed: {0}")]
-    UnregisterEmployeeFailed(DaoError),
-    #[error("employee not found: {0}")]
-    NotFound(DaoError),
-    #[error("can't get all employees: {0}")]
-    GetAllFailed(DaoError),
-    #[error("employee is not hourly salary: {0}")]
-    NotHourlySalary(String),
-    #[error("employee is not commissioned salary: {0}")]
-    NotCommissionedSalary(String),
-    #[error("update employee failed: {0}")]
-    UpdateEmployeeFailed(DaoError),
-    #[error("employee is not union member: {0}")]
-    NotUnionMember(String),
-    #[error("add union member failed: {0}")]
-    AddUnionMemberFailed(DaoError),
-    #[error("remove union member failed: {0}")]
-    RemoveUnionMemberFailed(DaoError),
+    #[error("register employee failed")]
+    RegisterEmployeeFailed(#[source] DaoError),
+    #[error("unregister employee failed")]
+    UnregisterEmployeeFailed(#[source] DaoError),
+    #[error("employee not found")]
+    NotFound(#[source] DaoError),
+    #[error("can't fetch employee")]
+    FetchEmployeeFailed(#[source] DaoError),
+    #[error("union member not found")]
+    UnionMemberNotFound(#[source] DaoError),
+    #[error("can't get all employees")]
+    GetAllFailed(#[source] DaoError),
+    #[error("can't find employees")]
+    FindEmployeesFailed(#[source] DaoError),
+    #[error("can't get payable employees")]
+    GetPayableFailed(#[source] DaoError),
+    #[error("emp_id={emp_id} is not hourly salary")]
+    NotHourlySalary { emp_id: EmployeeId },
+    #[error("emp_id={emp_id} is not commissioned salary")]
+    NotCommissionedSalary { emp_id: EmployeeId },
+    #[error("composite salary of emp_id={emp_id} is invalid: {reason}")]
+    InvalidComposite { emp_id: EmployeeId, reason: String },
+    #[error("update employee failed")]
+    UpdateEmployeeFailed(#[source] DaoError),
+    #[error("employee was changed concurrently, retry")]
+    UpdateConflict(#[source] DaoError),
+    #[error("commit failed")]
+    CommitFailed(#[source] DaoError),
+    #[error("record paycheck failed")]
+    RecordPaycheckFailed(#[source] DaoError),
+    #[error("can't get paychecks")]
+    GetPaychecksFailed(#[source] DaoError),
+    #[error("emp_id={emp_id} has no pay date in {year}")]
+    PayDateNotFound { emp_id: EmployeeId, year: i32 },
+    #[error("emp_id={emp_id} has no affiliation {name}")]
+    AffiliationNotFound { emp_id: EmployeeId, name: String },
+    #[error("affiliation {name} of emp_id={emp_id} is already in use")]
+    AffiliationConflict { emp_id: EmployeeId, name: String },
+    #[error("emp_id={emp_id} is not paid monthly")]
+    NotPaidMonthly { emp_id: EmployeeId },
+    #[error("tax table {path} unavailable: {reason}")]
+    TaxTableUnavailable { path: String, reason: String },
+    #[error("insurance table {path} unavailable: {reason}")]
+    InsuranceTableUnavailable { path: String, reason: String },
+    #[error("remuneration grade {grade} not found for emp_id={emp_id}")]
+    GradeNotFound { emp_id: EmployeeId, grade: u32 },
+    #[error("prefecture {prefecture} not found for emp_id={emp_id}")]
+    PrefectureNotFound {
+        emp_id: EmployeeId,
+        prefecture: String,
+    },
+    #[error("resident tax notices {path} unavailable: {reason}")]
+    ResidentTaxNoticesUnavailable { path: String, reason: String },
+    #[error("year-end declarations {path} unavailable: {reason}")]
+    DeclarationsUnavailable { path: String, reason: String },
+    #[error("garnishment order {case_id} of emp_id={emp_id} already exists")]
+    GarnishmentOrderConflict { emp_id: EmployeeId, case_id: String },
+    #[error("garnishment order {case_id} of emp_id={emp_id} not found")]
+    GarnishmentOrderNotFound { emp_id: EmployeeId, case_id: String },
+    #[error("advance {advance_id} of emp_id={emp_id} already exists")]
+    AdvanceConflict {
+        emp_id: EmployeeId,
+        advance_id: String,
+    },
+    #[error("advance {advance_id} of emp_id={emp_id} is invalid: {reason}")]
+    InvalidAdvance {
+        emp_id: EmployeeId,
+        advance_id: String,
+        reason: String,
+    },
+    #[error("emp_id={emp_id} is not union member")]
+    NotUnionMember { emp_id: EmployeeId },
+    #[error("service charge {charge_id} of member_id={member_id} not found")]
+    ServiceChargeNotFound {
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+    },
+    #[error("service charge {charge_id} of member_id={member_id} already reversed")]
+    ServiceChargeAlreadyReversed {
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+    },
+}
+impl CommitError for UsecaseError {
+    fn commit_failed(e: DaoError) -> Self {
+        UsecaseError::CommitFailed(e)
+    }
+}
+impl UsecaseError {
+    // for the failure of `EmployeeDao::fetch`
+    pub fn fetch_failed(e: DaoError) -> Self {
+        match e {
+            DaoError::EmployeeNotFound(_) => UsecaseError::NotFound(e),
+            _ => UsecaseError::FetchEmployeeFailed(e),
+        }
+    }
+    // for the failure of `EmployeeDao::update`
+    pub fn update_failed(e: DaoError) -> Self {
+        match e {
+            DaoError::VersionConflict { .. } => UsecaseError::UpdateConflict(e),
+            _ => UsecaseError::UpdateEmployeeFailed(e),
+        }
+    }
+    // whether running the transaction again from the start may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            UsecaseError::UpdateConflict(_)
+                | UsecaseError::CommitFailed(DaoError::VersionConflict { .. })
+        )
+    }
+    // stable across releases, for front-ends to tell the errors apart
+    pub fn code(&self) -> &'static str {
+        match self {
+            UsecaseError::RegisterEmployeeFailed(_) => "usecase.register_employee_failed",
+            UsecaseError::UnregisterEmployeeFailed(_) => "usecase.unregister_employee_failed",
+            UsecaseError::NotFound(_) => "usecase.not_found",
+            UsecaseError::FetchEmployeeFailed(_) => "usecase.fetch_employee_failed",
+            UsecaseError::UnionMemberNotFound(_) => "usecase.union_member_not_found",
+            UsecaseError::GetAllFailed(_) => "usecase.get_all_failed",
+            UsecaseError::FindEmployeesFailed(_) => "usecase.find_employees_failed",
+            UsecaseError::GetPayableFailed(_) => "usecase.get_payable_failed",
+            UsecaseError::NotHourlySalary { .. } => "usecase.not_hourly_salary",
+            UsecaseError::NotCommissionedSalary { .. } => "usecase.not_commissioned_salary",
+            UsecaseError::InvalidComposite { .. } => "usecase.invalid_composite",
+            UsecaseError::UpdateEmployeeFailed(_) => "usecase.update_employee_failed",
+            UsecaseError::UpdateConflict(_) => "usecase.update_conflict",
+            UsecaseError::CommitFailed(_) => "usecase.commit_failed",
+            UsecaseError::RecordPaycheckFailed(_) => "usecase.record_paycheck_failed",
+            UsecaseError::GetPaychecksFailed(_) => "usecase.get_paychecks_failed",
+            UsecaseError::PayDateNotFound { .. } => "usecase.pay_date_not_found",
+            UsecaseError::AffiliationNotFound { .. } => "usecase.affiliation_not_found",
+            UsecaseError::AffiliationConflict { .. } => "usecase.affiliation_conflict",
+            UsecaseError::NotPaidMonthly { .. } => "usecase.not_paid_monthly",
+            UsecaseError::TaxTableUnavailable { .. } => "usecase.tax_table_unavailable",
+            UsecaseError::InsuranceTableUnavailable { .. } => "usecase.insurance_table_unavailable",
+            UsecaseError::GradeNotFound { .. } => "usecase.grade_not_found",
+            UsecaseError::PrefectureNotFound { .. } => "usecase.prefecture_not_found",
+            UsecaseError::ResidentTaxNoticesUnavailable { .. } => {
+                "usecase.resident_tax_notices_unavailable"
+            }
+            UsecaseError::DeclarationsUnavailable { .. } => "usecase.declarations_unavailable",
+            UsecaseError::GarnishmentOrderConflict { .. } => "usecase.garnishment_order_conflict",
+            UsecaseError::GarnishmentOrderNotFound { .. } => "usecase.garnishment_order_not_found",
+            UsecaseError::AdvanceConflict { .. } => "usecase.advance_conflict",
+            UsecaseError::InvalidAdvance { .. } => "usecase.invalid_advance",
+            UsecaseError::NotUnionMember { .. } => "usecase.not_union_member",
+            UsecaseError::ServiceChargeNotFound { .. } => "usecase.service_charge_not_found",
+            UsecaseError::ServiceChargeAlreadyReversed { .. } => {
+                "usecase.service_charge_already_reversed"
+            }
+        }
+    }
 }
@@ -4,6 +4,7 @@ mod change_classification_tx;
 mod change_employee_tx;
 mod change_method_tx;
 mod error;
+mod remove_affiliation_tx;
 
 pub use add_employee_tx::AddEmployeeTransaction;
 pub use change_affiliation_tx::ChangeAffiliationTransaction;
@@ -11,3 +12,4 @@ pub use change_classification_tx::ChangeClassificationTransaction;
 pub use change_employee_tx::ChangeEmployeeTransaction;
 pub use change_method_tx::ChangeMethodTransaction;
 pub use error::UsecaseError;
+pub use remove_affiliation_tx::RemoveAffiliationTransaction;
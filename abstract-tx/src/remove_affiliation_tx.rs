@@ -0,0 +1,25 @@
+use crate::change_employee_tx::ChangeEmployeeTransaction;
+use crate::error::UsecaseError;
+use payroll_domain::EmployeeId;
+
+pub trait RemoveAffiliationTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        name: &str,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::<Ctx>::execute(self, emp_id, move |_ctx, emp| {
+            emp.remove_affiliation(name)
+                .ok_or(UsecaseError::AffiliationNotFound {
+                    emp_id,
+                    name: name.to_string(),
+                })?;
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<Ctx, T> RemoveAffiliationTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -1,13 +1,83 @@
+use std::{error::Error as StdError, fmt, sync::Arc};
 use thiserror::Error;
 
+use payroll_domain::{EmployeeId, MemberId};
+
 #[derive(Debug, Clone, Eq, PartialEq, Error)]
 pub enum DaoError {
-    #[error("insert error: {0}")]
-    InsertError(String),
-    #[error("delete error: {0}")]
-    DeleteError(String),
-    #[error("fetch error: {0}")]
-    FetchError(String),
-    #[error("update error: {0}")]
-    UpdateError(String),
+    #[error("emp_id={0} not found")]
+    EmployeeNotFound(EmployeeId),
+    #[error("emp_id={0} already exists")]
+    EmployeeAlreadyExists(EmployeeId),
+    #[error("member_id={0} not found")]
+    UnionMemberNotFound(MemberId),
+    // the member id is held by another employee
+    #[error("member_id={0} already exists")]
+    UnionMemberAlreadyExists(MemberId),
+    // the employee has been updated since it was fetched
+    #[error("emp_id={emp_id} version={version} is stale")]
+    VersionConflict { emp_id: EmployeeId, version: u32 },
+    // the employee can't be stored as it is, e.g. a trait object of unknown type
+    #[error("unsupported data")]
+    UnsupportedData(#[source] Cause),
+    // a failure of the storage itself
+    #[error("storage error")]
+    StorageError(#[source] Cause),
+}
+impl DaoError {
+    pub fn unsupported_data(e: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        DaoError::UnsupportedData(Cause::new(e))
+    }
+    pub fn storage_error(e: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        DaoError::StorageError(Cause::new(e))
+    }
+    // stable across releases, for front-ends to tell the errors apart
+    pub fn code(&self) -> &'static str {
+        match self {
+            DaoError::EmployeeNotFound(_) => "dao.employee_not_found",
+            DaoError::EmployeeAlreadyExists(_) => "dao.employee_already_exists",
+            DaoError::UnionMemberNotFound(_) => "dao.union_member_not_found",
+            DaoError::UnionMemberAlreadyExists(_) => "dao.union_member_already_exists",
+            DaoError::VersionConflict { .. } => "dao.version_conflict",
+            DaoError::UnsupportedData(_) => "dao.unsupported_data",
+            DaoError::StorageError(_) => "dao.storage_error",
+        }
+    }
+}
+
+// the error of the storage or of the data format a DaoError comes from; shared so that
+// DaoError can still be cloned, and told apart by its message
+#[derive(Debug, Clone)]
+pub struct Cause(Arc<dyn StdError + Send + Sync>);
+impl Cause {
+    pub fn new(e: impl Into<Box<dyn StdError + Send + Sync>>) -> Self {
+        Self(Arc::from(e.into()))
+    }
+}
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+// stands in for the error it wraps
+impl StdError for Cause {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.0.source()
+    }
+}
+impl PartialEq for Cause {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+impl Eq for Cause {}
+
+// how the error of what runs in a transaction tells a failure to commit it
+pub trait CommitError {
+    fn commit_failed(e: DaoError) -> Self;
+}
+impl CommitError for DaoError {
+    fn commit_failed(e: DaoError) -> Self {
+        e
+    }
 }
@@ -1,5 +1,11 @@
 mod error;
-pub use error::DaoError;
+mod query;
+
+pub use error::{Cause, CommitError, DaoError};
+pub use query::{ClassificationKind, EmployeeQuery, MethodKind, ScheduleKind};
+
+use chrono::NaiveDate;
+use std::ops::RangeInclusive;
 
 use payroll_domain::{Employee, EmployeeId, MemberId, Paycheck};
 
@@ -9,15 +15,21 @@ pub trait EmployeeDao<Ctx> {
     fn fetch(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<Ctx, Item = Employee, Err = DaoError>;
     fn update(&self, emp: Employee) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError>;
     fn get_all(&self) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError>;
-    fn add_union_member(
+    // those to be paid on the pay date, in the order of emp_id; a batch of at most
+    // `limit` of them following `after`, the last one of the previous batch
+    fn get_payable(
         &self,
-        member_id: MemberId,
-        emp_id: EmployeeId,
-    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError>;
-    fn remove_union_member(
+        pay_date: NaiveDate,
+        after: Option<EmployeeId>,
+        limit: usize,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError>;
+    // in the order of emp_id
+    fn find_employees(
         &self,
-        member_id: MemberId,
-    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError>;
+        query: EmployeeQuery,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError>;
+    // the union members are indexed by the union affiliation of the employees as they
+    // are inserted, updated and deleted; no two of them may have the same member id
     fn find_union_member(
         &self,
         member_id: MemberId,
@@ -27,8 +39,29 @@ pub trait EmployeeDao<Ctx> {
         emp_id: EmployeeId,
         pc: Paycheck,
     ) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError>;
+    fn get_paychecks(
+        &self,
+        emp_id: EmployeeId,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Paycheck>, Err = DaoError>;
+    // whether the employee has been paid for the period, without fetching the paychecks
+    fn has_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        period: RangeInclusive<NaiveDate>,
+    ) -> impl tx_rs::Tx<Ctx, Item = bool, Err = DaoError>;
 }
 
 pub trait HaveEmployeeDao<Ctx> {
     fn dao(&self) -> Box<&impl EmployeeDao<Ctx>>;
 }
+
+// a store the transactions of a front-end run against
+pub trait Database: Clone {
+    type Ctx;
+    fn dao(&self) -> &impl EmployeeDao<Self::Ctx>;
+    // runs `f` in a transaction of its own, committed only if it succeeds
+    fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Self::Ctx) -> Result<T, E>,
+        E: CommitError;
+}
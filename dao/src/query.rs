@@ -0,0 +1,81 @@
+// the kinds are named after the types the employee records are tagged with
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ClassificationKind {
+    Salaried,
+    Hourly,
+    Commissioned,
+    Composite,
+}
+impl ClassificationKind {
+    pub fn get_type(&self) -> &'static str {
+        match self {
+            Self::Salaried => "Salaried",
+            Self::Hourly => "Hourly",
+            Self::Commissioned => "Commissioned",
+            Self::Composite => "Composite",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ScheduleKind {
+    Monthly,
+    Weekly,
+    Biweekly,
+}
+impl ScheduleKind {
+    pub fn get_type(&self) -> &'static str {
+        match self {
+            Self::Monthly => "Monthly",
+            Self::Weekly => "Weekly",
+            Self::Biweekly => "Biweekly",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MethodKind {
+    Hold,
+    Direct,
+    Mail,
+}
+impl MethodKind {
+    pub fn get_type(&self) -> &'static str {
+        match self {
+            Self::Hold => "Hold",
+            Self::Direct => "Direct",
+            Self::Mail => "Mail",
+        }
+    }
+}
+
+// the employees meeting all of the conditions given, none given for any of them
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct EmployeeQuery {
+    // a part of the name
+    pub name: Option<String>,
+    pub classification: Option<ClassificationKind>,
+    pub schedule: Option<ScheduleKind>,
+    pub method: Option<MethodKind>,
+    pub union_member: Option<bool>,
+}
+impl EmployeeQuery {
+    pub fn matches_name(&self, name: &str) -> bool {
+        self.name.as_ref().is_none_or(|part| name.contains(part))
+    }
+    pub fn matches_classification(&self, classification_type: &str) -> bool {
+        self.classification
+            .is_none_or(|kind| kind.get_type() == classification_type)
+    }
+    pub fn matches_schedule(&self, schedule_type: &str) -> bool {
+        self.schedule
+            .is_none_or(|kind| kind.get_type() == schedule_type)
+    }
+    pub fn matches_method(&self, method_type: &str) -> bool {
+        self.method
+            .is_none_or(|kind| kind.get_type() == method_type)
+    }
+    pub fn matches_union_member(&self, union_member: bool) -> bool {
+        self.union_member.is_none_or(|m| m == union_member)
+    }
+}
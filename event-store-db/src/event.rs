@@ -0,0 +1,524 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use payroll_domain::{DeductionLine, DuesPolicy, EmployeeId, MemberId, Paycheck, ServiceChargeId};
+use payroll_impl::{
+    affiliation::UnionAffiliation,
+    classification::{CommissionedClassification, HourlyClassification},
+    record::{
+        AffiliationRecord, ClassificationRecord, EmployeeRecord, MethodRecord, ScheduleRecord,
+    },
+};
+
+// what has happened to an employee; the current state is what these add up to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum EmployeeEvent {
+    EmployeeAdded {
+        record: EmployeeRecord,
+    },
+    EmployeeDeleted {
+        emp_id: EmployeeId,
+    },
+    NameChanged {
+        emp_id: EmployeeId,
+        name: String,
+    },
+    AddressChanged {
+        emp_id: EmployeeId,
+        address: String,
+    },
+    TimeCardPosted {
+        emp_id: EmployeeId,
+        date: NaiveDate,
+        hours: f32,
+    },
+    SalesReceiptPosted {
+        emp_id: EmployeeId,
+        date: NaiveDate,
+        amount: f32,
+    },
+    ClassificationChanged {
+        emp_id: EmployeeId,
+        classification: ClassificationRecord,
+    },
+    ScheduleChanged {
+        emp_id: EmployeeId,
+        schedule: ScheduleRecord,
+    },
+    MethodChanged {
+        emp_id: EmployeeId,
+        method: MethodRecord,
+    },
+    // replaces the one of the same name, if any, as the last of them
+    AffiliationAdded {
+        emp_id: EmployeeId,
+        affiliation: AffiliationRecord,
+    },
+    AffiliationRemoved {
+        emp_id: EmployeeId,
+        name: String,
+    },
+    // any other change of an affiliation in place, e.g. keeping track of what has been
+    // paid on payday
+    AffiliationChanged {
+        emp_id: EmployeeId,
+        affiliation: AffiliationRecord,
+    },
+    ServiceChargePosted {
+        emp_id: EmployeeId,
+        date: NaiveDate,
+        amount: f32,
+    },
+    ServiceChargeReversed {
+        emp_id: EmployeeId,
+        charge_id: ServiceChargeId,
+        date: NaiveDate,
+    },
+    UnionDuesChanged {
+        emp_id: EmployeeId,
+        dues: DuesPolicy,
+        effective: Option<NaiveDate>,
+    },
+    MemberIdChanged {
+        emp_id: EmployeeId,
+        member_id: MemberId,
+    },
+    NetPayFloorChanged {
+        emp_id: EmployeeId,
+        net_pay_floor: f32,
+    },
+    ArrearsChanged {
+        emp_id: EmployeeId,
+        arrears: Vec<DeductionLine>,
+    },
+    PaydayRun {
+        emp_id: EmployeeId,
+        paycheck: Paycheck,
+    },
+}
+impl EmployeeEvent {
+    pub fn get_emp_id(&self) -> EmployeeId {
+        match self {
+            Self::EmployeeAdded { record } => record.emp_id,
+            Self::EmployeeDeleted { emp_id }
+            | Self::NameChanged { emp_id, .. }
+            | Self::AddressChanged { emp_id, .. }
+            | Self::TimeCardPosted { emp_id, .. }
+            | Self::SalesReceiptPosted { emp_id, .. }
+            | Self::ClassificationChanged { emp_id, .. }
+            | Self::ScheduleChanged { emp_id, .. }
+            | Self::MethodChanged { emp_id, .. }
+            | Self::AffiliationAdded { emp_id, .. }
+            | Self::AffiliationRemoved { emp_id, .. }
+            | Self::AffiliationChanged { emp_id, .. }
+            | Self::ServiceChargePosted { emp_id, .. }
+            | Self::ServiceChargeReversed { emp_id, .. }
+            | Self::UnionDuesChanged { emp_id, .. }
+            | Self::MemberIdChanged { emp_id, .. }
+            | Self::NetPayFloorChanged { emp_id, .. }
+            | Self::ArrearsChanged { emp_id, .. }
+            | Self::PaydayRun { emp_id, .. } => *emp_id,
+        }
+    }
+}
+
+// an event as it was committed to the store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RecordedEvent {
+    pub seq: usize,
+    pub recorded_at: NaiveDateTime,
+    pub event: EmployeeEvent,
+}
+
+// the state the events add up to
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct State {
+    pub employees: BTreeMap<EmployeeId, EmployeeRecord>,
+    // the union members both ways, by the union affiliation of the employees
+    pub union_members: BTreeMap<MemberId, EmployeeId>,
+    pub member_ids: BTreeMap<EmployeeId, MemberId>,
+    pub paychecks: BTreeMap<EmployeeId, Vec<Paycheck>>,
+}
+impl State {
+    pub fn apply(&mut self, event: &EmployeeEvent) {
+        let emp_id = event.get_emp_id();
+        match event {
+            EmployeeEvent::EmployeeAdded { record } => {
+                self.employees.insert(emp_id, record.clone());
+                self.index_member(emp_id);
+            }
+            EmployeeEvent::EmployeeDeleted { .. } => {
+                self.employees.remove(&emp_id);
+                self.index_member(emp_id);
+            }
+            EmployeeEvent::PaydayRun { paycheck, .. } => {
+                self.paychecks
+                    .entry(emp_id)
+                    .or_default()
+                    .push(paycheck.clone());
+            }
+            EmployeeEvent::AffiliationAdded { .. }
+            | EmployeeEvent::AffiliationRemoved { .. }
+            | EmployeeEvent::AffiliationChanged { .. }
+            | EmployeeEvent::MemberIdChanged { .. } => {
+                if let Some(record) = self.employees.get_mut(&emp_id) {
+                    apply_change(record, event);
+                }
+                self.index_member(emp_id);
+            }
+            _ => {
+                if let Some(record) = self.employees.get_mut(&emp_id) {
+                    apply_change(record, event);
+                }
+            }
+        }
+    }
+    // follows the union affiliation of the employee
+    fn index_member(&mut self, emp_id: EmployeeId) {
+        // unless passed on to another employee already
+        if let Some(member_id) = self.member_ids.remove(&emp_id) {
+            if self.union_members.get(&member_id) == Some(&emp_id) {
+                self.union_members.remove(&member_id);
+            }
+        }
+        let member_id = self.employees.get(&emp_id).and_then(|r| r.member_id());
+        if let Some(member_id) = member_id {
+            self.union_members.insert(member_id, emp_id);
+            self.member_ids.insert(emp_id, member_id);
+        }
+    }
+}
+
+// every change makes a new version of the employee
+pub fn apply_change(record: &mut EmployeeRecord, event: &EmployeeEvent) {
+    record.version += 1;
+    match event {
+        EmployeeEvent::NameChanged { name, .. } => record.name = name.clone(),
+        EmployeeEvent::AddressChanged { address, .. } => record.address = address.clone(),
+        EmployeeEvent::TimeCardPosted { date, hours, .. } => {
+            if let Some(c) = hourly_mut(&mut record.classification) {
+                c.add_timecard(*date, *hours);
+            }
+        }
+        EmployeeEvent::SalesReceiptPosted { date, amount, .. } => {
+            if let Some(c) = commissioned_mut(&mut record.classification) {
+                c.add_sales_receipt(*date, *amount);
+            }
+        }
+        EmployeeEvent::ClassificationChanged { classification, .. } => {
+            record.classification = classification.clone()
+        }
+        EmployeeEvent::ScheduleChanged { schedule, .. } => record.schedule = schedule.clone(),
+        EmployeeEvent::MethodChanged { method, .. } => record.method = method.clone(),
+        EmployeeEvent::AffiliationAdded { affiliation, .. } => {
+            let name = affiliation.get_name();
+            record.affiliations.retain(|a| a.get_name() != name);
+            record.affiliations.push(affiliation.clone());
+        }
+        EmployeeEvent::AffiliationRemoved { name, .. } => {
+            record.affiliations.retain(|a| a.get_name() != name)
+        }
+        EmployeeEvent::AffiliationChanged { affiliation, .. } => {
+            let name = affiliation.get_name();
+            if let Some(a) = record
+                .affiliations
+                .iter_mut()
+                .find(|a| a.get_name() == name)
+            {
+                *a = affiliation.clone();
+            }
+        }
+        EmployeeEvent::ServiceChargePosted { .. }
+        | EmployeeEvent::ServiceChargeReversed { .. }
+        | EmployeeEvent::UnionDuesChanged { .. }
+        | EmployeeEvent::MemberIdChanged { .. } => {
+            let union = record.affiliations.iter_mut().find_map(|a| match a {
+                AffiliationRecord::Union(u) => Some(u),
+                _ => None,
+            });
+            if let Some(union) = union {
+                apply_union(union, event);
+            }
+        }
+        EmployeeEvent::NetPayFloorChanged { net_pay_floor, .. } => {
+            record.net_pay_floor = *net_pay_floor
+        }
+        EmployeeEvent::ArrearsChanged { arrears, .. } => record.arrears = arrears.clone(),
+        _ => {}
+    }
+}
+
+// the events that turn `old` into `new`, the same employee before and after an update
+pub fn diff(old: &EmployeeRecord, new: &EmployeeRecord) -> Vec<EmployeeEvent> {
+    let emp_id = new.emp_id;
+    let mut events = vec![];
+    if old.name != new.name {
+        events.push(EmployeeEvent::NameChanged {
+            emp_id,
+            name: new.name.clone(),
+        });
+    }
+    if old.address != new.address {
+        events.push(EmployeeEvent::AddressChanged {
+            emp_id,
+            address: new.address.clone(),
+        });
+    }
+    if old.classification != new.classification {
+        events.extend(diff_classification(old, new));
+    }
+    if old.schedule != new.schedule {
+        events.push(EmployeeEvent::ScheduleChanged {
+            emp_id,
+            schedule: new.schedule.clone(),
+        });
+    }
+    if old.method != new.method {
+        events.push(EmployeeEvent::MethodChanged {
+            emp_id,
+            method: new.method.clone(),
+        });
+    }
+    if old.affiliations != new.affiliations {
+        events.extend(diff_affiliations(old, new));
+    }
+    if old.net_pay_floor != new.net_pay_floor {
+        events.push(EmployeeEvent::NetPayFloorChanged {
+            emp_id,
+            net_pay_floor: new.net_pay_floor,
+        });
+    }
+    if old.arrears != new.arrears {
+        events.push(EmployeeEvent::ArrearsChanged {
+            emp_id,
+            arrears: new.arrears.clone(),
+        });
+    }
+    events
+}
+
+fn apply_union(union: &mut UnionAffiliation, event: &EmployeeEvent) {
+    match event {
+        EmployeeEvent::ServiceChargePosted { date, amount, .. } => {
+            union.add_service_charge(*date, *amount);
+        }
+        EmployeeEvent::ServiceChargeReversed {
+            charge_id, date, ..
+        } => union.reverse_service_charge(*charge_id, *date),
+        EmployeeEvent::UnionDuesChanged {
+            dues, effective, ..
+        } => union.change_dues(dues.clone(), *effective),
+        EmployeeEvent::MemberIdChanged { member_id, .. } => union.set_member_id(*member_id),
+        _ => {}
+    }
+}
+
+// the affiliations are told apart by name; those kept in the same order are changed in
+// place, and from the first one out of order on they are added again
+fn diff_affiliations(old: &EmployeeRecord, new: &EmployeeRecord) -> Vec<EmployeeEvent> {
+    let emp_id = new.emp_id;
+    let is_kept = |a: &AffiliationRecord| {
+        new.affiliations
+            .iter()
+            .any(|n| n.get_name() == a.get_name())
+    };
+    let mut events = old
+        .affiliations
+        .iter()
+        .filter(|a| !is_kept(a))
+        .map(|a| EmployeeEvent::AffiliationRemoved {
+            emp_id,
+            name: a.get_name().to_string(),
+        })
+        .collect::<Vec<_>>();
+    let kept = old.affiliations.iter().filter(|a| is_kept(a));
+    let in_order = kept
+        .zip(new.affiliations.iter())
+        .take_while(|(o, n)| o.get_name() == n.get_name())
+        .collect::<Vec<_>>();
+    for &(o, n) in in_order.iter() {
+        if o != n {
+            events.extend(diff_affiliation(emp_id, o, n));
+        }
+    }
+    events.extend(new.affiliations[in_order.len()..].iter().map(|a| {
+        EmployeeEvent::AffiliationAdded {
+            emp_id,
+            affiliation: a.clone(),
+        }
+    }));
+    events
+}
+// the changes of the union made by the transactions are told apart from any other
+// change of an affiliation
+fn diff_affiliation(
+    emp_id: EmployeeId,
+    old: &AffiliationRecord,
+    new: &AffiliationRecord,
+) -> Vec<EmployeeEvent> {
+    if let (AffiliationRecord::Union(o), AffiliationRecord::Union(n)) = (old, new) {
+        if let Some(event) = diff_union(emp_id, o, n) {
+            return vec![event];
+        }
+    }
+    vec![EmployeeEvent::AffiliationChanged {
+        emp_id,
+        affiliation: new.clone(),
+    }]
+}
+// none unless the event applied to `old` makes `new`
+fn diff_union(
+    emp_id: EmployeeId,
+    old: &UnionAffiliation,
+    new: &UnionAffiliation,
+) -> Option<EmployeeEvent> {
+    let mut candidates = vec![];
+    if old.get_member_id() != new.get_member_id() {
+        candidates.push(EmployeeEvent::MemberIdChanged {
+            emp_id,
+            member_id: new.get_member_id(),
+        });
+    }
+    if let Some(sc) = new
+        .get_service_charges()
+        .get(old.get_service_charges().len())
+    {
+        candidates.push(EmployeeEvent::ServiceChargePosted {
+            emp_id,
+            date: sc.get_date(),
+            amount: sc.get_amount(),
+        });
+    }
+    for (o, n) in old
+        .get_service_charges()
+        .iter()
+        .zip(new.get_service_charges())
+    {
+        if let (None, Some(date)) = (o.get_reversed_on(), n.get_reversed_on()) {
+            candidates.push(EmployeeEvent::ServiceChargeReversed {
+                emp_id,
+                charge_id: n.get_id(),
+                date,
+            });
+        }
+    }
+    if old.get_dues() != new.get_dues() {
+        candidates.push(EmployeeEvent::UnionDuesChanged {
+            emp_id,
+            dues: new.get_dues(),
+            effective: None,
+        });
+    }
+    if let Some((effective, dues)) = new.get_dues_changes().pop() {
+        candidates.push(EmployeeEvent::UnionDuesChanged {
+            emp_id,
+            dues,
+            effective: Some(effective),
+        });
+    }
+    candidates.into_iter().find(|event| {
+        let mut expected = old.clone();
+        apply_union(&mut expected, event);
+        expected == *new
+    })
+}
+
+// timecards and sales receipts posted to the same classification, or to a component
+// of a composite one, are told apart from any other change of it
+fn diff_classification(old: &EmployeeRecord, new: &EmployeeRecord) -> Vec<EmployeeEvent> {
+    let emp_id = new.emp_id;
+    if let Some(posted) = posted_timecards(&old.classification, &new.classification) {
+        return posted
+            .into_iter()
+            .map(|(date, hours)| EmployeeEvent::TimeCardPosted {
+                emp_id,
+                date,
+                hours,
+            })
+            .collect();
+    }
+    if let Some(posted) = posted_sales_receipts(&old.classification, &new.classification) {
+        return posted
+            .into_iter()
+            .map(|(date, amount)| EmployeeEvent::SalesReceiptPosted {
+                emp_id,
+                date,
+                amount,
+            })
+            .collect();
+    }
+    vec![EmployeeEvent::ClassificationChanged {
+        emp_id,
+        classification: new.classification.clone(),
+    }]
+}
+
+// the classification itself, otherwise its first component of the kind, as transactions post to it
+fn hourly(classification: &ClassificationRecord) -> Option<&HourlyClassification> {
+    match classification {
+        ClassificationRecord::Hourly(c) => Some(c),
+        ClassificationRecord::Composite { components } => components.iter().find_map(hourly),
+        _ => None,
+    }
+}
+fn hourly_mut(classification: &mut ClassificationRecord) -> Option<&mut HourlyClassification> {
+    match classification {
+        ClassificationRecord::Hourly(c) => Some(c),
+        ClassificationRecord::Composite { components } => {
+            components.iter_mut().find_map(hourly_mut)
+        }
+        _ => None,
+    }
+}
+fn commissioned(classification: &ClassificationRecord) -> Option<&CommissionedClassification> {
+    match classification {
+        ClassificationRecord::Commissioned(c) => Some(c),
+        ClassificationRecord::Composite { components } => components.iter().find_map(commissioned),
+        _ => None,
+    }
+}
+fn commissioned_mut(
+    classification: &mut ClassificationRecord,
+) -> Option<&mut CommissionedClassification> {
+    match classification {
+        ClassificationRecord::Commissioned(c) => Some(c),
+        ClassificationRecord::Composite { components } => {
+            components.iter_mut().find_map(commissioned_mut)
+        }
+        _ => None,
+    }
+}
+
+// none unless posting them to `old` makes `new`
+fn posted_timecards(
+    old: &ClassificationRecord,
+    new: &ClassificationRecord,
+) -> Option<Vec<(NaiveDate, f32)>> {
+    let posted = hourly(new)?
+        .get_timecards()
+        .get(hourly(old)?.get_timecards().len()..)?
+        .to_vec();
+    let mut expected = old.clone();
+    let c = hourly_mut(&mut expected)?;
+    for &(date, hours) in posted.iter() {
+        c.add_timecard(date, hours);
+    }
+    (expected == *new).then_some(posted)
+}
+fn posted_sales_receipts(
+    old: &ClassificationRecord,
+    new: &ClassificationRecord,
+) -> Option<Vec<(NaiveDate, f32)>> {
+    let posted = commissioned(new)?
+        .get_sales_receipts()
+        .get(commissioned(old)?.get_sales_receipts().len()..)?
+        .to_vec();
+    let mut expected = old.clone();
+    let c = commissioned_mut(&mut expected)?;
+    for &(date, amount) in posted.iter() {
+        c.add_sales_receipt(date, amount);
+    }
+    (expected == *new).then_some(posted)
+}
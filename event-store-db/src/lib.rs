@@ -0,0 +1,895 @@
+mod event;
+
+pub use event::{EmployeeEvent, RecordedEvent};
+
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    ops::{Bound, RangeInclusive},
+    rc::Rc,
+};
+
+use dao::{CommitError, DaoError, Database, EmployeeDao, EmployeeQuery};
+use event::State;
+use payroll_domain::{Employee, EmployeeId, MemberId, Paycheck};
+use payroll_impl::record::EmployeeRecord;
+
+#[derive(Debug, Clone, Default)]
+struct Log {
+    // append only
+    events: Vec<RecordedEvent>,
+    // what all the events add up to
+    state: State,
+}
+
+// the context of EventStoreDb; the events of a transaction are appended to the log
+// only when it is committed
+#[derive(Debug, Clone, Default)]
+pub struct EventTx {
+    events: Vec<EmployeeEvent>,
+    // the employees the events have changed, as they add up to in the transaction,
+    // none for a deleted one
+    employees: BTreeMap<EmployeeId, Option<EmployeeRecord>>,
+    paychecks: BTreeMap<EmployeeId, Vec<Paycheck>>,
+    // the stored versions the changed employees were fetched at, none for one
+    // added, which must still be absent
+    versions: HashMap<EmployeeId, Option<u32>>,
+}
+
+// employees are never overwritten; every change is recorded as an event and the
+// employee is rebuilt by replaying them
+#[derive(Debug, Clone)]
+pub struct EventStoreDb {
+    log: Rc<RefCell<Log>>,
+}
+impl Default for EventStoreDb {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl EventStoreDb {
+    pub fn new() -> Self {
+        Self {
+            log: Rc::new(RefCell::new(Log::default())),
+        }
+    }
+    pub fn begin(&self) -> EventTx {
+        EventTx::default()
+    }
+    pub fn commit(&self, tx: EventTx) -> Result<(), DaoError> {
+        self.commit_at(tx, Local::now().naive_local())
+    }
+    // records the events of the transaction as having happened at `recorded_at`;
+    // fails, recording nothing, if an employee changed in the transaction has been
+    // added, updated or deleted by another one committed in the meantime, or if a
+    // member id given in it has been taken by another employee
+    pub fn commit_at(&self, tx: EventTx, recorded_at: NaiveDateTime) -> Result<(), DaoError> {
+        let mut log = self.log.borrow_mut();
+        for (&emp_id, &version) in tx.versions.iter() {
+            let stored = log.state.employees.get(&emp_id).map(|r| r.version);
+            if stored != version {
+                return Err(match version {
+                    Some(version) => DaoError::VersionConflict { emp_id, version },
+                    None => DaoError::EmployeeAlreadyExists(emp_id),
+                });
+            }
+        }
+        // the member ids given in the transaction must not have been taken since
+        for (&emp_id, record) in tx.employees.iter() {
+            let Some(member_id) = record.as_ref().and_then(|r| r.member_id()) else {
+                continue;
+            };
+            let held = log
+                .state
+                .union_members
+                .get(&member_id)
+                .is_some_and(|other| *other != emp_id && !tx.employees.contains_key(other));
+            if held {
+                return Err(DaoError::UnionMemberAlreadyExists(member_id));
+            }
+        }
+        // only the events of the transaction are applied
+        for event in tx.events.iter() {
+            log.state.apply(event);
+        }
+        for event in tx.events {
+            let seq = log.events.len();
+            log.events.push(RecordedEvent {
+                seq,
+                recorded_at,
+                event,
+            });
+        }
+        Ok(())
+    }
+    pub fn rollback(&self, tx: EventTx) {
+        drop(tx);
+    }
+    // runs `f` in a transaction of its own, committed only if it succeeds
+    pub fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut EventTx) -> Result<T, E>,
+        E: CommitError,
+    {
+        let mut tx = self.begin();
+        match f(&mut tx) {
+            Ok(x) => {
+                self.commit(tx).map_err(E::commit_failed)?;
+                Ok(x)
+            }
+            Err(e) => {
+                self.rollback(tx);
+                Err(e)
+            }
+        }
+    }
+
+    // the audit trail
+    pub fn get_events(&self) -> Vec<RecordedEvent> {
+        self.log.borrow().events.clone()
+    }
+    pub fn get_history(&self, emp_id: EmployeeId) -> Vec<RecordedEvent> {
+        self.log
+            .borrow()
+            .events
+            .iter()
+            .filter(|e| e.event.get_emp_id() == emp_id)
+            .cloned()
+            .collect()
+    }
+    // the employee as it was at `at`, e.g. for a dispute about a past paycheck
+    pub fn fetch_as_of(&self, emp_id: EmployeeId, at: NaiveDateTime) -> Option<Employee> {
+        self.state_as_of(at)
+            .employees
+            .remove(&emp_id)
+            .map(EmployeeRecord::into_employee)
+    }
+    pub fn get_paychecks_as_of(&self, emp_id: EmployeeId, at: NaiveDateTime) -> Vec<Paycheck> {
+        self.state_as_of(at)
+            .paychecks
+            .remove(&emp_id)
+            .unwrap_or_default()
+    }
+    fn state_as_of(&self, at: NaiveDateTime) -> State {
+        let mut state = State::default();
+        for e in self.log.borrow().events.iter() {
+            if e.recorded_at <= at {
+                state.apply(&e.event);
+            }
+        }
+        state
+    }
+
+    // appends the event to the transaction, changing the employee as seen from it
+    fn push(&self, tx: &mut EventTx, event: EmployeeEvent) {
+        let emp_id = event.get_emp_id();
+        // paychecks are appended whatever else changes the employee
+        if let EmployeeEvent::PaydayRun { paycheck, .. } = &event {
+            tx.paychecks
+                .entry(emp_id)
+                .or_default()
+                .push(paycheck.clone());
+            tx.events.push(event);
+            return;
+        }
+        if !tx.employees.contains_key(&emp_id) {
+            let stored = self.log.borrow().state.employees.get(&emp_id).cloned();
+            tx.versions
+                .insert(emp_id, stored.as_ref().map(|r| r.version));
+            tx.employees.insert(emp_id, stored);
+        }
+        match &event {
+            EmployeeEvent::EmployeeAdded { record } => {
+                tx.employees.insert(emp_id, Some(record.clone()));
+            }
+            EmployeeEvent::EmployeeDeleted { .. } => {
+                tx.employees.insert(emp_id, None);
+            }
+            _ => {
+                if let Some(Some(record)) = tx.employees.get_mut(&emp_id) {
+                    event::apply_change(record, &event);
+                }
+            }
+        }
+        tx.events.push(event);
+    }
+    // as seen from the transaction; only the employee itself is copied
+    fn employee(&self, tx: &EventTx, emp_id: EmployeeId) -> Option<EmployeeRecord> {
+        match tx.employees.get(&emp_id) {
+            Some(record) => record.clone(),
+            None => self.log.borrow().state.employees.get(&emp_id).cloned(),
+        }
+    }
+    fn union_member(&self, tx: &EventTx, member_id: MemberId) -> Option<EmployeeId> {
+        let changed = tx
+            .employees
+            .iter()
+            .find(|(_, r)| r.as_ref().and_then(|r| r.member_id()) == Some(member_id));
+        if let Some((&emp_id, _)) = changed {
+            return Some(emp_id);
+        }
+        // unless it has been released in the transaction
+        self.log
+            .borrow()
+            .state
+            .union_members
+            .get(&member_id)
+            .filter(|emp_id| !tx.employees.contains_key(emp_id))
+            .copied()
+    }
+    // fails if the member id of the employee is held by another one
+    fn check_member(&self, tx: &EventTx, record: &EmployeeRecord) -> Result<(), DaoError> {
+        let Some(member_id) = record.member_id() else {
+            return Ok(());
+        };
+        match self.union_member(tx, member_id) {
+            Some(emp_id) if emp_id != record.emp_id => {
+                Err(DaoError::UnionMemberAlreadyExists(member_id))
+            }
+            _ => Ok(()),
+        }
+    }
+    // those committed and not changed in the transaction, then those changed in it
+    fn with_employees<T>(
+        &self,
+        tx: &EventTx,
+        f: impl FnOnce(&mut dyn Iterator<Item = &EmployeeRecord>) -> T,
+    ) -> T {
+        let log = self.log.borrow();
+        let committed = log
+            .state
+            .employees
+            .iter()
+            .filter(|(emp_id, _)| !tx.employees.contains_key(emp_id))
+            .map(|(_, r)| r);
+        let changed = tx.employees.values().flatten();
+        f(&mut committed.chain(changed))
+    }
+}
+impl Database for EventStoreDb {
+    type Ctx = EventTx;
+
+    fn dao(&self) -> &impl EmployeeDao<EventTx> {
+        self
+    }
+    fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut EventTx) -> Result<T, E>,
+        E: CommitError,
+    {
+        EventStoreDb::with_transaction(self, f)
+    }
+}
+impl EmployeeDao<EventTx> for EventStoreDb {
+    fn insert(&self, emp: Employee) -> impl tx_rs::Tx<EventTx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let emp_id = emp.get_emp_id();
+            if self.employee(tx, emp_id).is_some() {
+                return Err(DaoError::EmployeeAlreadyExists(emp_id));
+            }
+            let record = EmployeeRecord::from_employee(&emp).map_err(DaoError::unsupported_data)?;
+            self.check_member(tx, &record)?;
+            self.push(tx, EmployeeEvent::EmployeeAdded { record });
+            Ok(emp_id)
+        })
+    }
+    fn delete(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<EventTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            if self.employee(tx, emp_id).is_none() {
+                return Err(DaoError::EmployeeNotFound(emp_id));
+            }
+            self.push(tx, EmployeeEvent::EmployeeDeleted { emp_id });
+            Ok(())
+        })
+    }
+    fn fetch(
+        &self,
+        emp_id: EmployeeId,
+    ) -> impl tx_rs::Tx<EventTx, Item = Employee, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| match self.employee(tx, emp_id) {
+            Some(record) => Ok(record.into_employee()),
+            None => Err(DaoError::EmployeeNotFound(emp_id)),
+        })
+    }
+    fn update(&self, emp: Employee) -> impl tx_rs::Tx<EventTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let emp_id = emp.get_emp_id();
+            let old = self
+                .employee(tx, emp_id)
+                .ok_or(DaoError::EmployeeNotFound(emp_id))?;
+            if old.version != emp.get_version() {
+                return Err(DaoError::VersionConflict {
+                    emp_id,
+                    version: emp.get_version(),
+                });
+            }
+            let new = EmployeeRecord::from_employee(&emp).map_err(DaoError::unsupported_data)?;
+            self.check_member(tx, &new)?;
+            for event in event::diff(&old, &new) {
+                self.push(tx, event);
+            }
+            Ok(())
+        })
+    }
+    fn get_all(&self) -> impl tx_rs::Tx<EventTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let mut records =
+                self.with_employees(tx, |employees| employees.cloned().collect::<Vec<_>>());
+            records.sort_by_key(|r| r.emp_id);
+            Ok(records
+                .into_iter()
+                .map(EmployeeRecord::into_employee)
+                .collect())
+        })
+    }
+    fn get_payable(
+        &self,
+        pay_date: NaiveDate,
+        after: Option<EmployeeId>,
+        limit: usize,
+    ) -> impl tx_rs::Tx<EventTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let from = (
+                after.map_or(Bound::Unbounded, Bound::Excluded),
+                Bound::Unbounded,
+            );
+            let is_payable = |r: &&EmployeeRecord| {
+                r.schedule
+                    .clone()
+                    .into_schedule()
+                    .read()
+                    .unwrap()
+                    .is_pay_date(pay_date)
+            };
+            let log = self.log.borrow();
+            let committed = log
+                .state
+                .employees
+                .range(from)
+                .filter(|(emp_id, _)| !tx.employees.contains_key(emp_id))
+                .map(|(_, r)| r)
+                .filter(is_payable)
+                .take(limit);
+            let changed = tx
+                .employees
+                .range(from)
+                .filter_map(|(_, r)| r.as_ref())
+                .filter(is_payable)
+                .take(limit);
+            let mut records = committed.chain(changed).collect::<Vec<_>>();
+            records.sort_by_key(|r| r.emp_id);
+            records.truncate(limit);
+
+            // only the batch is copied
+            Ok(records
+                .into_iter()
+                .map(|r| r.clone().into_employee())
+                .collect())
+        })
+    }
+    fn find_employees(
+        &self,
+        query: EmployeeQuery,
+    ) -> impl tx_rs::Tx<EventTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let mut records = self.with_employees(tx, |employees| {
+                employees
+                    .filter(|r| {
+                        query.matches_name(&r.name)
+                            && query.matches_classification(r.classification.get_type())
+                            && query.matches_schedule(r.schedule.get_type())
+                            && query.matches_method(r.method.get_type())
+                            && query.matches_union_member(r.member_id().is_some())
+                    })
+                    .cloned()
+                    .collect::<Vec<_>>()
+            });
+            records.sort_by_key(|r| r.emp_id);
+            Ok(records
+                .into_iter()
+                .map(EmployeeRecord::into_employee)
+                .collect())
+        })
+    }
+
+    fn find_union_member(
+        &self,
+        member_id: MemberId,
+    ) -> impl tx_rs::Tx<EventTx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(
+            move |tx: &mut EventTx| match self.union_member(tx, member_id) {
+                Some(emp_id) => Ok(emp_id),
+                None => Err(DaoError::UnionMemberNotFound(member_id)),
+            },
+        )
+    }
+
+    fn record_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        pc: Paycheck,
+    ) -> impl tx_rs::Tx<EventTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            self.push(
+                tx,
+                EmployeeEvent::PaydayRun {
+                    emp_id,
+                    paycheck: pc,
+                },
+            );
+            Ok(())
+        })
+    }
+    fn get_paychecks(
+        &self,
+        emp_id: EmployeeId,
+    ) -> impl tx_rs::Tx<EventTx, Item = Vec<Paycheck>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let log = self.log.borrow();
+            let committed = log.state.paychecks.get(&emp_id).into_iter().flatten();
+            let recorded = tx.paychecks.get(&emp_id).into_iter().flatten();
+            Ok(committed.chain(recorded).cloned().collect())
+        })
+    }
+    fn has_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        period: RangeInclusive<NaiveDate>,
+    ) -> impl tx_rs::Tx<EventTx, Item = bool, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut EventTx| {
+            let log = self.log.borrow();
+            let committed = log.state.paychecks.get(&emp_id).into_iter().flatten();
+            let recorded = tx.paychecks.get(&emp_id).into_iter().flatten();
+            Ok(committed
+                .chain(recorded)
+                .any(|pc| pc.get_pay_period() == period))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_event_store_db {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::sync::{Arc, RwLock};
+    use tx_rs::Tx;
+
+    use payroll_domain::DuesPolicy;
+    use payroll_impl::{
+        affiliation::{GeneralDeduction, UnionAffiliation},
+        classification::{
+            find_classification_mut, CommissionedClassification, CompositeClassification,
+            HourlyClassification, SalariedClassification,
+        },
+        method::HoldMethod,
+        record::{AffiliationRecord, ClassificationRecord},
+        schedule::WeeklySchedule,
+    };
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+    fn at(m: u32, d: u32) -> NaiveDateTime {
+        date(m, d).and_hms_opt(12, 0, 0).unwrap()
+    }
+    fn hourly_emp(emp_id: EmployeeId) -> Employee {
+        Employee::new(
+            emp_id,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(HourlyClassification::new(15.25))),
+            Arc::new(RwLock::new(WeeklySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![],
+        )
+    }
+    fn join_union(mut emp: Employee, member_id: MemberId) -> Employee {
+        emp.add_affiliation(Arc::new(RwLock::new(UnionAffiliation::new(
+            member_id,
+            DuesPolicy::PerFriday { dues: 9.42 },
+        ))));
+        emp
+    }
+    fn post_timecard(emp: &Employee, date: NaiveDate, hours: f32) {
+        emp.get_classification()
+            .write()
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<HourlyClassification>()
+            .unwrap()
+            .add_timecard(date, hours);
+    }
+    fn type_of(e: &RecordedEvent) -> String {
+        serde_json::to_value(&e.event).unwrap()["type"]
+            .as_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_events() {
+        let db = EventStoreDb::new();
+        let mut tx = db.begin();
+        db.insert(hourly_emp(42)).run(&mut tx).unwrap();
+        db.commit_at(tx, at(8, 1)).unwrap();
+
+        let mut tx = db.begin();
+        let mut emp = db.fetch(42).run(&mut tx).unwrap();
+        post_timecard(&emp, date(8, 2), 8.0);
+        emp.set_address("Office");
+        db.update(emp).run(&mut tx).unwrap();
+        db.record_paycheck(42, Paycheck::new(date(7, 27)..=date(8, 2)))
+            .run(&mut tx)
+            .unwrap();
+        db.commit_at(tx, at(8, 2)).unwrap();
+
+        let types = db.get_history(42).iter().map(type_of).collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                "EmployeeAdded",
+                "AddressChanged",
+                "TimeCardPosted",
+                "PaydayRun"
+            ]
+        );
+        assert_eq!(
+            db.get_history(42)[2].event,
+            EmployeeEvent::TimeCardPosted {
+                emp_id: 42,
+                date: date(8, 2),
+                hours: 8.0
+            }
+        );
+    }
+    #[test]
+    fn test_composite_events() {
+        let db = EventStoreDb::new();
+        let mut emp = hourly_emp(42);
+        emp.set_classification(Arc::new(RwLock::new(CompositeClassification::new(vec![
+            Box::new(SalariedClassification::new(1000.0)),
+            Box::new(HourlyClassification::new(15.25)),
+            Box::new(CommissionedClassification::new(0.0, 0.1)),
+        ]))));
+        db.with_transaction(|tx| db.insert(emp).run(tx)).unwrap();
+
+        let result: Result<(), DaoError> = db.with_transaction(|tx| {
+            let emp = db.fetch(42).run(tx)?;
+            {
+                let classification = emp.get_classification();
+                let mut c = classification.write().unwrap();
+                find_classification_mut::<HourlyClassification>(&mut *c)
+                    .unwrap()
+                    .add_timecard(date(8, 2), 8.0);
+            }
+            db.update(emp).run(tx)?;
+            let emp = db.fetch(42).run(tx)?;
+            {
+                let classification = emp.get_classification();
+                let mut c = classification.write().unwrap();
+                find_classification_mut::<CommissionedClassification>(&mut *c)
+                    .unwrap()
+                    .add_sales_receipt(date(8, 2), 500.0);
+            }
+            db.update(emp).run(tx)
+        });
+        assert_eq!(result, Ok(()));
+
+        let history = db.get_history(42);
+        assert_eq!(
+            history.iter().map(type_of).collect::<Vec<_>>(),
+            vec!["EmployeeAdded", "TimeCardPosted", "SalesReceiptPosted"]
+        );
+        assert_eq!(
+            history[2].event,
+            EmployeeEvent::SalesReceiptPosted {
+                emp_id: 42,
+                date: date(8, 2),
+                amount: 500.0
+            }
+        );
+        // replayed into the components they were posted to
+        let mut tx = db.begin();
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        let ClassificationRecord::Composite { components } =
+            EmployeeRecord::from_employee(&emp).unwrap().classification
+        else {
+            panic!("not composite");
+        };
+        assert!(
+            matches!(&components[1], ClassificationRecord::Hourly(c) if c.get_timecards() == [(date(8, 2), 8.0)])
+        );
+        assert!(
+            matches!(&components[2], ClassificationRecord::Commissioned(c) if c.get_sales_receipts() == [(date(8, 2), 500.0)])
+        );
+    }
+    #[test]
+    fn test_affiliation_events() {
+        let db = EventStoreDb::new();
+        let mut tx = db.begin();
+        db.insert(join_union(hourly_emp(42), 7))
+            .run(&mut tx)
+            .unwrap();
+        db.commit_at(tx, at(8, 1)).unwrap();
+        let change = |day: u32, f: &dyn Fn(&mut Employee)| {
+            let mut tx = db.begin();
+            let mut emp = db.fetch(42).run(&mut tx).unwrap();
+            f(&mut emp);
+            db.update(emp).run(&mut tx).unwrap();
+            db.commit_at(tx, at(8, day)).unwrap();
+        };
+        let change_union = |day: u32, f: &dyn Fn(&mut UnionAffiliation)| {
+            change(day, &|emp: &mut Employee| {
+                let union = emp.get_affiliation(UnionAffiliation::NAME).unwrap();
+                let mut union = union.write().unwrap();
+                f(union.as_any_mut().downcast_mut().unwrap());
+            })
+        };
+        change_union(5, &|u| {
+            u.add_service_charge(date(8, 5), 12.5);
+        });
+        change_union(6, &|u| u.reverse_service_charge(1, date(8, 6)));
+        change_union(7, &|u| {
+            u.change_dues(DuesPolicy::PerFriday { dues: 10.0 }, Some(date(9, 1)))
+        });
+        change_union(8, &|u| u.set_member_id(8));
+        change(9, &|emp| {
+            emp.add_affiliation(Arc::new(RwLock::new(GeneralDeduction::new("Loan", 20.0))))
+        });
+        change(10, &|emp| {
+            emp.remove_affiliation(UnionAffiliation::NAME);
+        });
+
+        let types = db.get_history(42).iter().map(type_of).collect::<Vec<_>>();
+        assert_eq!(
+            types,
+            vec![
+                "EmployeeAdded",
+                "ServiceChargePosted",
+                "ServiceChargeReversed",
+                "UnionDuesChanged",
+                "MemberIdChanged",
+                "AffiliationAdded",
+                "AffiliationRemoved",
+            ]
+        );
+        assert_eq!(
+            db.get_history(42)[3].event,
+            EmployeeEvent::UnionDuesChanged {
+                emp_id: 42,
+                dues: DuesPolicy::PerFriday { dues: 10.0 },
+                effective: Some(date(9, 1)),
+            }
+        );
+        let mut tx = db.begin();
+        assert_eq!(
+            db.find_union_member(8).run(&mut tx),
+            Err(DaoError::UnionMemberNotFound(8))
+        );
+        // kept as the events add up to
+        let as_of = |day| {
+            db.fetch_as_of(42, at(8, day))
+                .map(|emp| EmployeeRecord::from_employee(&emp).unwrap())
+        };
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(as_of(31), EmployeeRecord::from_employee(&emp).ok());
+        let union = as_of(8)
+            .unwrap()
+            .affiliations
+            .into_iter()
+            .find_map(|a| match a {
+                AffiliationRecord::Union(u) => Some(u),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(union.get_member_id(), 8);
+        assert_eq!(
+            union.get_service_charge(1).unwrap().get_reversed_on(),
+            Some(date(8, 6))
+        );
+    }
+    #[test]
+    fn test_replay() {
+        let db = EventStoreDb::new();
+        let emp = hourly_emp(42);
+        post_timecard(&emp, date(8, 1), 9.0);
+        db.with_transaction(|tx| db.insert(emp).run(tx)).unwrap();
+        let result: Result<(), DaoError> = db.with_transaction(|tx| {
+            let mut emp = db.fetch(42).run(tx)?;
+            post_timecard(&emp, date(8, 2), 8.0);
+            emp.set_name("Robert");
+            db.update(emp).run(tx)
+        });
+        assert_eq!(result, Ok(()));
+
+        let mut expected = hourly_emp(42);
+        post_timecard(&expected, date(8, 1), 9.0);
+        post_timecard(&expected, date(8, 2), 8.0);
+        expected.set_name("Robert");
+        // a version for each change
+        expected.set_version(2);
+
+        let mut tx = db.begin();
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(
+            EmployeeRecord::from_employee(&emp),
+            EmployeeRecord::from_employee(&expected)
+        );
+    }
+    #[test]
+    fn test_as_of() {
+        let db = EventStoreDb::new();
+        let mut tx = db.begin();
+        db.insert(hourly_emp(42)).run(&mut tx).unwrap();
+        db.commit_at(tx, at(8, 1)).unwrap();
+        let mut tx = db.begin();
+        let mut emp = db.fetch(42).run(&mut tx).unwrap();
+        emp.set_address("Office");
+        db.update(emp).run(&mut tx).unwrap();
+        db.record_paycheck(42, Paycheck::new(date(8, 3)..=date(8, 9)))
+            .run(&mut tx)
+            .unwrap();
+        db.commit_at(tx, at(8, 9)).unwrap();
+        let mut tx = db.begin();
+        db.delete(42).run(&mut tx).unwrap();
+        db.commit_at(tx, at(9, 1)).unwrap();
+
+        assert!(db.fetch_as_of(42, at(7, 31)).is_none());
+        assert_eq!(db.fetch_as_of(42, at(8, 5)).unwrap().get_address(), "Home");
+        assert_eq!(
+            db.fetch_as_of(42, at(8, 31)).unwrap().get_address(),
+            "Office"
+        );
+        assert!(db.fetch_as_of(42, at(9, 1)).is_none());
+        assert_eq!(db.get_paychecks_as_of(42, at(8, 5)).len(), 0);
+        assert_eq!(db.get_paychecks_as_of(42, at(8, 31)).len(), 1);
+    }
+    #[test]
+    fn test_version_conflict() {
+        let db = EventStoreDb::new();
+        db.with_transaction(|tx| db.insert(hourly_emp(42)).run(tx))
+            .unwrap();
+
+        let mut tx1 = db.begin();
+        let mut emp1 = db.fetch(42).run(&mut tx1).unwrap();
+        let mut tx2 = db.begin();
+        let mut emp2 = db.fetch(42).run(&mut tx2).unwrap();
+        emp1.set_name("Robert");
+        db.update(emp1).run(&mut tx1).unwrap();
+        emp2.set_address("Office");
+        db.update(emp2).run(&mut tx2).unwrap();
+        assert_eq!(db.commit_at(tx1, at(8, 1)), Ok(()));
+        assert!(matches!(
+            db.commit_at(tx2, at(8, 1)),
+            Err(DaoError::VersionConflict { .. })
+        ));
+        assert_eq!(db.get_history(42).len(), 2);
+
+        let mut tx = db.begin();
+        let mut stale = db.fetch(42).run(&mut tx).unwrap();
+        stale.set_version(0);
+        assert!(matches!(
+            db.update(stale).run(&mut tx),
+            Err(DaoError::VersionConflict { .. })
+        ));
+    }
+    #[test]
+    fn test_rollback() {
+        let db = EventStoreDb::new();
+        let result = db.with_transaction(|tx| {
+            db.insert(join_union(hourly_emp(42), 7234)).run(tx)?;
+            db.insert(join_union(hourly_emp(43), 7234)).run(tx)
+        });
+        assert!(result.is_err());
+        assert_eq!(db.get_events(), vec![]);
+
+        let mut tx = db.begin();
+        assert!(db.fetch(42).run(&mut tx).is_err());
+        assert!(db.find_union_member(7234).run(&mut tx).is_err());
+    }
+    #[test]
+    fn test_union_member_index() {
+        let db = EventStoreDb::new();
+        db.with_transaction(|tx| {
+            db.insert(join_union(hourly_emp(42), 7)).run(tx)?;
+            db.insert(hourly_emp(43)).run(tx)
+        })
+        .unwrap();
+
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(7).run(&mut tx), Ok(42));
+        let emp = db.fetch(43).run(&mut tx).unwrap();
+        assert_eq!(
+            db.update(join_union(emp, 7)).run(&mut tx),
+            Err(DaoError::UnionMemberAlreadyExists(7))
+        );
+        // released on delete, to be taken by another employee
+        db.delete(42).run(&mut tx).unwrap();
+        let emp = db.fetch(43).run(&mut tx).unwrap();
+        db.update(join_union(emp, 7)).run(&mut tx).unwrap();
+        db.commit(tx).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(7).run(&mut tx), Ok(43));
+
+        // taken by another transaction committed in the meantime
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        db.insert(join_union(hourly_emp(44), 8))
+            .run(&mut tx1)
+            .unwrap();
+        db.insert(join_union(hourly_emp(45), 8))
+            .run(&mut tx2)
+            .unwrap();
+        db.commit(tx1).unwrap();
+        let events = db.get_events().len();
+        assert_eq!(db.commit(tx2), Err(DaoError::UnionMemberAlreadyExists(8)));
+        assert_eq!(db.get_events().len(), events);
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(8).run(&mut tx), Ok(44));
+    }
+    #[test]
+    fn test_concurrent_writes() {
+        let db = EventStoreDb::new();
+
+        // added by both before either commits
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        db.insert(hourly_emp(42)).run(&mut tx1).unwrap();
+        db.insert(hourly_emp(42)).run(&mut tx2).unwrap();
+        assert_eq!(db.commit_at(tx1, at(8, 1)), Ok(()));
+        assert_eq!(
+            db.commit_at(tx2, at(8, 1)),
+            Err(DaoError::EmployeeAlreadyExists(42))
+        );
+
+        // paychecks recorded by both are kept
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        let period = date(8, 3)..=date(8, 9);
+        db.record_paycheck(42, Paycheck::new(period.clone()))
+            .run(&mut tx1)
+            .unwrap();
+        db.record_paycheck(42, Paycheck::new(period))
+            .run(&mut tx2)
+            .unwrap();
+        db.commit_at(tx1, at(8, 9)).unwrap();
+        db.commit_at(tx2, at(8, 9)).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(db.get_paychecks(42).run(&mut tx).unwrap().len(), 2);
+    }
+    #[test]
+    fn test_payable_batches() {
+        let db = EventStoreDb::new();
+        db.with_transaction(|tx| {
+            for emp_id in [1, 3, 5] {
+                db.insert(hourly_emp(emp_id)).run(tx)?;
+            }
+            Ok::<_, DaoError>(())
+        })
+        .unwrap();
+
+        // those added and deleted in the transaction as well as those committed
+        let mut tx = db.begin();
+        db.insert(hourly_emp(2)).run(&mut tx).unwrap();
+        db.insert(hourly_emp(4)).run(&mut tx).unwrap();
+        db.delete(3).run(&mut tx).unwrap();
+        let emp_ids = |after, tx: &mut EventTx| {
+            db.get_payable(date(8, 9), after, 2)
+                .run(tx)
+                .unwrap()
+                .iter()
+                .map(|emp| emp.get_emp_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(emp_ids(None, &mut tx), vec![1, 2]);
+        assert_eq!(emp_ids(Some(2), &mut tx), vec![4, 5]);
+        assert!(emp_ids(Some(5), &mut tx).is_empty());
+        // not paid on a thursday
+        assert!(db
+            .get_payable(date(8, 8), None, 2)
+            .run(&mut tx)
+            .unwrap()
+            .is_empty());
+    }
+}
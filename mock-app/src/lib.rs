@@ -1,24 +1,31 @@
 use std::path::PathBuf;
 
+use dao::Database;
 use mock_db::MockDb;
 use mock_tx_impl::TransactionFactoryImpl;
 use text_parser_tx_source::TextParserTransactionSource;
 use tx_app::{TransactionApplication, TransactionSource};
 
 #[derive(Debug, Clone)]
-pub struct TestPayrollApp {
-    db: MockDb,
+pub struct TestPayrollApp<Db = MockDb> {
+    db: Db,
     file_path: PathBuf,
 }
 impl TestPayrollApp {
     pub fn new(file_name: &str) -> Self {
+        Self::with_db(file_name, MockDb::new())
+    }
+}
+impl<Db: Database + 'static> TestPayrollApp<Db> {
+    // runs the script against `db`, e.g. one restored from a snapshot
+    pub fn with_db(file_name: &str, db: Db) -> Self {
         Self {
-            db: MockDb::new(),
+            db,
             file_path: file_name.into(),
         }
     }
 }
-impl TransactionApplication<()> for TestPayrollApp {
+impl<Db: Database + 'static> TransactionApplication<()> for TestPayrollApp<Db> {
     fn tx_source(&self) -> impl TransactionSource<()> {
         let input = std::fs::read_to_string(&self.file_path).expect("read script file");
         let tx_factory = TransactionFactoryImpl::new(self.db.clone());
@@ -1,133 +1,927 @@
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+mod snapshot;
 
-use dao::{DaoError, EmployeeDao};
+pub use snapshot::{SnapshotError, SNAPSHOT_VERSION};
+
+use chrono::NaiveDate;
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    ops::{Bound, RangeInclusive},
+    rc::Rc,
+};
+
+use dao::{CommitError, DaoError, Database, EmployeeDao, EmployeeQuery};
 use payroll_domain::{Employee, EmployeeId, MemberId, Paycheck};
+use payroll_impl::{
+    affiliation::UnionAffiliation,
+    record::{ClassificationRecord, MethodRecord, ScheduleRecord},
+};
+
+#[derive(Debug, Clone, Default)]
+struct Tables {
+    employees: HashMap<EmployeeId, Employee>,
+    // the employees by the schedule they are paid on, none for an unknown type of it
+    schedules: HashMap<Option<ScheduleRecord>, BTreeSet<EmployeeId>>,
+    // likewise by the type of their classification and of their payment method
+    classifications: HashMap<Option<&'static str>, BTreeSet<EmployeeId>>,
+    methods: HashMap<Option<String>, BTreeSet<EmployeeId>>,
+    // the union members both ways, by the union affiliation of the employees
+    union_members: HashMap<MemberId, EmployeeId>,
+    member_ids: HashMap<EmployeeId, MemberId>,
+    paychecks: HashMap<EmployeeId, Vec<Paycheck>>,
+}
+impl Tables {
+    fn put_employee(&mut self, emp: Employee) {
+        let emp_id = emp.get_emp_id();
+        self.remove_employee(emp_id);
+        self.schedules
+            .entry(schedule_of(&emp))
+            .or_default()
+            .insert(emp_id);
+        self.classifications
+            .entry(classification_of(&emp))
+            .or_default()
+            .insert(emp_id);
+        self.methods
+            .entry(method_of(&emp))
+            .or_default()
+            .insert(emp_id);
+        if let Some(member_id) = UnionAffiliation::member_id_of(&emp) {
+            self.union_members.insert(member_id, emp_id);
+            self.member_ids.insert(emp_id, member_id);
+        }
+        self.employees.insert(emp_id, emp);
+    }
+    fn remove_employee(&mut self, emp_id: EmployeeId) {
+        if let Some(emp) = self.employees.remove(&emp_id) {
+            if let Some(emp_ids) = self.schedules.get_mut(&schedule_of(&emp)) {
+                emp_ids.remove(&emp_id);
+            }
+            if let Some(emp_ids) = self.classifications.get_mut(&classification_of(&emp)) {
+                emp_ids.remove(&emp_id);
+            }
+            if let Some(emp_ids) = self.methods.get_mut(&method_of(&emp)) {
+                emp_ids.remove(&emp_id);
+            }
+        }
+        // unless passed on to another employee already put
+        if let Some(member_id) = self.member_ids.remove(&emp_id) {
+            if self.union_members.get(&member_id) == Some(&emp_id) {
+                self.union_members.remove(&member_id);
+            }
+        }
+    }
+    // those meeting the conditions of the query on the types, by intersecting the
+    // indexes; none if it has no such condition
+    fn find_by_types(&self, query: &EmployeeQuery) -> Option<Vec<EmployeeId>> {
+        let mut indexes = vec![];
+        if let Some(kind) = query.classification {
+            indexes.push(self.classifications.get(&Some(kind.get_type())));
+        }
+        if let Some(kind) = query.schedule {
+            let emp_ids = self.schedules.iter().find_map(|(schedule, emp_ids)| {
+                schedule
+                    .as_ref()
+                    .filter(|s| s.get_type() == kind.get_type())
+                    .map(|_| emp_ids)
+            });
+            indexes.push(emp_ids);
+        }
+        if let Some(kind) = query.method {
+            indexes.push(self.methods.get(&Some(kind.get_type().to_string())));
+        }
+        if indexes.is_empty() {
+            return None;
+        }
+        let Some(mut indexes) = indexes.into_iter().collect::<Option<Vec<_>>>() else {
+            return Some(vec![]);
+        };
+        indexes.sort_by_key(|emp_ids| emp_ids.len());
+        let (smallest, others) = indexes.split_first()?;
+        Some(
+            smallest
+                .iter()
+                .filter(|emp_id| others.iter().all(|emp_ids| emp_ids.contains(emp_id)))
+                .copied()
+                .collect(),
+        )
+    }
+}
+fn schedule_of(emp: &Employee) -> Option<ScheduleRecord> {
+    ScheduleRecord::from_schedule(&*emp.get_schedule().read().unwrap()).ok()
+}
+fn classification_of(emp: &Employee) -> Option<&'static str> {
+    ClassificationRecord::type_of(&*emp.get_classification().read().unwrap())
+}
+fn method_of(emp: &Employee) -> Option<String> {
+    MethodRecord::from_method(&*emp.get_method().read().unwrap())
+        .ok()
+        .map(|r| r.get_type().to_string())
+}
+
+// the context of MockDb; the changes made in a transaction are kept here, visible
+// only to it until committed, and dropping it rolls them back
+#[derive(Debug, Clone, Default)]
+pub struct MockTx {
+    // none for a removed one
+    employees: BTreeMap<EmployeeId, Option<Employee>>,
+    // the changes of the union members by those of the employees, none for a removed one
+    union_members: HashMap<MemberId, Option<EmployeeId>>,
+    member_ids: HashMap<EmployeeId, Option<MemberId>>,
+    // appended to those committed
+    paychecks: HashMap<EmployeeId, Vec<Paycheck>>,
+    // the stored versions the changed employees were fetched at, none for one
+    // inserted, which must still be absent
+    versions: HashMap<EmployeeId, Option<u32>>,
+}
 
 #[derive(Debug, Clone)]
 pub struct MockDb {
-    employees: Rc<RefCell<HashMap<EmployeeId, Employee>>>,
-    union_members: Rc<RefCell<HashMap<MemberId, EmployeeId>>>,
-    paychecks: Rc<RefCell<HashMap<EmployeeId, Vec<Paycheck>>>>,
+    // committed
+    tables: Rc<RefCell<Tables>>,
 }
 impl MockDb {
     pub fn new() -> Self {
         Self {
-            employees: Rc::new(RefCell::new(HashMap::new())),
-            union_members: Rc::new(RefCell::new(HashMap::new())),
-            paychecks: Rc::new(RefCell::new(HashMap::new())),
+            tables: Rc::new(RefCell::new(Tables::default())),
         }
     }
+    pub fn begin(&self) -> MockTx {
+        MockTx::default()
+    }
+    // fails, changing nothing, if an employee changed in the transaction has been
+    // inserted, updated or deleted by another one committed in the meantime
+    pub fn commit(&self, tx: MockTx) -> Result<(), DaoError> {
+        let mut tables = self.tables.borrow_mut();
+        for (&emp_id, &version) in tx.versions.iter() {
+            let stored = tables.employees.get(&emp_id).map(|e| e.get_version());
+            if stored != version {
+                return Err(match version {
+                    Some(version) => DaoError::VersionConflict { emp_id, version },
+                    None => DaoError::EmployeeAlreadyExists(emp_id),
+                });
+            }
+        }
+        // the member id has been taken by another employee in the meantime
+        for (&member_id, &emp_id) in tx.union_members.iter() {
+            let Some(emp_id) = emp_id else {
+                continue;
+            };
+            let held = tables
+                .union_members
+                .get(&member_id)
+                .is_some_and(|other| *other != emp_id && !tx.member_ids.contains_key(other));
+            if held {
+                return Err(DaoError::UnionMemberAlreadyExists(member_id));
+            }
+        }
+        for (emp_id, emp) in tx.employees {
+            match emp {
+                Some(emp) => tables.put_employee(emp),
+                None => tables.remove_employee(emp_id),
+            };
+        }
+        for (emp_id, paychecks) in tx.paychecks {
+            tables
+                .paychecks
+                .entry(emp_id)
+                .or_default()
+                .extend(paychecks);
+        }
+        Ok(())
+    }
+    pub fn rollback(&self, tx: MockTx) {
+        drop(tx);
+    }
+    // runs `f` in a transaction of its own, committed only if it succeeds
+    pub fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut MockTx) -> Result<T, E>,
+        E: CommitError,
+    {
+        let mut tx = self.begin();
+        match f(&mut tx) {
+            Ok(x) => {
+                self.commit(tx).map_err(E::commit_failed)?;
+                Ok(x)
+            }
+            Err(e) => {
+                self.rollback(tx);
+                Err(e)
+            }
+        }
+    }
+
+    // as seen from the transaction
+    fn get_employee(&self, tx: &MockTx, emp_id: EmployeeId) -> Option<Employee> {
+        match tx.employees.get(&emp_id) {
+            Some(emp) => emp.clone(),
+            None => self.tables.borrow().employees.get(&emp_id).cloned(),
+        }
+    }
+    fn get_union_member(&self, tx: &MockTx, member_id: MemberId) -> Option<EmployeeId> {
+        match tx.union_members.get(&member_id) {
+            Some(emp_id) => *emp_id,
+            None => self.tables.borrow().union_members.get(&member_id).cloned(),
+        }
+    }
+    fn get_member_id(&self, tx: &MockTx, emp_id: EmployeeId) -> Option<MemberId> {
+        match tx.member_ids.get(&emp_id) {
+            Some(member_id) => *member_id,
+            None => self.tables.borrow().member_ids.get(&emp_id).cloned(),
+        }
+    }
+    // follows the union affiliation of the employee, none for one deleted or not in the
+    // union; fails, changing nothing, if the member id is held by another employee
+    fn index_member(
+        &self,
+        tx: &mut MockTx,
+        emp_id: EmployeeId,
+        member_id: Option<MemberId>,
+    ) -> Result<(), DaoError> {
+        if let Some(member_id) = member_id {
+            if self
+                .get_union_member(tx, member_id)
+                .is_some_and(|other| other != emp_id)
+            {
+                return Err(DaoError::UnionMemberAlreadyExists(member_id));
+            }
+        }
+        let old = self.get_member_id(tx, emp_id);
+        if old == member_id {
+            return Ok(());
+        }
+        if let Some(old) = old {
+            tx.union_members.insert(old, None);
+        }
+        if let Some(member_id) = member_id {
+            tx.union_members.insert(member_id, Some(emp_id));
+        }
+        tx.member_ids.insert(emp_id, member_id);
+        Ok(())
+    }
+    fn paychecks(&self, tx: &MockTx, emp_id: EmployeeId) -> Vec<Paycheck> {
+        let tables = self.tables.borrow();
+        let committed = tables.paychecks.get(&emp_id).into_iter().flatten();
+        let appended = tx.paychecks.get(&emp_id).into_iter().flatten();
+        committed.chain(appended).cloned().collect()
+    }
+    // the version of the stored employee is checked again on commit
+    fn track_version(&self, tx: &mut MockTx, emp_id: EmployeeId, version: Option<u32>) {
+        if !tx.employees.contains_key(&emp_id) {
+            tx.versions.insert(emp_id, version);
+        }
+    }
+}
+fn is_match(query: &EmployeeQuery, emp: &Employee, union_member: bool) -> bool {
+    query.matches_name(emp.get_name())
+        && ClassificationRecord::type_of(&*emp.get_classification().read().unwrap())
+            .map_or(query.classification.is_none(), |t| {
+                query.matches_classification(t)
+            })
+        && ScheduleRecord::from_schedule(&*emp.get_schedule().read().unwrap())
+            .map_or(query.schedule.is_none(), |r| {
+                query.matches_schedule(r.get_type())
+            })
+        && MethodRecord::from_method(&*emp.get_method().read().unwrap())
+            .map_or(query.method.is_none(), |r| {
+                query.matches_method(r.get_type())
+            })
+        && query.matches_union_member(union_member)
+}
+
+impl Database for MockDb {
+    type Ctx = MockTx;
+
+    fn dao(&self) -> &impl EmployeeDao<MockTx> {
+        self
+    }
+    fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut MockTx) -> Result<T, E>,
+        E: CommitError,
+    {
+        MockDb::with_transaction(self, f)
+    }
 }
-impl EmployeeDao<()> for MockDb {
-    fn insert(&self, emp: Employee) -> impl tx_rs::Tx<(), Item = EmployeeId, Err = DaoError> {
-        tx_rs::with_tx(move |_| {
+impl EmployeeDao<MockTx> for MockDb {
+    fn insert(&self, emp: Employee) -> impl tx_rs::Tx<MockTx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
             let emp_id = emp.get_emp_id();
-            if self.employees.borrow().contains_key(&emp_id) {
-                return Err(DaoError::InsertError(format!(
-                    "emp_id={} already exists",
-                    emp_id
-                )));
+            if self.get_employee(tx, emp_id).is_some() {
+                return Err(DaoError::EmployeeAlreadyExists(emp_id));
             }
-            self.employees.borrow_mut().insert(emp_id, emp);
+            self.index_member(tx, emp_id, UnionAffiliation::member_id_of(&emp))?;
+            self.track_version(tx, emp_id, None);
+            tx.employees.insert(emp_id, Some(emp));
             Ok(emp_id)
         })
     }
-    fn delete(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<(), Item = (), Err = DaoError> {
-        tx_rs::with_tx(move |_| {
-            if self.employees.borrow_mut().remove(&emp_id).is_none() {
-                return Err(DaoError::DeleteError(format!(
-                    "emp_id={} not found",
-                    emp_id
-                )));
-            }
+    fn delete(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<MockTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            let Some(stored) = self.get_employee(tx, emp_id) else {
+                return Err(DaoError::EmployeeNotFound(emp_id));
+            };
+            self.index_member(tx, emp_id, None)?;
+            self.track_version(tx, emp_id, Some(stored.get_version()));
+            tx.employees.insert(emp_id, None);
             Ok(())
         })
     }
-    fn fetch(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<(), Item = Employee, Err = DaoError> {
-        tx_rs::with_tx(move |_| match self.employees.borrow().get(&emp_id) {
-            Some(emp) => Ok(emp.clone()),
-            None => Err(DaoError::FetchError(format!("emp_id={} not found", emp_id))),
+    fn fetch(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<MockTx, Item = Employee, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| match self.get_employee(tx, emp_id) {
+            Some(emp) => Ok(emp),
+            None => Err(DaoError::EmployeeNotFound(emp_id)),
         })
     }
-    fn update(&self, emp: Employee) -> impl tx_rs::Tx<(), Item = (), Err = DaoError> {
-        tx_rs::with_tx(move |_| {
+    fn update(&self, mut emp: Employee) -> impl tx_rs::Tx<MockTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
             let emp_id = emp.get_emp_id();
-            if !self.employees.borrow().contains_key(&emp_id) {
-                return Err(DaoError::UpdateError(format!(
-                    "emp_id={} not found",
-                    emp_id
-                )));
+            let Some(stored) = self.get_employee(tx, emp_id) else {
+                return Err(DaoError::EmployeeNotFound(emp_id));
+            };
+            if stored.get_version() != emp.get_version() {
+                return Err(DaoError::VersionConflict {
+                    emp_id,
+                    version: emp.get_version(),
+                });
             }
-            self.employees.borrow_mut().insert(emp_id, emp);
+            self.index_member(tx, emp_id, UnionAffiliation::member_id_of(&emp))?;
+            self.track_version(tx, emp_id, Some(stored.get_version()));
+            emp.set_version(stored.get_version() + 1);
+            tx.employees.insert(emp_id, Some(emp));
             Ok(())
         })
     }
-    fn get_all(&self) -> impl tx_rs::Tx<(), Item = Vec<Employee>, Err = DaoError> {
-        tx_rs::with_tx(move |_| Ok(self.employees.borrow().values().cloned().collect()))
+    fn get_all(&self) -> impl tx_rs::Tx<MockTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            let mut employees = self.tables.borrow().employees.clone();
+            for (&emp_id, emp) in tx.employees.iter() {
+                match emp {
+                    Some(emp) => employees.insert(emp_id, emp.clone()),
+                    None => employees.remove(&emp_id),
+                };
+            }
+            Ok(employees.into_values().collect())
+        })
     }
-
-    fn add_union_member(
+    fn find_employees(
         &self,
-        member_id: MemberId,
-        emp_id: EmployeeId,
-    ) -> impl tx_rs::Tx<(), Item = (), Err = DaoError> {
-        tx_rs::with_tx(move |_| {
-            if self.union_members.borrow().contains_key(&member_id) {
-                return Err(DaoError::InsertError(format!(
-                    "member_id={} already exists",
-                    member_id
-                )));
-            }
-            if self.union_members.borrow().values().any(|&v| v == emp_id) {
-                return Err(DaoError::InsertError(format!(
-                    "emp_id={} already exists",
-                    emp_id
-                )));
-            }
-            self.union_members.borrow_mut().insert(member_id, emp_id);
-            Ok(())
+        query: EmployeeQuery,
+    ) -> impl tx_rs::Tx<MockTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            let tables = self.tables.borrow();
+            // only the name is to be scanned for among those committed
+            let committed = match (tables.find_by_types(&query), query.union_member) {
+                (Some(emp_ids), _) => emp_ids,
+                (None, Some(true)) => tables.member_ids.keys().copied().collect(),
+                (None, _) => tables.employees.keys().copied().collect(),
+            };
+            let committed = committed
+                .into_iter()
+                .filter(|emp_id| !tx.employees.contains_key(emp_id))
+                .filter(|emp_id| query.matches_union_member(tables.member_ids.contains_key(emp_id)))
+                .map(|emp_id| &tables.employees[&emp_id])
+                .filter(|emp| query.matches_name(emp.get_name()));
+            // those changed in the transaction, not deleted
+            let uncommitted = tx
+                .employees
+                .iter()
+                .filter_map(|(&emp_id, emp)| emp.as_ref().map(|emp| (emp_id, emp)))
+                .filter(|(emp_id, emp)| {
+                    is_match(&query, emp, self.get_member_id(tx, *emp_id).is_some())
+                })
+                .map(|(_, emp)| emp);
+            // only those found are copied
+            let mut employees = committed.chain(uncommitted).cloned().collect::<Vec<_>>();
+            employees.sort_by_key(|emp| emp.get_emp_id());
+            Ok(employees)
         })
     }
-    fn remove_union_member(
+    fn get_payable(
         &self,
-        member_id: MemberId,
-    ) -> impl tx_rs::Tx<(), Item = (), Err = DaoError> {
-        tx_rs::with_tx(move |_| {
-            if self.union_members.borrow_mut().remove(&member_id).is_none() {
-                return Err(DaoError::DeleteError(format!(
-                    "member_id={} not found",
-                    member_id
-                )));
+        pay_date: NaiveDate,
+        after: Option<EmployeeId>,
+        limit: usize,
+    ) -> impl tx_rs::Tx<MockTx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            let tables = self.tables.borrow();
+            let from = after.map_or(Bound::Unbounded, Bound::Excluded);
+            let mut emp_ids = vec![];
+            for (schedule, ids) in tables.schedules.iter() {
+                // all or none of those on a known schedule are paid on the date
+                if let Some(schedule) = schedule {
+                    if !schedule
+                        .clone()
+                        .into_schedule()
+                        .read()
+                        .unwrap()
+                        .is_pay_date(pay_date)
+                    {
+                        continue;
+                    }
+                }
+                let payable = ids
+                    .range((from, Bound::Unbounded))
+                    .filter(|emp_id| !tx.employees.contains_key(emp_id))
+                    .filter(|emp_id| {
+                        schedule.is_some() || tables.employees[emp_id].is_pay_date(pay_date)
+                    })
+                    .take(limit);
+                emp_ids.extend(payable);
             }
-            Ok(())
+            // those changed in the transaction, not deleted
+            let changed = tx
+                .employees
+                .range((from, Bound::Unbounded))
+                .filter(|(_, emp)| emp.as_ref().is_some_and(|emp| emp.is_pay_date(pay_date)))
+                .map(|(emp_id, _)| emp_id)
+                .take(limit);
+            emp_ids.extend(changed);
+            emp_ids.sort();
+            emp_ids.truncate(limit);
+
+            // only the batch is copied
+            Ok(emp_ids
+                .into_iter()
+                .map(|emp_id| match tx.employees.get(emp_id) {
+                    Some(Some(emp)) => emp.clone(),
+                    _ => tables.employees[emp_id].clone(),
+                })
+                .collect())
         })
     }
 
     fn find_union_member(
         &self,
         member_id: MemberId,
-    ) -> impl tx_rs::Tx<(), Item = EmployeeId, Err = DaoError> {
-        tx_rs::with_tx(move |_| match self.union_members.borrow().get(&member_id) {
-            Some(&emp_id) => Ok(emp_id),
-            None => Err(DaoError::FetchError(format!(
-                "member_id={} not found",
-                member_id
-            ))),
-        })
+    ) -> impl tx_rs::Tx<MockTx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(
+            move |tx: &mut MockTx| match self.get_union_member(tx, member_id) {
+                Some(emp_id) => Ok(emp_id),
+                None => Err(DaoError::UnionMemberNotFound(member_id)),
+            },
+        )
     }
 
     fn record_paycheck(
         &self,
         emp_id: EmployeeId,
         pc: Paycheck,
-    ) -> impl tx_rs::Tx<(), Item = (), Err = DaoError> {
-        tx_rs::with_tx(move |_| {
-            self.paychecks
-                .borrow_mut()
-                .entry(emp_id)
-                .or_insert(vec![])
-                .push(pc);
+    ) -> impl tx_rs::Tx<MockTx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            tx.paychecks.entry(emp_id).or_default().push(pc);
+            Ok(())
+        })
+    }
+    fn get_paychecks(
+        &self,
+        emp_id: EmployeeId,
+    ) -> impl tx_rs::Tx<MockTx, Item = Vec<Paycheck>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| Ok(self.paychecks(tx, emp_id)))
+    }
+    fn has_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        period: RangeInclusive<NaiveDate>,
+    ) -> impl tx_rs::Tx<MockTx, Item = bool, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut MockTx| {
+            let tables = self.tables.borrow();
+            let committed = tables.paychecks.get(&emp_id).into_iter().flatten();
+            let appended = tx.paychecks.get(&emp_id).into_iter().flatten();
+            Ok(committed
+                .chain(appended)
+                .any(|pc| pc.get_pay_period() == period))
+        })
+    }
+}
+#[cfg(test)]
+mod test_mock_db {
+    use super::*;
+    use chrono::NaiveDate;
+    use dao::{ClassificationKind, MethodKind, ScheduleKind};
+    use std::sync::{Arc, RwLock};
+    use tx_rs::Tx;
+
+    use payroll_domain::DuesPolicy;
+    use payroll_impl::{
+        classification::{HourlyClassification, SalariedClassification},
+        method::HoldMethod,
+        schedule::{MonthlySchedule, WeeklySchedule},
+    };
+
+    fn salaried_emp(emp_id: EmployeeId) -> Employee {
+        Employee::new(
+            emp_id,
+            "Alice",
+            "Home",
+            Arc::new(RwLock::new(SalariedClassification::new(1020.85))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![],
+        )
+    }
+
+    fn hourly_emp(emp_id: EmployeeId) -> Employee {
+        Employee::new(
+            emp_id,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(HourlyClassification::new(15.25))),
+            Arc::new(RwLock::new(WeeklySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![],
+        )
+    }
+
+    fn join_union(mut emp: Employee, member_id: MemberId) -> Employee {
+        emp.add_affiliation(Arc::new(RwLock::new(UnionAffiliation::new(
+            member_id,
+            DuesPolicy::PerFriday { dues: 9.42 },
+        ))));
+        emp
+    }
+
+    #[test]
+    fn test_commit() {
+        let db = MockDb::new();
+        let result: Result<(), DaoError> = db.with_transaction(|tx| {
+            db.insert(join_union(salaried_emp(42), 7234)).run(tx)?;
+            // visible to the transaction itself
+            assert_eq!(db.find_union_member(7234).run(tx), Ok(42));
             Ok(())
+        });
+        assert_eq!(result, Ok(()));
+
+        let mut tx = db.begin();
+        assert!(db.fetch(42).run(&mut tx).is_ok());
+        assert_eq!(db.find_union_member(7234).run(&mut tx), Ok(42));
+    }
+    #[test]
+    fn test_rollback() {
+        let db = MockDb::new();
+        let result = db.with_transaction(|tx| {
+            db.insert(join_union(salaried_emp(42), 7234)).run(tx)?;
+            // no such employee
+            db.update(salaried_emp(43)).run(tx)
+        });
+        assert!(result.is_err());
+
+        let mut tx = db.begin();
+        assert!(db.find_union_member(7234).run(&mut tx).is_err());
+        assert_eq!(
+            db.insert(join_union(salaried_emp(57), 7234)).run(&mut tx),
+            Ok(57)
+        );
+    }
+    #[test]
+    fn test_union_member_index() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| {
+            db.insert(join_union(salaried_emp(42), 7)).run(tx)?;
+            db.insert(hourly_emp(57)).run(tx)
         })
+        .unwrap();
+
+        let mut tx = db.begin();
+        // follows the member id of the affiliation
+        let mut emp = db.fetch(42).run(&mut tx).unwrap();
+        emp.remove_affiliation(UnionAffiliation::NAME);
+        db.update(join_union(emp, 8)).run(&mut tx).unwrap();
+        assert!(db.find_union_member(7).run(&mut tx).is_err());
+        assert_eq!(db.find_union_member(8).run(&mut tx), Ok(42));
+        // held by another employee
+        let emp = db.fetch(57).run(&mut tx).unwrap();
+        assert_eq!(
+            db.update(join_union(emp, 8)).run(&mut tx),
+            Err(DaoError::UnionMemberAlreadyExists(8))
+        );
+        assert!(UnionAffiliation::member_id_of(&db.fetch(57).run(&mut tx).unwrap()).is_none());
+        // released on delete
+        db.delete(42).run(&mut tx).unwrap();
+        assert!(db.find_union_member(8).run(&mut tx).is_err());
+        let emp = db.fetch(57).run(&mut tx).unwrap();
+        db.update(join_union(emp, 8)).run(&mut tx).unwrap();
+        db.commit(tx).unwrap();
+
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(8).run(&mut tx), Ok(57));
+        assert!(db.find_union_member(7).run(&mut tx).is_err());
+
+        // passed on to another employee in the same transaction
+        let mut emp = db.fetch(57).run(&mut tx).unwrap();
+        emp.remove_affiliation(UnionAffiliation::NAME);
+        db.update(join_union(emp, 10)).run(&mut tx).unwrap();
+        db.insert(join_union(hourly_emp(60), 8))
+            .run(&mut tx)
+            .unwrap();
+        db.commit(tx).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(8).run(&mut tx), Ok(60));
+        assert_eq!(db.find_union_member(10).run(&mut tx), Ok(57));
+
+        // taken by another transaction committed in the meantime
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        db.insert(join_union(hourly_emp(58), 9))
+            .run(&mut tx1)
+            .unwrap();
+        db.insert(join_union(hourly_emp(59), 9))
+            .run(&mut tx2)
+            .unwrap();
+        db.commit(tx1).unwrap();
+        assert_eq!(db.commit(tx2), Err(DaoError::UnionMemberAlreadyExists(9)));
+        let mut tx = db.begin();
+        assert_eq!(db.find_union_member(9).run(&mut tx), Ok(58));
+        assert!(db.fetch(59).run(&mut tx).is_err());
+    }
+    #[test]
+    fn test_uncommitted() {
+        let db = MockDb::new();
+        let period = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()
+            ..=NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+        let mut tx1 = db.begin();
+        db.insert(salaried_emp(42)).run(&mut tx1).unwrap();
+        db.record_paycheck(42, Paycheck::new(period.clone()))
+            .run(&mut tx1)
+            .unwrap();
+        assert_eq!(db.has_paycheck(42, period.clone()).run(&mut tx1), Ok(true));
+
+        let mut tx2 = db.begin();
+        assert!(db.fetch(42).run(&mut tx2).is_err());
+        assert_eq!(db.get_paychecks(42).run(&mut tx2), Ok(vec![]));
+        assert_eq!(db.has_paycheck(42, period.clone()).run(&mut tx2), Ok(false));
+
+        db.commit(tx1).unwrap();
+        assert!(db.fetch(42).run(&mut tx2).is_ok());
+        assert_eq!(db.get_paychecks(42).run(&mut tx2).unwrap().len(), 1);
+        assert_eq!(db.has_paycheck(42, period).run(&mut tx2), Ok(true));
+    }
+    #[test]
+    fn test_isolation() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| db.insert(hourly_emp(42)).run(tx))
+            .unwrap();
+
+        let result: Result<(), DaoError> = db.with_transaction(|tx| {
+            let emp = db.fetch(42).run(tx)?;
+            // changed but never updated
+            emp.get_classification()
+                .write()
+                .unwrap()
+                .as_any_mut()
+                .downcast_mut::<HourlyClassification>()
+                .unwrap()
+                .add_timecard(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), 8.0);
+            Ok(())
+        });
+        assert_eq!(result, Ok(()));
+
+        let mut tx = db.begin();
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(
+            emp.get_classification()
+                .read()
+                .unwrap()
+                .as_any()
+                .downcast_ref::<HourlyClassification>(),
+            Some(&HourlyClassification::new(15.25))
+        );
+    }
+    #[test]
+    fn test_version_conflict() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| db.insert(salaried_emp(42)).run(tx))
+            .unwrap();
+
+        // updated by both before either commits
+        let mut tx1 = db.begin();
+        let mut emp1 = db.fetch(42).run(&mut tx1).unwrap();
+        let mut tx2 = db.begin();
+        let mut emp2 = db.fetch(42).run(&mut tx2).unwrap();
+        emp1.set_name("Bob");
+        db.update(emp1).run(&mut tx1).unwrap();
+        emp2.set_address("Office");
+        db.update(emp2).run(&mut tx2).unwrap();
+        assert_eq!(db.commit(tx1), Ok(()));
+        assert!(matches!(
+            db.commit(tx2),
+            Err(DaoError::VersionConflict { .. })
+        ));
+
+        // updated after the other has committed
+        let mut tx = db.begin();
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(emp.get_version(), 1);
+        assert_eq!(emp.get_address(), "Home");
+        let mut stale = emp.clone();
+        db.update(emp).run(&mut tx).unwrap();
+        stale.set_address("Office");
+        assert!(matches!(
+            db.update(stale).run(&mut tx),
+            Err(DaoError::VersionConflict { .. })
+        ));
+    }
+    #[test]
+    fn test_concurrent_writes() {
+        let db = MockDb::new();
+        let period = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap()
+            ..=NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+
+        // inserted by both before either commits
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        db.insert(salaried_emp(42)).run(&mut tx1).unwrap();
+        db.insert(hourly_emp(42)).run(&mut tx2).unwrap();
+        assert_eq!(db.commit(tx1), Ok(()));
+        assert_eq!(db.commit(tx2), Err(DaoError::EmployeeAlreadyExists(42)));
+        let mut tx = db.begin();
+        assert_eq!(db.fetch(42).run(&mut tx).unwrap().get_name(), "Alice");
+
+        // paychecks recorded by both are kept
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        db.record_paycheck(42, Paycheck::new(period.clone()))
+            .run(&mut tx1)
+            .unwrap();
+        db.record_paycheck(42, Paycheck::new(period.clone()))
+            .run(&mut tx2)
+            .unwrap();
+        db.commit(tx1).unwrap();
+        db.commit(tx2).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(db.get_paychecks(42).run(&mut tx).unwrap().len(), 2);
+
+        // deleted after the other has updated it
+        let mut tx1 = db.begin();
+        let mut tx2 = db.begin();
+        let mut emp = db.fetch(42).run(&mut tx1).unwrap();
+        emp.set_name("Bob");
+        db.update(emp).run(&mut tx1).unwrap();
+        db.delete(42).run(&mut tx2).unwrap();
+        db.commit(tx1).unwrap();
+        assert!(matches!(
+            db.commit(tx2),
+            Err(DaoError::VersionConflict { .. })
+        ));
+    }
+
+    #[test]
+    fn test_error_kinds() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| db.insert(join_union(salaried_emp(42), 7)).run(tx))
+            .unwrap();
+
+        let mut tx = db.begin();
+        let err = db.insert(hourly_emp(42)).run(&mut tx).unwrap_err();
+        assert_eq!(err, DaoError::EmployeeAlreadyExists(42));
+        assert_eq!(err.code(), "dao.employee_already_exists");
+        let err = db.fetch(43).run(&mut tx).unwrap_err();
+        assert_eq!(err, DaoError::EmployeeNotFound(43));
+        assert_eq!(err.code(), "dao.employee_not_found");
+        assert_eq!(
+            db.insert(join_union(hourly_emp(43), 7)).run(&mut tx),
+            Err(DaoError::UnionMemberAlreadyExists(7))
+        );
+        assert_eq!(
+            db.find_union_member(8).run(&mut tx),
+            Err(DaoError::UnionMemberNotFound(8))
+        );
+    }
+
+    #[test]
+    fn test_find_employees() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| {
+            db.insert(join_union(salaried_emp(42), 7)).run(tx)?;
+            db.insert(hourly_emp(57)).run(tx)
+        })
+        .unwrap();
+
+        let mut tx = db.begin();
+        let mut emp = hourly_emp(58);
+        emp.set_name("Bobby");
+        db.insert(emp).run(&mut tx).unwrap();
+        let find = |tx: &mut MockTx, query: EmployeeQuery| {
+            db.find_employees(query)
+                .run(tx)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_emp_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(find(&mut tx, EmployeeQuery::default()), vec![42, 57, 58]);
+        let hourly = EmployeeQuery {
+            classification: Some(ClassificationKind::Hourly),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut tx, hourly.clone()), vec![57, 58]);
+        let query = EmployeeQuery {
+            name: Some("Bobb".to_string()),
+            union_member: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut tx, query), vec![58]);
+        let query = EmployeeQuery {
+            union_member: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut tx, query), vec![42]);
+        let query = EmployeeQuery {
+            schedule: Some(ScheduleKind::Monthly),
+            method: Some(MethodKind::Hold),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut tx, query), vec![42]);
+        let query = EmployeeQuery {
+            method: Some(MethodKind::Mail),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut tx, query), Vec::<EmployeeId>::new());
+
+        // as seen from the transaction
+        db.delete(57).run(&mut tx).unwrap();
+        assert_eq!(find(&mut tx, hourly.clone()), vec![58]);
+        assert_eq!(find(&mut db.begin(), hourly.clone()), vec![57]);
+
+        // the indexes follow the employees changed on commit
+        db.rollback(tx);
+        db.with_transaction(|tx| {
+            let mut emp = db.fetch(42).run(tx)?;
+            emp.set_classification(Arc::new(RwLock::new(HourlyClassification::new(15.25))));
+            db.update(emp).run(tx)
+        })
+        .unwrap();
+        let salaried = EmployeeQuery {
+            classification: Some(ClassificationKind::Salaried),
+            ..Default::default()
+        };
+        assert_eq!(find(&mut db.begin(), hourly), vec![42, 57]);
+        assert_eq!(find(&mut db.begin(), salaried), Vec::<EmployeeId>::new());
+    }
+
+    #[test]
+    fn test_get_payable() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| {
+            db.insert(salaried_emp(42)).run(tx)?;
+            for emp_id in [57, 58, 59] {
+                db.insert(hourly_emp(emp_id)).run(tx)?;
+            }
+            Ok::<_, DaoError>(())
+        })
+        .unwrap();
+        let friday = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+        let month_end = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+
+        let mut tx = db.begin();
+        db.insert(hourly_emp(60)).run(&mut tx).unwrap();
+        db.delete(58).run(&mut tx).unwrap();
+        let mut emp = db.fetch(42).run(&mut tx).unwrap();
+        emp.set_schedule(Arc::new(RwLock::new(WeeklySchedule)));
+        db.update(emp).run(&mut tx).unwrap();
+        // a batch at a time
+        let mut batches = vec![];
+        let mut after = None;
+        loop {
+            let batch = db
+                .get_payable(friday, after, 2)
+                .run(&mut tx)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_emp_id())
+                .collect::<Vec<_>>();
+            let Some(&last) = batch.last() else {
+                break;
+            };
+            after = Some(last);
+            batches.push(batch);
+        }
+        assert_eq!(batches, vec![vec![42, 57], vec![59, 60]]);
+        assert!(db
+            .get_payable(month_end, None, 10)
+            .run(&mut tx)
+            .unwrap()
+            .is_empty());
+
+        // the index is updated on commit
+        db.commit(tx).unwrap();
+        let mut tx = db.begin();
+        let payable = |tx: &mut MockTx, date| {
+            db.get_payable(date, None, 10)
+                .run(tx)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_emp_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(payable(&mut tx, friday), vec![42, 57, 59, 60]);
+        let mut emp = db.fetch(59).run(&mut tx).unwrap();
+        emp.set_schedule(Arc::new(RwLock::new(MonthlySchedule)));
+        db.update(emp).run(&mut tx).unwrap();
+        db.commit(tx).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(payable(&mut tx, friday), vec![42, 57, 60]);
+        assert_eq!(payable(&mut tx, month_end), vec![59]);
     }
 }
@@ -0,0 +1,209 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+use crate::{MockDb, Tables};
+use payroll_domain::{EmployeeId, Paycheck};
+use payroll_impl::record::EmployeeRecord;
+
+// bumped whenever the layout of the document changes
+pub const SNAPSHOT_VERSION: u32 = 3;
+// version 1 had the union members along with the employees, now rebuilt from them;
+// versions before 3 had a copy of the tax and insurance tables in each employee
+// subject to them, now referred to by their paths, so that such an employee cannot be read
+const READABLE_VERSIONS: [u32; 3] = [1, 2, SNAPSHOT_VERSION];
+
+#[derive(Debug, Clone, Eq, PartialEq, Error)]
+pub enum SnapshotError {
+    #[error("io error: {0}")]
+    IoError(String),
+    #[error("format error: {0}")]
+    FormatError(String),
+    #[error("unsupported version: {0}")]
+    UnsupportedVersion(u32),
+}
+
+// the committed state of the whole database, ordered by id so that the same
+// state is always written out the same way; the indexes are rebuilt from the employees
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    employees: Vec<EmployeeRecord>,
+    paychecks: BTreeMap<EmployeeId, Vec<Paycheck>>,
+}
+
+impl MockDb {
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        let tables = self.tables.borrow();
+        let mut employees = tables
+            .employees
+            .values()
+            .map(EmployeeRecord::from_employee)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(SnapshotError::FormatError)?;
+        employees.sort_by_key(|r| r.emp_id);
+        let snapshot = Snapshot {
+            version: SNAPSHOT_VERSION,
+            employees,
+            paychecks: tables.paychecks.clone().into_iter().collect(),
+        };
+        serde_json::to_string_pretty(&snapshot)
+            .map_err(|e| SnapshotError::FormatError(e.to_string()))
+    }
+    // replaces whatever has been committed so far
+    pub fn restore_json(&self, json: &str) -> Result<(), SnapshotError> {
+        let value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| SnapshotError::FormatError(e.to_string()))?;
+        // the version is checked before the rest of the document is interpreted
+        let version = value
+            .get("version")
+            .and_then(|v| v.as_u64())
+            .ok_or(SnapshotError::FormatError("version not found".to_string()))?;
+        if !READABLE_VERSIONS.iter().any(|&v| v as u64 == version) {
+            return Err(SnapshotError::UnsupportedVersion(version as u32));
+        }
+        let snapshot: Snapshot =
+            serde_json::from_value(value).map_err(|e| SnapshotError::FormatError(e.to_string()))?;
+        let mut tables = Tables {
+            paychecks: snapshot.paychecks.into_iter().collect(),
+            ..Default::default()
+        };
+        for record in snapshot.employees {
+            tables.put_employee(record.into_employee());
+        }
+        *self.tables.borrow_mut() = tables;
+        Ok(())
+    }
+    pub fn save(&self, path: &str) -> Result<(), SnapshotError> {
+        let json = self.to_json()?;
+        std::fs::write(path, json).map_err(|e| SnapshotError::IoError(e.to_string()))
+    }
+    pub fn load(&self, path: &str) -> Result<(), SnapshotError> {
+        let json =
+            std::fs::read_to_string(path).map_err(|e| SnapshotError::IoError(e.to_string()))?;
+        self.restore_json(&json)
+    }
+}
+
+#[cfg(test)]
+mod test_snapshot {
+    use super::*;
+    use chrono::NaiveDate;
+    use std::sync::{Arc, RwLock};
+    use tx_rs::Tx;
+
+    use dao::EmployeeDao;
+    use payroll_domain::{DeductionKind, DeductionLine, DuesPolicy, Employee};
+    use payroll_impl::{
+        affiliation::UnionAffiliation, classification::HourlyClassification, method::DirectMethod,
+        schedule::WeeklySchedule,
+    };
+
+    fn hourly_emp(emp_id: EmployeeId) -> Employee {
+        let mut classification = HourlyClassification::new(15.25);
+        classification.add_timecard(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), 9.0);
+        let mut emp = Employee::new(
+            emp_id,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(classification)),
+            Arc::new(RwLock::new(WeeklySchedule)),
+            Arc::new(RwLock::new(DirectMethod::new("Bank", "1234"))),
+            vec![Arc::new(RwLock::new(UnionAffiliation::new(
+                7234,
+                DuesPolicy::PerFriday { dues: 9.42 },
+            )))],
+        );
+        emp.set_net_pay_floor(100.0);
+        emp.set_arrears(vec![DeductionLine::new(
+            DeductionKind::General,
+            "Union dues",
+            1.5,
+        )]);
+        emp
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| {
+            db.insert(hourly_emp(42)).run(tx)?;
+            db.record_paycheck(
+                42,
+                Paycheck::new(
+                    NaiveDate::from_ymd_opt(2024, 7, 27).unwrap()
+                        ..=NaiveDate::from_ymd_opt(2024, 8, 2).unwrap(),
+                ),
+            )
+            .run(tx)
+        })
+        .unwrap();
+        let json = db.to_json().unwrap();
+
+        let restored = MockDb::new();
+        restored.restore_json(&json).unwrap();
+        assert_eq!(restored.to_json(), Ok(json));
+
+        let mut tx = restored.begin();
+        let emp = restored.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(
+            EmployeeRecord::from_employee(&emp),
+            EmployeeRecord::from_employee(&hourly_emp(42))
+        );
+        assert_eq!(restored.find_union_member(7234).run(&mut tx), Ok(42));
+        assert_eq!(restored.get_paychecks(42).run(&mut tx).unwrap().len(), 1);
+        // along with the schedule index
+        let friday = NaiveDate::from_ymd_opt(2024, 8, 9).unwrap();
+        assert_eq!(
+            restored
+                .get_payable(friday, None, 10)
+                .run(&mut tx)
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+    #[test]
+    fn test_version_1() {
+        let db = MockDb::new();
+        db.with_transaction(|tx| db.insert(hourly_emp(42)).run(tx))
+            .unwrap();
+        let mut snapshot: serde_json::Value = serde_json::from_str(&db.to_json().unwrap()).unwrap();
+        snapshot["version"] = 1.into();
+        // ignored, the members follow the affiliations
+        snapshot["union_members"] = serde_json::json!({ "7235": 42 });
+
+        let restored = MockDb::new();
+        restored.restore_json(&snapshot.to_string()).unwrap();
+        let mut tx = restored.begin();
+        assert!(restored.fetch(42).run(&mut tx).is_ok());
+        assert_eq!(restored.find_union_member(7234).run(&mut tx), Ok(42));
+        assert!(restored.find_union_member(7235).run(&mut tx).is_err());
+        // written out as the current version
+        assert_eq!(restored.to_json(), db.to_json());
+    }
+    #[test]
+    fn test_restore_replaces() {
+        let db = MockDb::new();
+        let json = db.to_json().unwrap();
+        db.with_transaction(|tx| db.insert(hourly_emp(42)).run(tx))
+            .unwrap();
+
+        db.restore_json(&json).unwrap();
+        let mut tx = db.begin();
+        assert_eq!(db.get_all().run(&mut tx).unwrap().len(), 0);
+    }
+    #[test]
+    fn test_unsupported_version() {
+        let db = MockDb::new();
+        let json = r#"{"version":99,"employees":[],"paychecks":{}}"#;
+        assert_eq!(
+            db.restore_json(json),
+            Err(SnapshotError::UnsupportedVersion(99))
+        );
+        assert!(matches!(
+            db.restore_json("{}"),
+            Err(SnapshotError::FormatError(_))
+        ));
+    }
+}
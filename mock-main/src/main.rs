@@ -1,11 +1,85 @@
-use abstract_tx::UsecaseError;
+use std::error::Error;
+
+use event_store_db::EventStoreDb;
 use mock_app::TestPayrollApp;
+use mock_db::MockDb;
+use sqlite_db::SqliteStore;
 use tx_app::TransactionApplication;
 
-fn main() -> Result<(), UsecaseError> {
-    let mut app = TestPayrollApp::new("script/test.scr");
+const USAGE: &str =
+    "usage: mock-main [--db <sqlite file> | --events | [--load <snapshot>] [--save <snapshot>]] [<script>]";
+
+#[derive(Debug, Clone)]
+struct Opts {
+    script: String,
+    // a SQLite database to run the script against, in place of the one in memory
+    db: Option<String>,
+    // run the script against an event store in memory, in place of the database
+    events: bool,
+    // a snapshot to restore before running the script
+    load: Option<String>,
+    // where to save the snapshot after running the script
+    save: Option<String>,
+}
+impl Opts {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut opts = Self {
+            script: "script/test.scr".to_string(),
+            db: None,
+            events: false,
+            load: None,
+            save: None,
+        };
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--db" => opts.db = Some(args.next().ok_or(USAGE)?),
+                "--events" => opts.events = true,
+                "--load" => opts.load = Some(args.next().ok_or(USAGE)?),
+                "--save" => opts.save = Some(args.next().ok_or(USAGE)?),
+                _ if arg.starts_with("--") => return Err(USAGE.to_string()),
+                _ => opts.script = arg,
+            }
+        }
+        // one store at a time; snapshots are of the database in memory
+        let snapshot = opts.load.is_some() || opts.save.is_some();
+        if [opts.db.is_some(), opts.events, snapshot]
+            .iter()
+            .filter(|&&x| x)
+            .count()
+            > 1
+        {
+            return Err(USAGE.to_string());
+        }
+        Ok(opts)
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let opts = Opts::parse(std::env::args().skip(1))?;
+
+    if let Some(path) = &opts.db {
+        let mut app = TestPayrollApp::with_db(&opts.script, SqliteStore::open(path)?);
+        app.run(&mut ())?;
+        println!("{:#?}", app);
+        return Ok(());
+    }
+    if opts.events {
+        let mut app = TestPayrollApp::with_db(&opts.script, EventStoreDb::new());
+        app.run(&mut ())?;
+        println!("{:#?}", app);
+        return Ok(());
+    }
+
+    let db = MockDb::new();
+    if let Some(path) = &opts.load {
+        db.load(path)?;
+    }
+    let mut app = TestPayrollApp::with_db(&opts.script, db.clone());
     app.run(&mut ())?;
     println!("{:#?}", app);
+    if let Some(path) = &opts.save {
+        db.save(path)?;
+    }
 
     Ok(())
 }
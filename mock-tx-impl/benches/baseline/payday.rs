@@ -0,0 +1,92 @@
+// the payday bench against the sequential payday on Rc<RefCell> it is compared with,
+// as of aacb32b; not built in this tree, but in a worktree of that revision by run.sh
+use chrono::{Days, NaiveDate};
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::{
+    any::Any,
+    cell::RefCell,
+    rc::Rc,
+};
+use tx_rs::Tx;
+
+use dao::{DaoError, EmployeeDao};
+use mock_db::MockDb;
+use mock_tx_impl::PaydayTransactionImpl;
+use payroll_domain::{Employee, Paycheck, PaymentMethod};
+use payroll_impl::{
+    affiliation::{DuesPolicy, UnionAffiliation},
+    classification::HourlyClassification,
+    schedule::WeeklySchedule,
+};
+use tx_impl::general::PaydayTransaction;
+
+const EMPLOYEES: u32 = 100_000;
+
+// pays out nothing, not to measure the printing
+#[derive(Debug, Clone)]
+struct SilentMethod;
+impl PaymentMethod for SilentMethod {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn pay(&self, _pc: &Paycheck) {}
+}
+
+// hourly union members with a timecard for every weekday of the last year
+fn synthetic_db(pay_date: NaiveDate) -> MockDb {
+    let db = MockDb::new();
+    db.with_transaction(|tx| {
+        for emp_id in 1..=EMPLOYEES {
+            let mut classification = HourlyClassification::new(15.25);
+            let mut affiliation =
+                UnionAffiliation::new(emp_id, DuesPolicy::PerFriday { dues: 9.42 });
+            for days in 0..365 {
+                let date = pay_date - Days::new(days);
+                if days % 7 < 5 {
+                    classification.add_timecard(date, 8.0 + (emp_id % 4) as f32);
+                }
+                if days % 30 == 0 {
+                    affiliation.add_service_charge(date, 12.5);
+                }
+            }
+            let emp = Employee::new(
+                emp_id,
+                &format!("Emp{}", emp_id),
+                "Home",
+                Rc::new(RefCell::new(classification)),
+                Rc::new(RefCell::new(WeeklySchedule)),
+                Rc::new(RefCell::new(SilentMethod)),
+                vec![Rc::new(RefCell::new(affiliation))],
+            );
+            db.insert(emp).run(tx)?;
+        }
+        Ok::<_, DaoError>(())
+    })
+    .unwrap();
+    db
+}
+
+fn bench_payday(c: &mut Criterion) {
+    let pay_date = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+    let db = synthetic_db(pay_date);
+
+    let mut group = c.benchmark_group("payday");
+    group.sample_size(10);
+    let payday = PaydayTransactionImpl {
+        db: db.clone(),
+        pay_date,
+    };
+    group.bench_function("sequential", |b| {
+        b.iter(|| {
+            let mut tx = db.begin();
+            PaydayTransaction::execute(&payday, pay_date)
+                .run(&mut tx)
+                .unwrap();
+            db.rollback(tx);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_payday);
+criterion_main!(benches);
@@ -0,0 +1,32 @@
+#!/bin/sh
+# runs the payday bench on the sequential payday on Rc<RefCell>, before the domain was
+# made thread-safe, to compare with `cargo bench -p mock-tx-impl --bench payday`;
+# arguments are passed on to cargo bench
+#
+# on 1 CPU, 100k hourly union members, 10 samples:
+#   baseline, payday/sequential   0.79 - 0.83 s
+#   this tree, payday/1           0.88 - 0.92 s
+# the payday now copies each paycheck, to pay it out only once its batch is committed,
+# where the baseline paid it out before the transaction was committed
+set -eu
+
+rev=aacb32b
+root=$(git rev-parse --show-toplevel)
+dir=$(mktemp -d)
+git -C "$root" worktree add --detach "$dir" "$rev"
+trap 'git -C "$root" worktree remove --force "$dir"' EXIT
+
+mkdir -p "$dir/mock-tx-impl/benches"
+cp "$root/mock-tx-impl/benches/baseline/payday.rs" "$dir/mock-tx-impl/benches/payday.rs"
+cat >>"$dir/mock-tx-impl/Cargo.toml" <<'TOML'
+
+[dev-dependencies]
+criterion = "0.5"
+
+[[bench]]
+name = "payday"
+harness = false
+TOML
+
+cd "$dir"
+cargo bench -p mock-tx-impl --bench payday "$@"
@@ -0,0 +1,97 @@
+use chrono::{Days, NaiveDate};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::{
+    any::Any,
+    sync::{Arc, RwLock},
+};
+use tx_rs::Tx;
+
+use dao::{DaoError, EmployeeDao};
+use mock_db::MockDb;
+use mock_tx_impl::PaydayTransactionImpl;
+use payroll_domain::{DuesPolicy, Employee, Paycheck, PaymentMethod};
+use payroll_impl::{
+    affiliation::UnionAffiliation, classification::HourlyClassification, schedule::WeeklySchedule,
+};
+use tx_impl::general::PaydayTransaction;
+
+const EMPLOYEES: u32 = 100_000;
+
+// pays out nothing, not to measure the printing
+#[derive(Debug, Clone)]
+struct SilentMethod;
+impl PaymentMethod for SilentMethod {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn pay(&self, _pc: &Paycheck) {}
+}
+
+// hourly union members with a timecard for every weekday of the last year
+fn synthetic_db(pay_date: NaiveDate) -> MockDb {
+    let db = MockDb::new();
+    db.with_transaction(|tx| {
+        for emp_id in 1..=EMPLOYEES {
+            let mut classification = HourlyClassification::new(15.25);
+            let mut affiliation =
+                UnionAffiliation::new(emp_id, DuesPolicy::PerFriday { dues: 9.42 });
+            for days in 0..365 {
+                let date = pay_date - Days::new(days);
+                if days % 7 < 5 {
+                    classification.add_timecard(date, 8.0 + (emp_id % 4) as f32);
+                }
+                if days % 30 == 0 {
+                    affiliation.add_service_charge(date, 12.5);
+                }
+            }
+            let emp = Employee::new(
+                emp_id,
+                &format!("Emp{}", emp_id),
+                "Home",
+                Arc::new(RwLock::new(classification)),
+                Arc::new(RwLock::new(WeeklySchedule)),
+                Arc::new(RwLock::new(SilentMethod)),
+                vec![Arc::new(RwLock::new(affiliation))],
+            );
+            db.insert(emp).run(tx)?;
+        }
+        Ok::<_, DaoError>(())
+    })
+    .unwrap();
+    db
+}
+
+fn bench_payday(c: &mut Criterion) {
+    let pay_date = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+    let db = synthetic_db(pay_date);
+
+    let mut group = c.benchmark_group("payday");
+    group.sample_size(10);
+    // 1 for the sequential payday
+    for threads in [1, 2, 4, 8] {
+        let payday = PaydayTransactionImpl {
+            db: db.clone(),
+            pay_date,
+            threads,
+        };
+        group.bench_with_input(BenchmarkId::from_parameter(threads), &threads, |b, _| {
+            b.iter(|| {
+                // never committed, so that every iteration pays the same employees
+                let mut tx = db.begin();
+                let mut after = None;
+                while let (Some(last), _) =
+                    PaydayTransaction::execute(&payday, pay_date, after, payday.threads)
+                        .run(&mut tx)
+                        .unwrap()
+                {
+                    after = Some(last);
+                }
+                db.rollback(tx);
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_payday);
+criterion_main!(benches);
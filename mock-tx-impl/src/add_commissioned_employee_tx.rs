@@ -1,15 +1,14 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::AddCommissionedEmployeeTransaction;
 
 #[derive(Debug, Clone)]
-pub struct AddCommissionedEmployeeTransactionImpl {
-    pub db: MockDb,
+pub struct AddCommissionedEmployeeTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub name: String,
@@ -17,22 +16,25 @@ pub struct AddCommissionedEmployeeTransactionImpl {
     pub salary: f32,
     pub commission_rate: f32,
 }
-impl HaveEmployeeDao<()> for AddCommissionedEmployeeTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AddCommissionedEmployeeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for AddCommissionedEmployeeTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        AddCommissionedEmployeeTransaction::execute(
-            self,
-            self.emp_id,
-            &self.name,
-            &self.address,
-            self.salary,
-            self.commission_rate,
-        )
-        .run(ctx)
-        .map(|_| ())
+impl<Db: Database> Transaction<()> for AddCommissionedEmployeeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| {
+                AddCommissionedEmployeeTransaction::execute(
+                    self,
+                    self.emp_id,
+                    &self.name,
+                    &self.address,
+                    self.salary,
+                    self.commission_rate,
+                )
+                .run(tx)
+            })
+            .map(|_| ())
     }
 }
@@ -0,0 +1,38 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{Component, EmployeeId};
+use tx_app::Transaction;
+use tx_impl::general::AddCompositeEmployeeTransaction;
+
+#[derive(Debug, Clone)]
+pub struct AddCompositeEmployeeTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub name: String,
+    pub address: String,
+    pub components: Vec<Component>,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AddCompositeEmployeeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for AddCompositeEmployeeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| {
+                AddCompositeEmployeeTransaction::execute(
+                    self,
+                    self.emp_id,
+                    &self.name,
+                    &self.address,
+                    &self.components,
+                )
+                .run(tx)
+            })
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,38 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::affiliation::AddGarnishmentTransaction;
+
+#[derive(Debug, Clone)]
+pub struct AddGarnishmentTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub case_id: String,
+    pub priority: u32,
+    pub limit_rate: f32,
+    pub total: f32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AddGarnishmentTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for AddGarnishmentTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            AddGarnishmentTransaction::execute(
+                self,
+                self.emp_id,
+                &self.case_id,
+                self.priority,
+                self.limit_rate,
+                self.total,
+            )
+            .run(tx)
+        })
+    }
+}
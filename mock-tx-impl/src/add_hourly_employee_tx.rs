@@ -1,36 +1,38 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::AddHourlyEmployeeTransaction;
 
 #[derive(Debug, Clone)]
-pub struct AddHourlyEmployeeTransactionImpl {
-    pub db: MockDb,
+pub struct AddHourlyEmployeeTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub name: String,
     pub address: String,
     pub hourly_rate: f32,
 }
-impl HaveEmployeeDao<()> for AddHourlyEmployeeTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AddHourlyEmployeeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for AddHourlyEmployeeTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        AddHourlyEmployeeTransaction::execute(
-            self,
-            self.emp_id,
-            &self.name,
-            &self.address,
-            self.hourly_rate,
-        )
-        .run(ctx)
-        .map(|_| ())
+impl<Db: Database> Transaction<()> for AddHourlyEmployeeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| {
+                AddHourlyEmployeeTransaction::execute(
+                    self,
+                    self.emp_id,
+                    &self.name,
+                    &self.address,
+                    self.hourly_rate,
+                )
+                .run(tx)
+            })
+            .map(|_| ())
     }
 }
@@ -1,36 +1,38 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::AddSalaryEmployeeTransaction;
 
 #[derive(Debug, Clone)]
-pub struct AddSalariedEmployeeTransactionImpl {
-    pub db: MockDb,
+pub struct AddSalariedEmployeeTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub name: String,
     pub address: String,
     pub salary: f32,
 }
-impl HaveEmployeeDao<()> for AddSalariedEmployeeTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AddSalariedEmployeeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for AddSalariedEmployeeTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        AddSalaryEmployeeTransaction::execute(
-            self,
-            self.emp_id,
-            &self.name,
-            &self.address,
-            self.salary,
-        )
-        .run(ctx)
-        .map(|_| ())
+impl<Db: Database> Transaction<()> for AddSalariedEmployeeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| {
+                AddSalaryEmployeeTransaction::execute(
+                    self,
+                    self.emp_id,
+                    &self.name,
+                    &self.address,
+                    self.salary,
+                )
+                .run(tx)
+            })
+            .map(|_| ())
     }
 }
@@ -0,0 +1,42 @@
+use chrono::NaiveDate;
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::general::AdvanceTransaction;
+
+#[derive(Debug, Clone)]
+pub struct AdvanceTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub advance_id: String,
+    pub date: NaiveDate,
+    pub principal: f32,
+    pub installment: f32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for AdvanceTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for AdvanceTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        let (emp, disbursement) = self.db.with_transaction(|tx| {
+            AdvanceTransaction::execute(
+                self,
+                self.emp_id,
+                &self.advance_id,
+                self.date,
+                self.principal,
+                self.installment,
+            )
+            .run(tx)
+        })?;
+        // paid out only once the advance has been recorded
+        emp.disburse(&disbursement);
+        Ok(())
+    }
+}
@@ -1,26 +1,27 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::ChangeAddressTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeAddressTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeAddressTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub address: String,
 }
-impl HaveEmployeeDao<()> for ChangeAddressTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeAddressTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeAddressTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeAddressTransaction::execute(self, self.emp_id, &self.address).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeAddressTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeAddressTransaction::execute(self, self.emp_id, &self.address).run(tx)
+        })
     }
 }
@@ -1,28 +1,34 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::classification::ChangeCommissionedTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeCommissionedTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeCommissionedTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub salary: f32,
     pub commission_rate: f32,
 }
-impl HaveEmployeeDao<()> for ChangeCommissionedTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeCommissionedTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeCommissionedTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeCommissionedTransaction::execute(self, self.emp_id, self.salary, self.commission_rate)
-            .run(ctx)
+impl<Db: Database> Transaction<()> for ChangeCommissionedTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeCommissionedTransaction::execute(
+                self,
+                self.emp_id,
+                self.salary,
+                self.commission_rate,
+            )
+            .run(tx)
+        })
     }
 }
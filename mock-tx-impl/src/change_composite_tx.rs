@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{Component, EmployeeId};
+use tx_app::Transaction;
+use tx_impl::classification::ChangeCompositeTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeCompositeTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub components: Vec<Component>,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeCompositeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeCompositeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeCompositeTransaction::execute(self, self.emp_id, &self.components).run(tx)
+        })
+    }
+}
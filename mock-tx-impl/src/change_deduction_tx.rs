@@ -0,0 +1,28 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeDeductionTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeDeductionTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub name: String,
+    pub amount: f32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeDeductionTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeDeductionTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeDeductionTransaction::execute(self, self.emp_id, &self.name, self.amount).run(tx)
+        })
+    }
+}
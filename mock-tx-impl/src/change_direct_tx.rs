@@ -1,27 +1,28 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::method::ChangeDirectTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeDirectTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeDirectTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub bank: String,
     pub account: String,
 }
-impl HaveEmployeeDao<()> for ChangeDirectTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeDirectTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeDirectTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeDirectTransaction::execute(self, self.emp_id, &self.bank, &self.account).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeDirectTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeDirectTransaction::execute(self, self.emp_id, &self.bank, &self.account).run(tx)
+        })
     }
 }
@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeGradeTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeGradeTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub grade: u32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeGradeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeGradeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeGradeTransaction::execute(self, self.emp_id, self.grade).run(tx)
+        })
+    }
+}
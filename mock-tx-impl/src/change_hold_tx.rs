@@ -1,25 +1,25 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::method::ChangeHoldTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeHoldTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeHoldTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
 }
-impl HaveEmployeeDao<()> for ChangeHoldTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeHoldTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeHoldTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeHoldTransaction::execute(self, self.emp_id).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeHoldTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| ChangeHoldTransaction::execute(self, self.emp_id).run(tx))
     }
 }
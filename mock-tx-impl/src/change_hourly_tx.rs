@@ -1,26 +1,27 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::classification::ChangeHourlyTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeHourlyTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeHourlyTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub hourly_rate: f32,
 }
-impl HaveEmployeeDao<()> for ChangeHourlyTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeHourlyTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeHourlyTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeHourlyTransaction::execute(self, self.emp_id, self.hourly_rate).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeHourlyTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeHourlyTransaction::execute(self, self.emp_id, self.hourly_rate).run(tx)
+        })
     }
 }
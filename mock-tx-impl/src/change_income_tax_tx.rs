@@ -0,0 +1,36 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use payroll_impl::{table::SharedTable, tax::TaxTable};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeIncomeTaxTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeIncomeTaxTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub table_path: String,
+    pub allowances: u32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeIncomeTaxTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeIncomeTaxTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        // read anew, for everyone withheld by it
+        let table = SharedTable::<TaxTable>::read(&self.table_path).map_err(|e| {
+            UsecaseError::TaxTableUnavailable {
+                path: self.table_path.clone(),
+                reason: e,
+            }
+        })?;
+        self.db.with_transaction(|tx| {
+            ChangeIncomeTaxTransaction::execute(self, self.emp_id, table, self.allowances).run(tx)
+        })
+    }
+}
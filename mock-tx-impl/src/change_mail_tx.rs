@@ -1,26 +1,27 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::method::ChangeMailTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeMailTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeMailTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub address: String,
 }
-impl HaveEmployeeDao<()> for ChangeMailTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeMailTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeMailTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeMailTransaction::execute(self, self.emp_id, &self.address).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeMailTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeMailTransaction::execute(self, self.emp_id, &self.address).run(tx)
+        })
     }
 }
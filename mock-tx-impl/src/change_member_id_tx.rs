@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{EmployeeId, MemberId};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeMemberIdTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeMemberIdTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub member_id: MemberId,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeMemberIdTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeMemberIdTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeMemberIdTransaction::execute(self, self.emp_id, self.member_id).run(tx)
+        })
+    }
+}
@@ -0,0 +1,45 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{EmployeeId, WithholdingColumn};
+use payroll_impl::{table::SharedTable, tax::MonthlyWithholdingTable};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeMonthlyTaxTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeMonthlyTaxTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub table_path: String,
+    pub column: WithholdingColumn,
+    pub dependents: u32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeMonthlyTaxTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeMonthlyTaxTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        // read anew, for everyone withheld by it
+        let table =
+            SharedTable::<MonthlyWithholdingTable>::read(&self.table_path).map_err(|e| {
+                UsecaseError::TaxTableUnavailable {
+                    path: self.table_path.clone(),
+                    reason: e,
+                }
+            })?;
+        self.db.with_transaction(|tx| {
+            ChangeMonthlyTaxTransaction::execute(
+                self,
+                self.emp_id,
+                table,
+                self.column,
+                self.dependents,
+            )
+            .run(tx)
+        })
+    }
+}
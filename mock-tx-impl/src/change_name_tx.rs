@@ -1,26 +1,27 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::ChangeNameTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeNameTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeNameTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub name: String,
 }
-impl HaveEmployeeDao<()> for ChangeNameTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeNameTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeNameTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeNameTransaction::execute(self, self.emp_id, &self.name).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeNameTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeNameTransaction::execute(self, self.emp_id, &self.name).run(tx)
+        })
     }
 }
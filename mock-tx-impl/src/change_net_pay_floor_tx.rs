@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::general::ChangeNetPayFloorTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeNetPayFloorTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub net_pay_floor: f32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeNetPayFloorTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeNetPayFloorTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeNetPayFloorTransaction::execute(self, self.emp_id, self.net_pay_floor).run(tx)
+        })
+    }
+}
@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{EmployeeId, RetirementElection};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeRetirementTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeRetirementTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub election: RetirementElection,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeRetirementTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeRetirementTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeRetirementTransaction::execute(self, self.emp_id, self.election).run(tx)
+        })
+    }
+}
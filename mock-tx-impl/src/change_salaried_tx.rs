@@ -1,26 +1,27 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::classification::ChangeSalariedTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeSalariedTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeSalariedTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub salary: f32,
 }
-impl HaveEmployeeDao<()> for ChangeSalariedTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeSalariedTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeSalariedTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeSalariedTransaction::execute(self, self.emp_id, self.salary).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeSalariedTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeSalariedTransaction::execute(self, self.emp_id, self.salary).run(tx)
+        })
     }
 }
@@ -0,0 +1,44 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use payroll_impl::{insurance::SocialInsuranceTable, table::SharedTable};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeSocialInsuranceTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeSocialInsuranceTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub table_path: String,
+    pub prefecture: String,
+    pub grade: u32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeSocialInsuranceTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeSocialInsuranceTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        // read anew, for everyone insured by it
+        let table = SharedTable::<SocialInsuranceTable>::read(&self.table_path).map_err(|e| {
+            UsecaseError::InsuranceTableUnavailable {
+                path: self.table_path.clone(),
+                reason: e,
+            }
+        })?;
+        self.db.with_transaction(|tx| {
+            ChangeSocialInsuranceTransaction::execute(
+                self,
+                self.emp_id,
+                table,
+                &self.prefecture,
+                self.grade,
+            )
+            .run(tx)
+        })
+    }
+}
@@ -0,0 +1,29 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{EmployeeId, WithholdingColumn};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeTaxColumnTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeTaxColumnTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub column: WithholdingColumn,
+    pub dependents: u32,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeTaxColumnTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeTaxColumnTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeTaxColumnTransaction::execute(self, self.emp_id, self.column, self.dependents)
+                .run(tx)
+        })
+    }
+}
@@ -1,25 +1,26 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::affiliation::ChangeUnaffiliatedTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeUnaffiliatedTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeUnaffiliatedTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
 }
-impl HaveEmployeeDao<()> for ChangeUnaffiliatedTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeUnaffiliatedTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeUnaffiliatedTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeUnaffiliatedTransaction::execute(self, self.emp_id).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeUnaffiliatedTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeUnaffiliatedTransaction::execute(self, self.emp_id).run(tx)
+        })
     }
 }
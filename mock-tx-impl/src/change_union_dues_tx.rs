@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{DuesPolicy, EmployeeId};
+use tx_app::Transaction;
+use tx_impl::affiliation::ChangeUnionDuesTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ChangeUnionDuesTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub dues: DuesPolicy,
+    pub effective: Option<NaiveDate>,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeUnionDuesTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ChangeUnionDuesTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeUnionDuesTransaction::execute(
+                self,
+                self.emp_id,
+                self.dues.clone(),
+                self.effective,
+            )
+            .run(tx)
+        })
+    }
+}
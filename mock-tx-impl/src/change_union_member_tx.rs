@@ -1,27 +1,34 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
-use payroll_domain::{EmployeeId, MemberId};
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{DuesPolicy, EmployeeId, MemberId};
 use tx_app::Transaction;
 use tx_impl::affiliation::ChangeUnionMemberTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ChangeUnionMemberTransactionImpl {
-    pub db: MockDb,
+pub struct ChangeUnionMemberTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub member_id: MemberId,
-    pub dues: f32,
+    pub dues: DuesPolicy,
 }
-impl HaveEmployeeDao<()> for ChangeUnionMemberTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ChangeUnionMemberTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ChangeUnionMemberTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ChangeUnionMemberTransaction::execute(self, self.emp_id, self.member_id, self.dues).run(ctx)
+impl<Db: Database> Transaction<()> for ChangeUnionMemberTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ChangeUnionMemberTransaction::execute(
+                self,
+                self.emp_id,
+                self.member_id,
+                self.dues.clone(),
+            )
+            .run(tx)
+        })
     }
 }
@@ -1,27 +1,26 @@
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::DeleteEmployeeTransaction;
 
 #[derive(Debug, Clone)]
-pub struct DeleteEmployeeTransactionImpl {
-    pub db: MockDb,
+pub struct DeleteEmployeeTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
 }
-impl HaveEmployeeDao<()> for DeleteEmployeeTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for DeleteEmployeeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for DeleteEmployeeTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        DeleteEmployeeTransaction::execute(self, self.emp_id)
-            .run(ctx)
+impl<Db: Database> Transaction<()> for DeleteEmployeeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| DeleteEmployeeTransaction::execute(self, self.emp_id).run(tx))
             .map(|_| ())
     }
 }
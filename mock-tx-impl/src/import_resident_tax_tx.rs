@@ -0,0 +1,32 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_impl::tax::ResidentTaxNotices;
+use tx_app::Transaction;
+use tx_impl::affiliation::ImportResidentTaxTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ImportResidentTaxTransactionImpl<Db> {
+    pub db: Db,
+
+    pub notices_path: String,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ImportResidentTaxTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ImportResidentTaxTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        let notices = std::fs::read_to_string(&self.notices_path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.parse::<ResidentTaxNotices>())
+            .map_err(|e| UsecaseError::ResidentTaxNoticesUnavailable {
+                path: self.notices_path.clone(),
+                reason: e,
+            })?;
+        self.db
+            .with_transaction(|tx| ImportResidentTaxTransaction::execute(self, &notices.0).run(tx))
+    }
+}
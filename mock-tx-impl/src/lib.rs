@@ -1,39 +1,79 @@
 mod add_commissioned_employee_tx;
+mod add_composite_employee_tx;
+mod add_garnishment_tx;
 mod add_hourly_employee_tx;
 mod add_salaried_employee_tx;
+mod advance_tx;
 mod change_address_tx;
 mod change_commissioned_tx;
+mod change_composite_tx;
+mod change_deduction_tx;
 mod change_direct_tx;
+mod change_grade_tx;
 mod change_hold_tx;
 mod change_hourly_tx;
+mod change_income_tax_tx;
 mod change_mail_tx;
+mod change_member_id_tx;
+mod change_monthly_tax_tx;
 mod change_name_tx;
+mod change_net_pay_floor_tx;
+mod change_no_deduction_tx;
+mod change_retirement_tx;
 mod change_salaried_tx;
+mod change_social_insurance_tx;
+mod change_tax_column_tx;
 mod change_unaffiliated_tx;
+mod change_union_dues_tx;
 mod change_union_member_tx;
 mod delete_employee_tx;
+mod import_resident_tax_tx;
+mod list_employees_tx;
 mod payday_tx;
+mod release_garnishment_tx;
+mod reverse_service_charge_tx;
 mod sales_receipt_tx;
 mod service_charge_tx;
 mod timecard_tx;
 mod tx_factory_impl;
+mod year_end_adjustment_tx;
 
 pub use add_commissioned_employee_tx::AddCommissionedEmployeeTransactionImpl;
+pub use add_composite_employee_tx::AddCompositeEmployeeTransactionImpl;
+pub use add_garnishment_tx::AddGarnishmentTransactionImpl;
 pub use add_hourly_employee_tx::AddHourlyEmployeeTransactionImpl;
 pub use add_salaried_employee_tx::AddSalariedEmployeeTransactionImpl;
+pub use advance_tx::AdvanceTransactionImpl;
 pub use change_address_tx::ChangeAddressTransactionImpl;
 pub use change_commissioned_tx::ChangeCommissionedTransactionImpl;
+pub use change_composite_tx::ChangeCompositeTransactionImpl;
+pub use change_deduction_tx::ChangeDeductionTransactionImpl;
 pub use change_direct_tx::ChangeDirectTransactionImpl;
+pub use change_grade_tx::ChangeGradeTransactionImpl;
 pub use change_hold_tx::ChangeHoldTransactionImpl;
 pub use change_hourly_tx::ChangeHourlyTransactionImpl;
+pub use change_income_tax_tx::ChangeIncomeTaxTransactionImpl;
 pub use change_mail_tx::ChangeMailTransactionImpl;
+pub use change_member_id_tx::ChangeMemberIdTransactionImpl;
+pub use change_monthly_tax_tx::ChangeMonthlyTaxTransactionImpl;
 pub use change_name_tx::ChangeNameTransactionImpl;
+pub use change_net_pay_floor_tx::ChangeNetPayFloorTransactionImpl;
+pub use change_no_deduction_tx::ChangeNoDeductionTransactionImpl;
+pub use change_retirement_tx::ChangeRetirementTransactionImpl;
 pub use change_salaried_tx::ChangeSalariedTransactionImpl;
+pub use change_social_insurance_tx::ChangeSocialInsuranceTransactionImpl;
+pub use change_tax_column_tx::ChangeTaxColumnTransactionImpl;
 pub use change_unaffiliated_tx::ChangeUnaffiliatedTransactionImpl;
+pub use change_union_dues_tx::ChangeUnionDuesTransactionImpl;
 pub use change_union_member_tx::ChangeUnionMemberTransactionImpl;
 pub use delete_employee_tx::DeleteEmployeeTransactionImpl;
+pub use import_resident_tax_tx::ImportResidentTaxTransactionImpl;
+pub use list_employees_tx::ListEmployeesTransactionImpl;
 pub use payday_tx::PaydayTransactionImpl;
+pub use release_garnishment_tx::ReleaseGarnishmentTransactionImpl;
+pub use reverse_service_charge_tx::ReverseServiceChargeTransactionImpl;
 pub use sales_receipt_tx::SalesReceiptTransactionImpl;
 pub use service_charge_tx::ServiceChargeTransactionImpl;
 pub use timecard_tx::TimeCardTransactionImpl;
 pub use tx_factory_impl::TransactionFactoryImpl;
+pub use year_end_adjustment_tx::YearEndAdjustmentTransactionImpl;
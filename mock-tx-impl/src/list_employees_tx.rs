@@ -0,0 +1,34 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, EmployeeQuery, HaveEmployeeDao};
+use tx_app::Transaction;
+use tx_impl::general::ListEmployeesTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ListEmployeesTransactionImpl<Db> {
+    pub db: Db,
+
+    pub query: EmployeeQuery,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ListEmployeesTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ListEmployeesTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        let employees = self.db.with_transaction(|tx| {
+            ListEmployeesTransaction::execute(self, self.query.clone()).run(tx)
+        })?;
+        for emp in employees {
+            println!(
+                "Employee {}: {}, {}",
+                emp.get_emp_id(),
+                emp.get_name(),
+                emp.get_address()
+            );
+        }
+        Ok(())
+    }
+}
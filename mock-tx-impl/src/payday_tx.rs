@@ -2,24 +2,37 @@ use chrono::NaiveDate;
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use tx_app::Transaction;
 use tx_impl::general::*;
 
 #[derive(Debug, Clone)]
-pub struct PaydayTransactionImpl {
-    pub db: MockDb,
+pub struct PaydayTransactionImpl<Db> {
+    pub db: Db,
 
     pub pay_date: NaiveDate,
+    pub threads: usize,
 }
-impl HaveEmployeeDao<()> for PaydayTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for PaydayTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for PaydayTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        PaydayTransaction::execute(self, self.pay_date).run(ctx)
+impl<Db: Database> Transaction<()> for PaydayTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        let mut after = None;
+        loop {
+            let (last, paid) = self.db.with_transaction(|tx| {
+                PaydayTransaction::execute(self, self.pay_date, after, self.threads).run(tx)
+            })?;
+            // nothing of a batch is paid out unless all of it has been committed
+            for (method, pc) in paid {
+                method.read().unwrap().pay(&pc);
+            }
+            let Some(last) = last else {
+                return Ok(());
+            };
+            after = Some(last);
+        }
     }
 }
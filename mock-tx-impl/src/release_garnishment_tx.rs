@@ -0,0 +1,27 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use tx_app::Transaction;
+use tx_impl::affiliation::ReleaseGarnishmentTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ReleaseGarnishmentTransactionImpl<Db> {
+    pub db: Db,
+
+    pub emp_id: EmployeeId,
+    pub case_id: String,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ReleaseGarnishmentTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ReleaseGarnishmentTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ReleaseGarnishmentTransaction::execute(self, self.emp_id, &self.case_id).run(tx)
+        })
+    }
+}
@@ -0,0 +1,35 @@
+use chrono::NaiveDate;
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{MemberId, ServiceChargeId};
+use tx_app::Transaction;
+use tx_impl::affiliation::ReverseServiceChargeTransaction;
+
+#[derive(Debug, Clone)]
+pub struct ReverseServiceChargeTransactionImpl<Db> {
+    pub db: Db,
+
+    pub member_id: MemberId,
+    pub charge_id: ServiceChargeId,
+    pub date: NaiveDate,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ReverseServiceChargeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for ReverseServiceChargeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            ReverseServiceChargeTransaction::execute(
+                self,
+                self.member_id,
+                self.charge_id,
+                self.date,
+            )
+            .run(tx)
+        })
+    }
+}
@@ -2,27 +2,28 @@ use chrono::NaiveDate;
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::SalesReceiptTransaction;
 
 #[derive(Debug, Clone)]
-pub struct SalesReceiptTransactionImpl {
-    pub db: MockDb,
+pub struct SalesReceiptTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub date: NaiveDate,
     pub amount: f32,
 }
-impl HaveEmployeeDao<()> for SalesReceiptTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for SalesReceiptTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for SalesReceiptTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        SalesReceiptTransaction::execute(self, self.emp_id, self.date, self.amount).run(ctx)
+impl<Db: Database> Transaction<()> for SalesReceiptTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            SalesReceiptTransaction::execute(self, self.emp_id, self.date, self.amount).run(tx)
+        })
     }
 }
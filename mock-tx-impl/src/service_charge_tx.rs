@@ -2,27 +2,31 @@ use chrono::NaiveDate;
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::MemberId;
 use tx_app::Transaction;
 use tx_impl::affiliation::ServiceChargeTransaction;
 
 #[derive(Debug, Clone)]
-pub struct ServiceChargeTransactionImpl {
-    pub db: MockDb,
+pub struct ServiceChargeTransactionImpl<Db> {
+    pub db: Db,
 
     pub member_id: MemberId,
     pub date: NaiveDate,
     pub amount: f32,
 }
-impl HaveEmployeeDao<()> for ServiceChargeTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for ServiceChargeTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for ServiceChargeTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        ServiceChargeTransaction::execute(self, self.member_id, self.date, self.amount).run(ctx)
+impl<Db: Database> Transaction<()> for ServiceChargeTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db
+            .with_transaction(|tx| {
+                ServiceChargeTransaction::execute(self, self.member_id, self.date, self.amount)
+                    .run(tx)
+            })
+            .map(|_| ())
     }
 }
@@ -2,27 +2,28 @@ use chrono::NaiveDate;
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
-use dao::{EmployeeDao, HaveEmployeeDao};
-use mock_db::MockDb;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
 use tx_app::Transaction;
 use tx_impl::general::TimeCardTransaction;
 
 #[derive(Debug, Clone)]
-pub struct TimeCardTransactionImpl {
-    pub db: MockDb,
+pub struct TimeCardTransactionImpl<Db> {
+    pub db: Db,
 
     pub emp_id: EmployeeId,
     pub date: NaiveDate,
     pub hours: f32,
 }
-impl HaveEmployeeDao<()> for TimeCardTransactionImpl {
-    fn dao(&self) -> Box<&impl EmployeeDao<()>> {
-        Box::new(&self.db)
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for TimeCardTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
     }
 }
-impl Transaction<()> for TimeCardTransactionImpl {
-    fn execute(&self, ctx: &mut ()) -> Result<(), UsecaseError> {
-        TimeCardTransaction::execute(self, self.emp_id, self.date, self.hours).run(ctx)
+impl<Db: Database> Transaction<()> for TimeCardTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        self.db.with_transaction(|tx| {
+            TimeCardTransaction::execute(self, self.emp_id, self.date, self.hours).run(tx)
+        })
     }
 }
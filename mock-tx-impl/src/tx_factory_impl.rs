@@ -1,18 +1,21 @@
-use mock_db::MockDb;
-use payroll_domain::{EmployeeId, MemberId};
+use dao::{Database, EmployeeQuery};
+use payroll_domain::{
+    Component, DuesPolicy, EmployeeId, MemberId, RetirementElection, ServiceChargeId,
+    WithholdingColumn,
+};
 use tx_app::Transaction;
 use tx_factory::TransactionFactory;
 
 #[derive(Debug)]
-pub struct TransactionFactoryImpl {
-    db: MockDb,
+pub struct TransactionFactoryImpl<Db> {
+    db: Db,
 }
-impl TransactionFactoryImpl {
-    pub fn new(db: MockDb) -> Self {
+impl<Db: Database> TransactionFactoryImpl<Db> {
+    pub fn new(db: Db) -> Self {
         Self { db }
     }
 }
-impl TransactionFactory<()> for TransactionFactoryImpl {
+impl<Db: Database + 'static> TransactionFactory<()> for TransactionFactoryImpl<Db> {
     fn mk_add_salary_employee_tx(
         &self,
         emp_id: EmployeeId,
@@ -66,12 +69,35 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
             },
         )
     }
+    fn mk_add_composite_employee_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+        address: String,
+        components: Vec<Component>,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::add_composite_employee_tx::AddCompositeEmployeeTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                name,
+                address,
+                components,
+            },
+        )
+    }
     fn mk_delete_employee_tx(&self, emp_id: EmployeeId) -> Box<dyn Transaction<()>> {
         Box::new(crate::delete_employee_tx::DeleteEmployeeTransactionImpl {
             db: self.db.clone(),
             emp_id,
         })
     }
+    fn mk_list_employees_tx(&self, query: EmployeeQuery) -> Box<dyn Transaction<()>> {
+        Box::new(crate::list_employees_tx::ListEmployeesTransactionImpl {
+            db: self.db.clone(),
+            query,
+        })
+    }
     fn mk_timecard_tx(
         &self,
         emp_id: EmployeeId,
@@ -116,6 +142,19 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
             address: address.to_string(),
         })
     }
+    fn mk_change_net_pay_floor_tx(
+        &self,
+        emp_id: EmployeeId,
+        net_pay_floor: f32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_net_pay_floor_tx::ChangeNetPayFloorTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                net_pay_floor,
+            },
+        )
+    }
     fn mk_change_salaried_tx(&self, emp_id: EmployeeId, salary: f32) -> Box<dyn Transaction<()>> {
         Box::new(crate::change_salaried_tx::ChangeSalariedTransactionImpl {
             db: self.db.clone(),
@@ -149,6 +188,17 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
             },
         )
     }
+    fn mk_change_composite_tx(
+        &self,
+        emp_id: EmployeeId,
+        components: Vec<Component>,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(crate::change_composite_tx::ChangeCompositeTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            components,
+        })
+    }
     fn mk_change_direct_tx(
         &self,
         emp_id: EmployeeId,
@@ -179,7 +229,7 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
         &self,
         emp_id: EmployeeId,
         member_id: MemberId,
-        dues: f32,
+        dues: DuesPolicy,
     ) -> Box<dyn Transaction<()>> {
         Box::new(
             crate::change_union_member_tx::ChangeUnionMemberTransactionImpl {
@@ -198,6 +248,180 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
             },
         )
     }
+    fn mk_change_union_dues_tx(
+        &self,
+        emp_id: EmployeeId,
+        dues: DuesPolicy,
+        effective: Option<chrono::NaiveDate>,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_union_dues_tx::ChangeUnionDuesTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                dues,
+                effective,
+            },
+        )
+    }
+    fn mk_change_member_id_tx(
+        &self,
+        emp_id: EmployeeId,
+        member_id: MemberId,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(crate::change_member_id_tx::ChangeMemberIdTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            member_id,
+        })
+    }
+    fn mk_change_deduction_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+        amount: f32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(crate::change_deduction_tx::ChangeDeductionTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            name,
+            amount,
+        })
+    }
+    fn mk_change_no_deduction_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_no_deduction_tx::ChangeNoDeductionTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                name,
+            },
+        )
+    }
+    fn mk_change_income_tax_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        allowances: u32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_income_tax_tx::ChangeIncomeTaxTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                table_path,
+                allowances,
+            },
+        )
+    }
+    fn mk_change_monthly_tax_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_monthly_tax_tx::ChangeMonthlyTaxTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                table_path,
+                column,
+                dependents,
+            },
+        )
+    }
+    fn mk_change_tax_column_tx(
+        &self,
+        emp_id: EmployeeId,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_tax_column_tx::ChangeTaxColumnTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                column,
+                dependents,
+            },
+        )
+    }
+    fn mk_change_social_insurance_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        prefecture: String,
+        grade: u32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_social_insurance_tx::ChangeSocialInsuranceTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                table_path,
+                prefecture,
+                grade,
+            },
+        )
+    }
+    fn mk_change_grade_tx(&self, emp_id: EmployeeId, grade: u32) -> Box<dyn Transaction<()>> {
+        Box::new(crate::change_grade_tx::ChangeGradeTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            grade,
+        })
+    }
+    fn mk_import_resident_tax_tx(&self, notices_path: String) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::import_resident_tax_tx::ImportResidentTaxTransactionImpl {
+                db: self.db.clone(),
+                notices_path,
+            },
+        )
+    }
+    fn mk_change_retirement_tx(
+        &self,
+        emp_id: EmployeeId,
+        election: RetirementElection,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::change_retirement_tx::ChangeRetirementTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                election,
+            },
+        )
+    }
+    fn mk_add_garnishment_tx(
+        &self,
+        emp_id: EmployeeId,
+        case_id: String,
+        priority: u32,
+        limit_rate: f32,
+        total: f32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(crate::add_garnishment_tx::AddGarnishmentTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            case_id,
+            priority,
+            limit_rate,
+            total,
+        })
+    }
+    fn mk_release_garnishment_tx(
+        &self,
+        emp_id: EmployeeId,
+        case_id: String,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::release_garnishment_tx::ReleaseGarnishmentTransactionImpl {
+                db: self.db.clone(),
+                emp_id,
+                case_id,
+            },
+        )
+    }
     fn mk_service_charge_tx(
         &self,
         member_id: MemberId,
@@ -211,10 +435,58 @@ impl TransactionFactory<()> for TransactionFactoryImpl {
             amount,
         })
     }
+    fn mk_reverse_service_charge_tx(
+        &self,
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+        date: chrono::NaiveDate,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::reverse_service_charge_tx::ReverseServiceChargeTransactionImpl {
+                db: self.db.clone(),
+                member_id,
+                charge_id,
+                date,
+            },
+        )
+    }
+    fn mk_advance_tx(
+        &self,
+        emp_id: EmployeeId,
+        advance_id: String,
+        date: chrono::NaiveDate,
+        principal: f32,
+        installment: f32,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(crate::advance_tx::AdvanceTransactionImpl {
+            db: self.db.clone(),
+            emp_id,
+            advance_id,
+            date,
+            principal,
+            installment,
+        })
+    }
     fn mk_payday_tx(&self, pay_date: chrono::NaiveDate) -> Box<dyn Transaction<()>> {
         Box::new(crate::payday_tx::PaydayTransactionImpl {
             db: self.db.clone(),
             pay_date,
+            threads: std::thread::available_parallelism().map_or(1, |n| n.get()),
         })
     }
+    fn mk_year_end_adjustment_tx(
+        &self,
+        year: i32,
+        table_path: String,
+        declarations_path: String,
+    ) -> Box<dyn Transaction<()>> {
+        Box::new(
+            crate::year_end_adjustment_tx::YearEndAdjustmentTransactionImpl {
+                db: self.db.clone(),
+                year,
+                table_path,
+                declarations_path,
+            },
+        )
+    }
 }
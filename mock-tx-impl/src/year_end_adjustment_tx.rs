@@ -0,0 +1,44 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{Database, EmployeeDao, HaveEmployeeDao};
+use payroll_impl::{
+    table::SharedTable,
+    tax::{YearEndDeclarations, YearEndTable},
+};
+use tx_app::Transaction;
+use tx_impl::general::YearEndAdjustmentTransaction;
+
+#[derive(Debug, Clone)]
+pub struct YearEndAdjustmentTransactionImpl<Db> {
+    pub db: Db,
+
+    pub year: i32,
+    pub table_path: String,
+    pub declarations_path: String,
+}
+impl<Db: Database> HaveEmployeeDao<Db::Ctx> for YearEndAdjustmentTransactionImpl<Db> {
+    fn dao(&self) -> Box<&impl EmployeeDao<Db::Ctx>> {
+        Box::new(self.db.dao())
+    }
+}
+impl<Db: Database> Transaction<()> for YearEndAdjustmentTransactionImpl<Db> {
+    fn execute(&self, _ctx: &mut ()) -> Result<(), UsecaseError> {
+        let table = SharedTable::<YearEndTable>::read(&self.table_path).map_err(|e| {
+            UsecaseError::TaxTableUnavailable {
+                path: self.table_path.clone(),
+                reason: e,
+            }
+        })?;
+        let declarations = std::fs::read_to_string(&self.declarations_path)
+            .map_err(|e| e.to_string())
+            .and_then(|s| s.parse::<YearEndDeclarations>())
+            .map_err(|e| UsecaseError::DeclarationsUnavailable {
+                path: self.declarations_path.clone(),
+                reason: e,
+            })?;
+        self.db.with_transaction(|tx| {
+            YearEndAdjustmentTransaction::execute(self, self.year, &table, &declarations.0).run(tx)
+        })
+    }
+}
@@ -1,5 +1,13 @@
+mod component;
+mod dues_policy;
 mod employee;
 mod paycheck;
+mod retirement_election;
+mod withholding_column;
 
+pub use component::Component;
+pub use dues_policy::DuesPolicy;
 pub use employee::Employee;
-pub use paycheck::Paycheck;
+pub use paycheck::{DeductionKind, DeductionLine, Paycheck};
+pub use retirement_election::RetirementElection;
+pub use withholding_column::WithholdingColumn;
@@ -0,0 +1,7 @@
+// a part of the pay of a composite employee, each kind as the employee paid by it alone
+#[derive(Debug, Clone, PartialEq)]
+pub enum Component {
+    Salaried { salary: f32 },
+    Hourly { hourly_rate: f32 },
+    Commissioned { salary: f32, commission_rate: f32 },
+}
@@ -0,0 +1,109 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::bo::Paycheck;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DuesPolicy {
+    // the book's rule: dues for every Friday in the pay period
+    PerFriday { dues: f32 },
+    PerPayPeriod { dues: f32 },
+    // prorated by the days of each month the pay period covers
+    PerMonth { dues: f32 },
+    PercentOfGross { rate: f32, cap: f32 },
+}
+impl DuesPolicy {
+    pub fn calculate_dues(&self, pc: &Paycheck) -> f32 {
+        let pay_period = pc.get_pay_period();
+        let days = pay_period
+            .start()
+            .iter_days()
+            .take_while(|d| d <= pay_period.end());
+        match *self {
+            DuesPolicy::PerFriday { dues } => {
+                days.filter(|d| d.weekday() == Weekday::Fri).count() as f32 * dues
+            }
+            DuesPolicy::PerPayPeriod { dues } => dues,
+            DuesPolicy::PerMonth { dues } => {
+                days.fold(0.0, |acc, d| acc + dues / days_in_month(d) as f32)
+            }
+            DuesPolicy::PercentOfGross { rate, cap } => (pc.get_gross_pay() * rate).min(cap),
+        }
+    }
+}
+
+fn days_in_month(date: NaiveDate) -> i64 {
+    let first = date.with_day(1).unwrap();
+    let next = if first.month() == 12 {
+        NaiveDate::from_ymd_opt(first.year() + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(first.year(), first.month() + 1, 1)
+    };
+    (next.unwrap() - first).num_days()
+}
+#[cfg(test)]
+mod test_dues_policy {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+    fn dues(policy: &DuesPolicy, start: NaiveDate, end: NaiveDate, gross_pay: f32) -> f32 {
+        let mut pc = Paycheck::new(start..=end);
+        pc.set_gross_pay(gross_pay);
+        policy.calculate_dues(&pc)
+    }
+    fn assert_near(actual: f32, expected: f32) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{} is not {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_per_month() {
+        let policy = DuesPolicy::PerMonth { dues: 30.0 };
+        // a whole month, whatever its length
+        assert_near(dues(&policy, date(8, 1), date(8, 31), 0.0), 30.0);
+        assert_near(dues(&policy, date(2, 1), date(2, 29), 0.0), 30.0);
+        // weekly
+        assert_near(
+            dues(&policy, date(8, 3), date(8, 9), 0.0),
+            30.0 * 7.0 / 31.0,
+        );
+        // a week and a biweekly period across two months of different lengths
+        assert_near(
+            dues(&policy, date(2, 26), date(3, 3), 0.0),
+            30.0 * 4.0 / 29.0 + 30.0 * 3.0 / 31.0,
+        );
+        assert_near(
+            dues(&policy, date(4, 20), date(5, 3), 0.0),
+            30.0 * 11.0 / 30.0 + 30.0 * 3.0 / 31.0,
+        );
+        // a year's weekly paychecks come to twelve months' dues
+        let total = (0..52)
+            .map(|w| {
+                let start = date(1, 1) + chrono::Days::new(w * 7);
+                dues(&policy, start, start + chrono::Days::new(6), 0.0)
+            })
+            .sum::<f32>()
+            + dues(&policy, date(12, 30), date(12, 31), 0.0);
+        assert_near(total, 360.0);
+    }
+    #[test]
+    fn test_percent_of_gross() {
+        let policy = DuesPolicy::PercentOfGross {
+            rate: 0.02,
+            cap: 50.0,
+        };
+        // the cap is per paycheck, whatever the length of its period
+        assert_near(dues(&policy, date(8, 3), date(8, 9), 1000.0), 20.0);
+        assert_near(dues(&policy, date(8, 3), date(8, 16), 2000.0), 40.0);
+        assert_near(dues(&policy, date(8, 1), date(8, 31), 4000.0), 50.0);
+        assert_near(dues(&policy, date(8, 3), date(8, 9), 3000.0), 50.0);
+        assert_near(dues(&policy, date(8, 1), date(8, 31), 2500.0), 50.0);
+        assert_near(dues(&policy, date(8, 1), date(8, 31), 0.0), 0.0);
+    }
+}
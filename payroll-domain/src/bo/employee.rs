@@ -1,29 +1,64 @@
 use chrono::NaiveDate;
-use std::{cell::RefCell, fmt::Debug, ops::RangeInclusive, rc::Rc};
+use std::{
+    fmt::Debug,
+    ops::RangeInclusive,
+    sync::{Arc, RwLock},
+};
 
-use crate::bo::Paycheck;
+use crate::bo::{DeductionKind, DeductionLine, Paycheck};
 use crate::interface::{Affiliation, PaymentClassification, PaymentMethod, PaymentSchedule};
 use crate::types::EmployeeId;
 
-#[derive(Debug, Clone)]
+#[derive(Debug)]
 pub struct Employee {
     emp_id: EmployeeId,
     name: String,
     address: String,
-    classification: Rc<RefCell<dyn PaymentClassification>>,
-    schedule: Rc<RefCell<dyn PaymentSchedule>>,
-    method: Rc<RefCell<dyn PaymentMethod>>,
-    affiliation: Rc<RefCell<dyn Affiliation>>,
+    classification: Arc<RwLock<dyn PaymentClassification>>,
+    schedule: Arc<RwLock<dyn PaymentSchedule>>,
+    method: Arc<RwLock<dyn PaymentMethod>>,
+    affiliations: Vec<Arc<RwLock<dyn Affiliation>>>,
+
+    net_pay_floor: f32,
+    // deductions carried forward to the next paycheck
+    arrears: Vec<DeductionLine>,
+
+    // the version of the stored employee this one was fetched at; the dao rejects
+    // updates made to a stale one
+    version: u32,
+}
+// a clone is a deep copy: the classification, schedule, method and affiliations
+// are not shared, so that changes made to a fetched employee never reach the
+// stored one until it is updated
+impl Clone for Employee {
+    fn clone(&self) -> Self {
+        Self {
+            emp_id: self.emp_id,
+            name: self.name.clone(),
+            address: self.address.clone(),
+            classification: self.classification.read().unwrap().clone_cell(),
+            schedule: self.schedule.read().unwrap().clone_cell(),
+            method: self.method.read().unwrap().clone_cell(),
+            affiliations: self
+                .affiliations
+                .iter()
+                .map(|a| a.read().unwrap().clone_cell())
+                .collect(),
+            net_pay_floor: self.net_pay_floor,
+            arrears: self.arrears.clone(),
+            version: self.version,
+        }
+    }
 }
 impl Employee {
     pub fn new(
         emp_id: EmployeeId,
         name: &str,
         address: &str,
-        classification: Rc<RefCell<dyn PaymentClassification>>,
-        schedule: Rc<RefCell<dyn PaymentSchedule>>,
-        method: Rc<RefCell<dyn PaymentMethod>>,
-        affiliation: Rc<RefCell<dyn Affiliation>>,
+        classification: Arc<RwLock<dyn PaymentClassification>>,
+        schedule: Arc<RwLock<dyn PaymentSchedule>>,
+        method: Arc<RwLock<dyn PaymentMethod>>,
+        affiliations: Vec<Arc<RwLock<dyn Affiliation>>>,
     ) -> Self {
         Self {
             emp_id,
@@ -32,49 +67,440 @@ impl Employee {
             classification,
             schedule,
             method,
-            affiliation,
+            affiliations,
+            net_pay_floor: 0.0,
+            arrears: vec![],
+            version: 0,
         }
     }
     pub fn is_pay_date(&self, date: NaiveDate) -> bool {
-        self.schedule.borrow().is_pay_date(date)
+        self.schedule.read().unwrap().is_pay_date(date)
     }
     pub fn get_pay_period(&self, date: NaiveDate) -> RangeInclusive<NaiveDate> {
-        self.schedule.borrow().get_pay_period(date)
+        self.schedule.read().unwrap().get_pay_period(date)
     }
-    pub fn payday(&self, pc: &mut Paycheck) {
-        let gross_pay = self.classification.borrow().calculate_pay(&pc);
-        let deductions = self.affiliation.borrow().calculate_deductions(&pc);
-        let net_pay = gross_pay - deductions;
+    pub fn payday(&mut self, pc: &mut Paycheck) {
+        self.make_out(pc);
+        self.disburse(pc);
+    }
+    // calculates the paycheck without paying it out, touching nothing but this employee
+    pub fn make_out(&mut self, pc: &mut Paycheck) {
+        let gross_pay = self.classification.read().unwrap().calculate_pay(pc);
+        // deductions may depend on the gross pay of this paycheck
         pc.set_gross_pay(gross_pay);
+        // deductions are made in the order of their kind; within a kind, arrears of
+        // earlier paychecks come first, then the affiliations in the order they were added
+        let mut sources = std::mem::take(&mut self.arrears)
+            .into_iter()
+            .map(DeductionSource::Arrears)
+            .chain(
+                self.affiliations
+                    .iter()
+                    .cloned()
+                    .map(DeductionSource::Affiliation),
+            )
+            .collect::<Vec<_>>();
+        sources.sort_by_key(|s| s.get_deduction_kind());
+        // deductions never take the net pay below the floor
+        let mut available = gross_pay - self.net_pay_floor;
+        for source in sources.iter() {
+            // each affiliation sees the lines deducted before it, e.g. for withholding
+            match source {
+                DeductionSource::Arrears(line) => {
+                    let applied = DeductionLine::new(
+                        line.get_kind(),
+                        &format!("{} (arrears)", line.get_description()),
+                        line.get_amount(),
+                    );
+                    let shortfall = deduct(pc, &mut available, applied);
+                    self.carry(shortfall, line);
+                }
+                DeductionSource::Affiliation(affiliation) => {
+                    let lines = affiliation.read().unwrap().calculate_deduction_lines(pc);
+                    let carries_arrears = affiliation.read().unwrap().carries_arrears();
+                    for line in lines {
+                        let shortfall = deduct(pc, &mut available, line.clone());
+                        if carries_arrears {
+                            self.carry(shortfall, &line);
+                        }
+                    }
+                    let contributions = affiliation
+                        .read()
+                        .unwrap()
+                        .calculate_employer_contributions(pc);
+                    for contribution in contributions {
+                        pc.add_employer_contribution(contribution);
+                    }
+                }
+            }
+        }
+        for line in self.arrears.iter() {
+            pc.add_arrears(line.clone());
+        }
+        let deductions = pc
+            .get_deduction_lines()
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount());
+        let net_pay = gross_pay - deductions;
         pc.set_deductions(deductions);
         pc.set_net_pay(net_pay);
-        self.method.borrow().pay(pc);
+        for affiliation in self.affiliations.iter() {
+            affiliation.write().unwrap().record_payment(pc);
+        }
+    }
+    // pays out money other than the pay of a period, e.g. an advance
+    pub fn disburse(&self, pc: &Paycheck) {
+        self.method.read().unwrap().pay(pc);
+    }
+    // what could not be deducted is deducted from the next paycheck
+    fn carry(&mut self, shortfall: f32, line: &DeductionLine) {
+        if shortfall > 0.0 {
+            self.arrears.push(DeductionLine::new(
+                line.get_kind(),
+                line.get_description(),
+                shortfall,
+            ));
+        }
+    }
+    pub fn get_arrears(&self) -> &[DeductionLine] {
+        &self.arrears
+    }
+    pub fn set_arrears(&mut self, arrears: Vec<DeductionLine>) {
+        self.arrears = arrears;
+    }
+    pub fn get_net_pay_floor(&self) -> f32 {
+        self.net_pay_floor
+    }
+    pub fn set_net_pay_floor(&mut self, net_pay_floor: f32) {
+        self.net_pay_floor = net_pay_floor;
+    }
+    pub fn get_version(&self) -> u32 {
+        self.version
+    }
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
     }
     pub fn get_emp_id(&self) -> EmployeeId {
         self.emp_id
     }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
     pub fn set_name(&mut self, name: &str) {
         self.name = name.to_string();
     }
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
     pub fn set_address(&mut self, address: &str) {
         self.address = address.to_string();
     }
-    pub fn get_classification(&self) -> Rc<RefCell<dyn PaymentClassification>> {
+    pub fn get_classification(&self) -> Arc<RwLock<dyn PaymentClassification>> {
         self.classification.clone()
     }
-    pub fn set_classification(&mut self, classification: Rc<RefCell<dyn PaymentClassification>>) {
+    pub fn set_classification(&mut self, classification: Arc<RwLock<dyn PaymentClassification>>) {
         self.classification = classification;
     }
-    pub fn set_schedule(&mut self, schedule: Rc<RefCell<dyn PaymentSchedule>>) {
+    pub fn get_schedule(&self) -> Arc<RwLock<dyn PaymentSchedule>> {
+        self.schedule.clone()
+    }
+    pub fn set_schedule(&mut self, schedule: Arc<RwLock<dyn PaymentSchedule>>) {
         self.schedule = schedule;
     }
-    pub fn set_method(&mut self, method: Rc<RefCell<dyn PaymentMethod>>) {
+    pub fn get_method(&self) -> Arc<RwLock<dyn PaymentMethod>> {
+        self.method.clone()
+    }
+    pub fn set_method(&mut self, method: Arc<RwLock<dyn PaymentMethod>>) {
         self.method = method;
     }
-    pub fn get_affiliation(&self) -> Rc<RefCell<dyn Affiliation>> {
-        self.affiliation.clone()
+    pub fn get_affiliation(&self, name: &str) -> Option<Arc<RwLock<dyn Affiliation>>> {
+        self.affiliations
+            .iter()
+            .find(|a| a.read().unwrap().get_name() == name)
+            .cloned()
+    }
+    pub fn get_affiliations(&self) -> Vec<Arc<RwLock<dyn Affiliation>>> {
+        self.affiliations.clone()
+    }
+    pub fn add_affiliation(&mut self, affiliation: Arc<RwLock<dyn Affiliation>>) {
+        // an affiliation with the same name is replaced
+        let name = affiliation.read().unwrap().get_name().to_string();
+        self.remove_affiliation(&name);
+        self.affiliations.push(affiliation);
+    }
+    pub fn remove_affiliation(&mut self, name: &str) -> Option<Arc<RwLock<dyn Affiliation>>> {
+        let pos = self
+            .affiliations
+            .iter()
+            .position(|a| a.read().unwrap().get_name() == name)?;
+        Some(self.affiliations.remove(pos))
+    }
+}
+
+enum DeductionSource {
+    Arrears(DeductionLine),
+    Affiliation(Arc<RwLock<dyn Affiliation>>),
+}
+impl DeductionSource {
+    fn get_deduction_kind(&self) -> DeductionKind {
+        match self {
+            DeductionSource::Arrears(line) => line.get_kind(),
+            DeductionSource::Affiliation(a) => a.read().unwrap().get_deduction_kind(),
+        }
+    }
+}
+
+// deducts as much of the line as is available, refunds are always made;
+// returns the shortfall rounded to cents
+fn deduct(pc: &mut Paycheck, available: &mut f32, line: DeductionLine) -> f32 {
+    let amount = line.get_amount();
+    let taken = if amount <= 0.0 {
+        amount
+    } else {
+        amount.min(available.max(0.0))
+    };
+    *available -= taken;
+    if taken != 0.0 {
+        pc.add_deduction_line(DeductionLine::new(
+            line.get_kind(),
+            line.get_description(),
+            taken,
+        ));
+    }
+    ((amount - taken) * 100.0).round() / 100.0
+}
+#[cfg(test)]
+mod test_employee {
+    use super::*;
+    use std::any::Any;
+
+    #[derive(Debug, Clone)]
+    struct Salary(f32);
+    impl PaymentClassification for Salary {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+        fn calculate_pay(&self, _pc: &Paycheck) -> f32 {
+            self.0
+        }
+    }
+    #[derive(Debug, Clone)]
+    struct Daily;
+    impl PaymentSchedule for Daily {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn is_pay_date(&self, _date: NaiveDate) -> bool {
+            true
+        }
+        fn get_pay_period(&self, pay_date: NaiveDate) -> RangeInclusive<NaiveDate> {
+            pay_date..=pay_date
+        }
+    }
+    #[derive(Debug, Clone)]
+    struct Hold;
+    impl PaymentMethod for Hold {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn pay(&self, _pc: &Paycheck) {}
+    }
+    #[derive(Debug, Clone)]
+    struct Deduction {
+        name: &'static str,
+        kind: DeductionKind,
+        amount: f32,
+        carries_arrears: bool,
+    }
+    impl Affiliation for Deduction {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+        fn as_any_mut(&mut self) -> &mut dyn Any {
+            self
+        }
+        fn get_name(&self) -> &str {
+            self.name
+        }
+        fn get_deduction_kind(&self) -> DeductionKind {
+            self.kind
+        }
+        fn calculate_deductions(&self, _pc: &Paycheck) -> f32 {
+            self.amount
+        }
+        fn carries_arrears(&self) -> bool {
+            self.carries_arrears
+        }
+    }
+
+    fn deduction(
+        name: &'static str,
+        kind: DeductionKind,
+        amount: f32,
+    ) -> Arc<RwLock<dyn Affiliation>> {
+        Arc::new(RwLock::new(Deduction {
+            name,
+            kind,
+            amount,
+            carries_arrears: true,
+        }))
+    }
+    fn employee(salary: f32, affiliations: Vec<Arc<RwLock<dyn Affiliation>>>) -> Employee {
+        Employee::new(
+            1,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(Salary(salary))),
+            Arc::new(RwLock::new(Daily)),
+            Arc::new(RwLock::new(Hold)),
+            affiliations,
+        )
+    }
+    fn make_out(emp: &mut Employee, day: u32) -> Paycheck {
+        let pay_date = NaiveDate::from_ymd_opt(2024, 8, day).unwrap();
+        let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+        emp.make_out(&mut pc);
+        pc
+    }
+    fn lines(lines: &[DeductionLine]) -> Vec<(&str, f32)> {
+        lines
+            .iter()
+            .map(|l| (l.get_description(), l.get_amount()))
+            .collect()
+    }
+
+    #[test]
+    fn test_order_of_kinds() {
+        let mut emp = employee(
+            1000.0,
+            vec![
+                deduction("Dues", DeductionKind::General, 10.0),
+                deduction("Income tax", DeductionKind::IncomeTax, 20.0),
+                deduction("Garnishment", DeductionKind::Garnishment, 40.0),
+                deduction("Health insurance", DeductionKind::SocialInsurance, 30.0),
+                deduction("Loan", DeductionKind::General, 50.0),
+            ],
+        );
+        let pc = make_out(&mut emp, 1);
+        // added in a different order, the affiliations of a kind in the order added
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![
+                ("Health insurance", 30.0),
+                ("Income tax", 20.0),
+                ("Garnishment", 40.0),
+                ("Dues", 10.0),
+                ("Loan", 50.0),
+            ]
+        );
+        assert_eq!(pc.get_deductions(), 150.0);
+        assert_eq!(pc.get_net_pay(), 850.0);
+    }
+    #[test]
+    fn test_net_pay_floor() {
+        let mut emp = employee(
+            100.0,
+            vec![
+                deduction("Health insurance", DeductionKind::SocialInsurance, 30.0),
+                deduction("Dues", DeductionKind::General, 20.0),
+                deduction("Loan", DeductionKind::General, 5.0),
+            ],
+        );
+        emp.set_net_pay_floor(60.0);
+        let pc = make_out(&mut emp, 1);
+        // dues cut short and the loan dropped, to keep the floor
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![("Health insurance", 30.0), ("Dues", 10.0)]
+        );
+        assert_eq!(pc.get_net_pay(), 60.0);
+        // the shortfalls carried as arrears
+        assert_eq!(
+            lines(emp.get_arrears()),
+            vec![("Dues", 10.0), ("Loan", 5.0)]
+        );
+        assert_eq!(lines(pc.get_arrears()), lines(emp.get_arrears()));
+    }
+    #[test]
+    fn test_no_arrears() {
+        let mut emp = employee(
+            100.0,
+            vec![Arc::new(RwLock::new(Deduction {
+                name: "Retirement plan",
+                kind: DeductionKind::PreTax,
+                amount: 50.0,
+                carries_arrears: false,
+            }))],
+        );
+        emp.set_net_pay_floor(80.0);
+        let pc = make_out(&mut emp, 1);
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![("Retirement plan", 20.0)]
+        );
+        assert!(emp.get_arrears().is_empty());
+    }
+    #[test]
+    fn test_arrears_repaid() {
+        let mut emp = employee(
+            100.0,
+            vec![
+                deduction("Health insurance", DeductionKind::SocialInsurance, 30.0),
+                deduction("Dues", DeductionKind::General, 20.0),
+            ],
+        );
+        emp.set_net_pay_floor(60.0);
+        make_out(&mut emp, 1);
+        assert_eq!(lines(emp.get_arrears()), vec![("Dues", 10.0)]);
+        // the arrears come first within their kind
+        emp.set_net_pay_floor(0.0);
+        let pc = make_out(&mut emp, 2);
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![
+                ("Health insurance", 30.0),
+                ("Dues (arrears)", 10.0),
+                ("Dues", 20.0),
+            ]
+        );
+        assert_eq!(pc.get_net_pay(), 40.0);
+        assert!(emp.get_arrears().is_empty());
+        assert!(pc.get_arrears().is_empty());
+    }
+    #[test]
+    fn test_arrears_carried_again() {
+        let mut emp = employee(100.0, vec![deduction("Dues", DeductionKind::General, 20.0)]);
+        emp.set_net_pay_floor(90.0);
+        make_out(&mut emp, 1);
+        // the arrears take what is available before the dues of the paycheck
+        let pc = make_out(&mut emp, 2);
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![("Dues (arrears)", 10.0)]
+        );
+        // repaid, while the dues are carried in turn
+        assert_eq!(lines(emp.get_arrears()), vec![("Dues", 20.0)]);
     }
-    pub fn set_affiliation(&mut self, affiliation: Rc<RefCell<dyn Affiliation>>) {
-        self.affiliation = affiliation;
+    #[test]
+    fn test_refund_under_floor() {
+        let mut emp = employee(
+            100.0,
+            vec![
+                deduction("Income tax", DeductionKind::IncomeTax, -15.0),
+                deduction("Dues", DeductionKind::General, 20.0),
+            ],
+        );
+        emp.set_net_pay_floor(100.0);
+        let pc = make_out(&mut emp, 1);
+        // a refund is made even at the floor, and what it adds can be deducted
+        assert_eq!(
+            lines(pc.get_deduction_lines()),
+            vec![("Income tax", -15.0), ("Dues", 15.0)]
+        );
+        assert_eq!(pc.get_net_pay(), 100.0);
+        assert_eq!(lines(emp.get_arrears()), vec![("Dues", 5.0)]);
     }
 }
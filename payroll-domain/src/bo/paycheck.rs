@@ -1,13 +1,59 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Days, NaiveDate};
+use serde::{Deserialize, Serialize};
 use std::{fmt::Debug, ops::RangeInclusive};
 
-#[derive(Debug, Clone, PartialEq)]
+// deductions are made in this order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum DeductionKind {
+    // deducted from the pay before income tax is withheld
+    SocialInsurance,
+    PreTax,
+    IncomeTax,
+    ResidentTax,
+    // limited by the disposable earnings, ahead of voluntary deductions
+    Garnishment,
+    General,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeductionLine {
+    kind: DeductionKind,
+    description: String,
+    amount: f32,
+}
+impl DeductionLine {
+    pub fn new(kind: DeductionKind, description: &str, amount: f32) -> Self {
+        Self {
+            kind,
+            description: description.to_string(),
+            amount,
+        }
+    }
+    pub fn get_kind(&self) -> DeductionKind {
+        self.kind
+    }
+    pub fn get_description(&self) -> &str {
+        &self.description
+    }
+    pub fn get_amount(&self) -> f32 {
+        self.amount
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Paycheck {
     period: RangeInclusive<NaiveDate>,
 
     gross_pay: f32,
     deductions: f32,
     net_pay: f32,
+
+    deduction_lines: Vec<DeductionLine>,
+    // paid by the employer, not part of the deductions
+    employer_contributions: Vec<DeductionLine>,
+    // could not be deducted without taking the net pay below the floor,
+    // carried forward to the next paycheck
+    arrears: Vec<DeductionLine>,
 }
 impl Paycheck {
     pub fn new(period: RangeInclusive<NaiveDate>) -> Self {
@@ -16,18 +62,86 @@ impl Paycheck {
             gross_pay: 0.0,
             deductions: 0.0,
             net_pay: 0.0,
+            deduction_lines: vec![],
+            employer_contributions: vec![],
+            arrears: vec![],
         }
     }
     pub fn get_pay_period(&self) -> RangeInclusive<NaiveDate> {
         self.period.clone()
     }
+    // the last day of a month falling in the pay period, monthly deductions are
+    // made on the paycheck covering it
+    pub fn get_month_end(&self) -> Option<NaiveDate> {
+        let next_day = *self.period.end() + Days::new(1);
+        let first_day = next_day.with_day(1)?;
+        let month_end = first_day - Days::new(1);
+        self.period.contains(&month_end).then_some(month_end)
+    }
+    pub fn get_gross_pay(&self) -> f32 {
+        self.gross_pay
+    }
     pub fn set_gross_pay(&mut self, gross_pay: f32) {
         self.gross_pay = gross_pay;
     }
+    pub fn get_deductions(&self) -> f32 {
+        self.deductions
+    }
     pub fn set_deductions(&mut self, deductions: f32) {
         self.deductions = deductions;
     }
+    pub fn get_net_pay(&self) -> f32 {
+        self.net_pay
+    }
     pub fn set_net_pay(&mut self, net_pay: f32) {
         self.net_pay = net_pay;
     }
+    pub fn get_deduction_lines(&self) -> &[DeductionLine] {
+        &self.deduction_lines
+    }
+    pub fn add_deduction_line(&mut self, line: DeductionLine) {
+        self.deduction_lines.push(line);
+    }
+    pub fn get_employer_contributions(&self) -> &[DeductionLine] {
+        &self.employer_contributions
+    }
+    pub fn add_employer_contribution(&mut self, line: DeductionLine) {
+        self.employer_contributions.push(line);
+    }
+    pub fn get_arrears(&self) -> &[DeductionLine] {
+        &self.arrears
+    }
+    pub fn add_arrears(&mut self, line: DeductionLine) {
+        self.arrears.push(line);
+    }
+    // gross pay less the social insurance and pre-tax contributions deducted so far
+    pub fn get_taxable_pay(&self) -> f32 {
+        self.deduction_lines
+            .iter()
+            .filter(|l| {
+                l.get_kind() == DeductionKind::SocialInsurance
+                    || l.get_kind() == DeductionKind::PreTax
+            })
+            .fold(self.gross_pay, |acc, l| acc - l.get_amount())
+    }
+    // gross pay less the deductions required by law made so far
+    pub fn get_disposable_earnings(&self) -> f32 {
+        self.deduction_lines
+            .iter()
+            .filter(|l| {
+                matches!(
+                    l.get_kind(),
+                    DeductionKind::SocialInsurance
+                        | DeductionKind::IncomeTax
+                        | DeductionKind::ResidentTax
+                )
+            })
+            .fold(self.gross_pay, |acc, l| acc - l.get_amount())
+    }
+    pub fn get_withheld_tax(&self) -> f32 {
+        self.deduction_lines
+            .iter()
+            .filter(|l| l.get_kind() == DeductionKind::IncomeTax)
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
 }
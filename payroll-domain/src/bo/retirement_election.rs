@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RetirementElection {
+    // of the gross pay
+    pub rate: f32,
+    // employee contributions per calendar year
+    pub annual_cap: f32,
+    // of the employee contribution
+    pub match_rate: f32,
+    // of the gross pay, contributions beyond it are not matched
+    pub match_limit: f32,
+    // employer contributions per calendar year
+    pub employer_annual_cap: f32,
+}
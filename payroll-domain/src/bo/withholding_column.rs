@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WithholdingColumn {
+    // 甲欄: the employee has filed the dependents declaration with us
+    Kou,
+    // 乙欄: the declaration is filed with another employer
+    Otsu,
+}
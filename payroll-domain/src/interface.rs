@@ -1,9 +1,27 @@
+// a trait `$cell` with a copy of the `$object` in a cell of its own, sharing nothing with
+// the original, for all of its implementations; `Employee::clone` copies each part so
+macro_rules! clone_cell {
+    ($cell:ident, $object:ident) => {
+        pub trait $cell {
+            fn clone_cell(&self) -> std::sync::Arc<std::sync::RwLock<dyn $object>>;
+        }
+        impl<T> $cell for T
+        where
+            T: $object + Clone + 'static,
+        {
+            fn clone_cell(&self) -> std::sync::Arc<std::sync::RwLock<dyn $object>> {
+                std::sync::Arc::new(std::sync::RwLock::new(self.clone()))
+            }
+        }
+    };
+}
+
 mod affiliation;
 mod payment_classification;
 mod payment_method;
 mod payment_schedule;
 
-pub use affiliation::Affiliation;
-pub use payment_classification::PaymentClassification;
-pub use payment_method::PaymentMethod;
-pub use payment_schedule::PaymentSchedule;
+pub use affiliation::{Affiliation, AffiliationCell};
+pub use payment_classification::{ClassificationCell, PaymentClassification};
+pub use payment_method::{MethodCell, PaymentMethod};
+pub use payment_schedule::{PaymentSchedule, ScheduleCell};
@@ -1,13 +1,42 @@
 use dyn_clone::DynClone;
 use std::{any::Any, fmt::Debug};
 
-use crate::bo::Paycheck;
+use crate::bo::{DeductionKind, DeductionLine, Paycheck};
 
-pub trait Affiliation: DynClone + Debug {
+pub trait Affiliation: DynClone + Debug + Send + Sync + AffiliationCell {
     fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
+    // identifies the affiliation among those of the same employee
+    fn get_name(&self) -> &str;
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::General
+    }
     fn calculate_deductions(&self, _pc: &Paycheck) -> f32 {
         0.0
     }
+    // itemized deductions shown on the paycheck, a single line by default
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let amount = self.calculate_deductions(pc);
+        if amount == 0.0 {
+            return vec![];
+        }
+        vec![DeductionLine::new(
+            self.get_deduction_kind(),
+            self.get_name(),
+            amount,
+        )]
+    }
+    // whether what could not be deducted is deducted from the next paycheck
+    fn carries_arrears(&self) -> bool {
+        true
+    }
+    // paid by the employer on top of the pay, not deducted from it
+    fn calculate_employer_contributions(&self, _pc: &Paycheck) -> Vec<DeductionLine> {
+        vec![]
+    }
+    // called once the paycheck is made out, to keep track of what has been paid
+    fn record_payment(&mut self, _pc: &Paycheck) {}
 }
 dyn_clone::clone_trait_object!(Affiliation);
+
+clone_cell!(AffiliationCell, Affiliation);
@@ -3,8 +3,11 @@ use std::{any::Any, fmt::Debug};
 
 use crate::bo::Paycheck;
 
-pub trait PaymentClassification: DynClone + Debug {
+pub trait PaymentClassification: DynClone + Debug + Send + Sync + ClassificationCell {
+    fn as_any(&self) -> &dyn Any;
     fn as_any_mut(&mut self) -> &mut dyn Any;
     fn calculate_pay(&self, pc: &Paycheck) -> f32;
 }
 dyn_clone::clone_trait_object!(PaymentClassification);
+
+clone_cell!(ClassificationCell, PaymentClassification);
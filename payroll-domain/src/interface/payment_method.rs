@@ -1,10 +1,13 @@
 use dyn_clone::DynClone;
-use std::fmt::Debug;
+use std::{any::Any, fmt::Debug};
 
 use crate::bo::Paycheck;
 
-pub trait PaymentMethod: DynClone + Debug {
+pub trait PaymentMethod: DynClone + Debug + Send + Sync + MethodCell {
+    fn as_any(&self) -> &dyn Any;
     // TODO: return type
     fn pay(&self, pc: &Paycheck);
 }
 dyn_clone::clone_trait_object!(PaymentMethod);
+
+clone_cell!(MethodCell, PaymentMethod);
@@ -1,9 +1,12 @@
 use chrono::NaiveDate;
 use dyn_clone::DynClone;
-use std::{fmt::Debug, ops::RangeInclusive};
+use std::{any::Any, fmt::Debug, ops::RangeInclusive};
 
-pub trait PaymentSchedule: DynClone + Debug {
+pub trait PaymentSchedule: DynClone + Debug + Send + Sync + ScheduleCell {
+    fn as_any(&self) -> &dyn Any;
     fn is_pay_date(&self, date: NaiveDate) -> bool;
     fn get_pay_period(&self, pay_date: NaiveDate) -> RangeInclusive<NaiveDate>;
 }
 dyn_clone::clone_trait_object!(PaymentSchedule);
+
+clone_cell!(ScheduleCell, PaymentSchedule);
@@ -1,2 +1,3 @@
 pub type EmployeeId = u32;
 pub type MemberId = u32;
+pub type ServiceChargeId = u32;
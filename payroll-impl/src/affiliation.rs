@@ -1,5 +1,11 @@
-mod no_affiliation;
+mod advance;
+mod garnishment;
+mod general_deduction;
+mod retirement_contribution;
 mod union_affiliation;
 
-pub use no_affiliation::NoAffiliation;
-pub use union_affiliation::UnionAffiliation;
+pub use advance::{Advance, Advances};
+pub use garnishment::{GarnishmentOrder, Garnishments};
+pub use general_deduction::GeneralDeduction;
+pub use retirement_contribution::RetirementContribution;
+pub use union_affiliation::{ServiceCharge, UnionAffiliation};
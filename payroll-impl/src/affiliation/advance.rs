@@ -0,0 +1,238 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::BTreeMap};
+
+use payroll_domain::{Affiliation, DeductionKind, DeductionLine, Paycheck};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advance {
+    advance_id: String,
+    disbursed_on: NaiveDate,
+    principal: f32,
+    installment: f32,
+    // by pay date
+    payments: BTreeMap<NaiveDate, f32>,
+}
+impl Advance {
+    // an advance of nothing, or one never repaid, is rejected
+    pub fn new(
+        advance_id: &str,
+        disbursed_on: NaiveDate,
+        principal: f32,
+        installment: f32,
+    ) -> Result<Self, String> {
+        if principal <= 0.0 {
+            return Err(format!("principal must be positive: {}", principal));
+        }
+        if installment <= 0.0 {
+            return Err(format!("installment must be positive: {}", installment));
+        }
+        Ok(Self {
+            advance_id: advance_id.to_string(),
+            disbursed_on,
+            principal,
+            installment,
+            payments: BTreeMap::new(),
+        })
+    }
+    pub fn get_advance_id(&self) -> &str {
+        &self.advance_id
+    }
+    pub fn get_disbursed_on(&self) -> NaiveDate {
+        self.disbursed_on
+    }
+    pub fn get_principal(&self) -> f32 {
+        self.principal
+    }
+    pub fn get_installment(&self) -> f32 {
+        self.installment
+    }
+    // what remains to be repaid before `pay_date`
+    pub fn get_balance(&self, pay_date: NaiveDate) -> f32 {
+        let repaid = self
+            .payments
+            .range(..pay_date)
+            .fold(0.0, |acc, (_, amount)| acc + amount);
+        ((self.principal - repaid).max(0.0) * 100.0).round() / 100.0
+    }
+    // the paycheck by which the advance is paid out
+    pub fn disbursement(&self) -> Paycheck {
+        let mut pc = Paycheck::new(self.disbursed_on..=self.disbursed_on);
+        pc.add_deduction_line(DeductionLine::new(
+            DeductionKind::General,
+            &format!("Disbursement of advance {}", self.advance_id),
+            -self.principal,
+        ));
+        pc.set_deductions(-self.principal);
+        pc.set_net_pay(self.principal);
+        pc
+    }
+    fn description(&self) -> String {
+        format!("Advance {}", self.advance_id)
+    }
+}
+
+// advances paid out to an employee, repaid by installments from the following paychecks
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Advances {
+    advances: Vec<Advance>,
+}
+impl Advances {
+    pub const NAME: &'static str = "Advances";
+
+    pub fn new() -> Self {
+        Self { advances: vec![] }
+    }
+    pub fn get_advance(&self, advance_id: &str) -> Option<Advance> {
+        self.advances
+            .iter()
+            .find(|a| a.advance_id == advance_id)
+            .cloned()
+    }
+    pub fn add_advance(&mut self, advance: Advance) {
+        self.advances.push(advance);
+    }
+}
+impl Default for Advances {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Affiliation for Advances {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    // the balance of the advances is what remains to be repaid
+    fn carries_arrears(&self) -> bool {
+        false
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.calculate_deduction_lines(pc)
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let pay_date = *pc.get_pay_period().end();
+        self.advances
+            .iter()
+            // repayment starts with the first paycheck after the disbursement
+            .filter(|a| a.disbursed_on < pay_date)
+            .map(|a| (a, a.installment.min(a.get_balance(pay_date))))
+            .filter(|(_, amount)| *amount > 0.0)
+            .map(|(a, amount)| DeductionLine::new(DeductionKind::General, &a.description(), amount))
+            .collect()
+    }
+    fn record_payment(&mut self, pc: &Paycheck) {
+        let pay_date = *pc.get_pay_period().end();
+        for advance in self.advances.iter_mut() {
+            let description = advance.description();
+            let repaid = pc
+                .get_deduction_lines()
+                .iter()
+                .filter(|l| l.get_description() == description)
+                .fold(0.0, |acc, l| acc + l.get_amount());
+            // a payday run again replaces what it recorded before
+            advance.payments.insert(pay_date, repaid);
+        }
+    }
+}
+#[cfg(test)]
+mod test_advance {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+
+    use payroll_domain::Employee;
+
+    use crate::{
+        classification::SalariedClassification, method::HoldMethod, schedule::MonthlySchedule,
+    };
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+    fn employee(advance: Advance) -> Employee {
+        let mut advances = Advances::new();
+        advances.add_advance(advance);
+        Employee::new(
+            1,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(SalariedClassification::new(1000.0))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![Arc::new(RwLock::new(advances))],
+        )
+    }
+    // the installment deducted from the paycheck on `pay_date`
+    fn repaid(emp: &mut Employee, pay_date: NaiveDate) -> f32 {
+        let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+        emp.make_out(&mut pc);
+        pc.get_deduction_lines()
+            .iter()
+            .filter(|l| l.get_description() == "Advance A-1")
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn balance(emp: &Employee, pay_date: NaiveDate) -> f32 {
+        let affiliation = emp.get_affiliation(Advances::NAME).unwrap();
+        let affiliation = affiliation.read().unwrap();
+        affiliation
+            .as_any()
+            .downcast_ref::<Advances>()
+            .unwrap()
+            .get_advance("A-1")
+            .unwrap()
+            .get_balance(pay_date)
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Advance::new("A-1", date(8, 15), 0.0, 100.0).is_err());
+        assert!(Advance::new("A-1", date(8, 15), 250.0, 0.0).is_err());
+    }
+    #[test]
+    fn test_final_installment() {
+        let mut emp = employee(Advance::new("A-1", date(8, 31), 250.0, 100.0).unwrap());
+        // not from the paycheck it is disbursed on
+        assert_eq!(repaid(&mut emp, date(8, 31)), 0.0);
+        assert_eq!(repaid(&mut emp, date(9, 30)), 100.0);
+        assert_eq!(repaid(&mut emp, date(10, 31)), 100.0);
+        // the last installment is what remains
+        assert_eq!(repaid(&mut emp, date(11, 30)), 50.0);
+        assert_eq!(balance(&emp, date(12, 31)), 0.0);
+        // repaid in full, nothing more is deducted
+        assert_eq!(repaid(&mut emp, date(12, 31)), 0.0);
+        assert_eq!(balance(&emp, date(12, 31)), 0.0);
+    }
+    #[test]
+    fn test_payday_run_again() {
+        let mut emp = employee(Advance::new("A-1", date(8, 15), 250.0, 100.0).unwrap());
+        assert_eq!(repaid(&mut emp, date(8, 31)), 100.0);
+        assert_eq!(repaid(&mut emp, date(8, 31)), 100.0);
+        assert_eq!(balance(&emp, date(9, 30)), 150.0);
+    }
+    #[test]
+    fn test_with_arrears() {
+        let mut emp = employee(Advance::new("A-1", date(8, 15), 300.0, 100.0).unwrap());
+        emp.set_net_pay_floor(900.0);
+        emp.set_arrears(vec![DeductionLine::new(
+            DeductionKind::General,
+            "Dues",
+            50.0,
+        )]);
+        // the arrears come first, the installment gets what the floor leaves
+        assert_eq!(repaid(&mut emp, date(8, 31)), 50.0);
+        // the rest is left in the balance rather than carried as arrears
+        assert!(emp.get_arrears().is_empty());
+        assert_eq!(balance(&emp, date(9, 30)), 250.0);
+        assert_eq!(repaid(&mut emp, date(9, 30)), 100.0);
+        assert_eq!(repaid(&mut emp, date(10, 31)), 100.0);
+        assert_eq!(repaid(&mut emp, date(11, 30)), 50.0);
+        assert_eq!(balance(&emp, date(12, 31)), 0.0);
+    }
+}
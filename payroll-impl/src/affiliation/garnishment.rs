@@ -0,0 +1,185 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::BTreeMap};
+
+use payroll_domain::{Affiliation, DeductionKind, DeductionLine, Paycheck};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GarnishmentOrder {
+    case_id: String,
+    // lower is deducted first
+    priority: u32,
+    // of the disposable earnings, together with the orders deducted before
+    limit_rate: f32,
+    total: f32,
+    // by pay date
+    payments: BTreeMap<NaiveDate, f32>,
+}
+impl GarnishmentOrder {
+    pub fn new(case_id: &str, priority: u32, limit_rate: f32, total: f32) -> Self {
+        Self {
+            case_id: case_id.to_string(),
+            priority,
+            limit_rate,
+            total,
+            payments: BTreeMap::new(),
+        }
+    }
+    pub fn get_case_id(&self) -> &str {
+        &self.case_id
+    }
+    pub fn get_priority(&self) -> u32 {
+        self.priority
+    }
+    pub fn get_total(&self) -> f32 {
+        self.total
+    }
+    // what remains to be paid before `pay_date`
+    pub fn get_balance(&self, pay_date: NaiveDate) -> f32 {
+        let paid = self
+            .payments
+            .range(..pay_date)
+            .fold(0.0, |acc, (_, amount)| acc + amount);
+        (self.total - paid).max(0.0)
+    }
+    fn description(&self) -> String {
+        format!("Garnishment {}", self.case_id)
+    }
+}
+
+// court orders of an employee, deducted in priority order until satisfied
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Garnishments {
+    // sorted by priority
+    orders: Vec<GarnishmentOrder>,
+}
+impl Garnishments {
+    pub const NAME: &'static str = "Garnishments";
+
+    pub fn new() -> Self {
+        Self { orders: vec![] }
+    }
+    pub fn get_order(&self, case_id: &str) -> Option<GarnishmentOrder> {
+        self.orders.iter().find(|o| o.case_id == case_id).cloned()
+    }
+    pub fn add_order(&mut self, order: GarnishmentOrder) {
+        self.orders.push(order);
+        self.orders.sort_by_key(|o| o.priority);
+    }
+    pub fn remove_order(&mut self, case_id: &str) -> Option<GarnishmentOrder> {
+        let pos = self.orders.iter().position(|o| o.case_id == case_id)?;
+        Some(self.orders.remove(pos))
+    }
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+}
+impl Default for Garnishments {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Affiliation for Garnishments {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::Garnishment
+    }
+    // the balance of the orders is what remains to be garnished
+    fn carries_arrears(&self) -> bool {
+        false
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.calculate_deduction_lines(pc)
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let pay_date = *pc.get_pay_period().end();
+        let disposable = pc.get_disposable_earnings().max(0.0);
+        let mut garnished = 0.0;
+        let mut lines = vec![];
+        for order in self.orders.iter() {
+            // the balance is money owed, the limit must not be exceeded even by a cent
+            let balance = (order.get_balance(pay_date) * 100.0).round() / 100.0;
+            let limit =
+                ((disposable * order.limit_rate - garnished).max(0.0) * 100.0).floor() / 100.0;
+            let amount = balance.min(limit);
+            if amount == 0.0 {
+                continue;
+            }
+            garnished += amount;
+            lines.push(DeductionLine::new(
+                DeductionKind::Garnishment,
+                &order.description(),
+                amount,
+            ));
+        }
+        lines
+    }
+    fn record_payment(&mut self, pc: &Paycheck) {
+        let pay_date = *pc.get_pay_period().end();
+        for order in self.orders.iter_mut() {
+            let description = order.description();
+            let paid = pc
+                .get_deduction_lines()
+                .iter()
+                .filter(|l| l.get_description() == description)
+                .fold(0.0, |acc, l| acc + l.get_amount());
+            // a payday run again replaces what it recorded before
+            order.payments.insert(pay_date, paid);
+        }
+    }
+}
+#[cfg(test)]
+mod test_garnishment {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+
+    use payroll_domain::Employee;
+
+    use crate::{
+        affiliation::GeneralDeduction, classification::SalariedClassification, method::HoldMethod,
+        schedule::MonthlySchedule,
+    };
+
+    #[test]
+    fn test_ahead_of_general_deductions() {
+        // the voluntary deduction is added first, but garnished after
+        let mut garnishments = Garnishments::new();
+        garnishments.add_order(GarnishmentOrder::new("CASE-1", 1, 0.25, 1000.0));
+        let mut emp = Employee::new(
+            1,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(SalariedClassification::new(1000.0))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![
+                Arc::new(RwLock::new(GeneralDeduction::new("Loan", 150.0))),
+                Arc::new(RwLock::new(garnishments)),
+            ],
+        );
+        emp.set_net_pay_floor(800.0);
+        let pay_date = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+        let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+        emp.make_out(&mut pc);
+
+        let lines = pc
+            .get_deduction_lines()
+            .iter()
+            .map(|l| (l.get_description().to_string(), l.get_amount()))
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![("Garnishment CASE-1".to_string(), 200.0)]);
+        // what the floor left of the pay is kept for the order, the loan waits
+        assert_eq!(emp.get_arrears().len(), 1);
+        assert_eq!(emp.get_arrears()[0].get_amount(), 150.0);
+    }
+}
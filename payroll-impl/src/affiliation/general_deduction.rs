@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+use payroll_domain::{Affiliation, Paycheck};
+
+// a fixed amount withheld from every paycheck, e.g. health insurance premium
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneralDeduction {
+    name: String,
+    amount: f32,
+}
+impl GeneralDeduction {
+    pub fn new(name: &str, amount: f32) -> Self {
+        Self {
+            name: name.to_string(),
+            amount,
+        }
+    }
+    pub fn get_amount(&self) -> f32 {
+        self.amount
+    }
+}
+impl Affiliation for GeneralDeduction {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+    fn calculate_deductions(&self, _pc: &Paycheck) -> f32 {
+        self.amount
+    }
+}
@@ -0,0 +1,210 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::BTreeMap};
+
+use payroll_domain::{Affiliation, DeductionKind, DeductionLine, Paycheck, RetirementElection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Contribution {
+    employee: f32,
+    employer: f32,
+}
+
+// defined-contribution plan, deducted before income tax is withheld
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetirementContribution {
+    election: RetirementElection,
+    // contributions made, by pay date
+    contributions: BTreeMap<NaiveDate, Contribution>,
+}
+impl RetirementContribution {
+    pub const NAME: &'static str = "Retirement plan";
+    pub const MATCH: &'static str = "Retirement plan match";
+
+    pub fn new(election: RetirementElection) -> Self {
+        Self {
+            election,
+            contributions: BTreeMap::new(),
+        }
+    }
+    pub fn get_election(&self) -> RetirementElection {
+        self.election
+    }
+    // contributions made so far are kept for the annual caps
+    pub fn set_election(&mut self, election: RetirementElection) {
+        self.election = election;
+    }
+    // contributions paid in the year of `pay_date` before it
+    pub fn get_year_to_date(&self, pay_date: NaiveDate) -> (f32, f32) {
+        let first_day = pay_date.with_ordinal(1).unwrap_or(pay_date);
+        self.contributions
+            .range(first_day..pay_date)
+            .fold((0.0, 0.0), |(employee, employer), (_, c)| {
+                (employee + c.employee, employer + c.employer)
+            })
+    }
+    fn calculate_employee_contribution(&self, pc: &Paycheck) -> f32 {
+        let e = &self.election;
+        let (employee_ytd, _) = self.get_year_to_date(*pc.get_pay_period().end());
+        let employee = (pc.get_gross_pay() * e.rate).min((e.annual_cap - employee_ytd).max(0.0));
+        round_to_cents(employee)
+    }
+    // matches what has been deducted from the paycheck, which the net pay floor may
+    // have cut short
+    fn calculate_employer_contribution(&self, pc: &Paycheck) -> f32 {
+        let e = &self.election;
+        let (_, employer_ytd) = self.get_year_to_date(*pc.get_pay_period().end());
+        let employee = deducted(pc);
+        let employer = (employee.min(pc.get_gross_pay() * e.match_limit) * e.match_rate)
+            .min((e.employer_annual_cap - employer_ytd).max(0.0));
+        round_to_cents(employer)
+    }
+}
+fn deducted(pc: &Paycheck) -> f32 {
+    pc.get_deduction_lines()
+        .iter()
+        .filter(|l| l.get_description() == RetirementContribution::NAME)
+        .fold(0.0, |acc, l| acc + l.get_amount())
+}
+fn round_to_cents(amount: f32) -> f32 {
+    (amount * 100.0).round() / 100.0
+}
+impl Affiliation for RetirementContribution {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::PreTax
+    }
+    // contributions that could not be made are not made up for
+    fn carries_arrears(&self) -> bool {
+        false
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.calculate_employee_contribution(pc)
+    }
+    fn calculate_employer_contributions(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let employer = self.calculate_employer_contribution(pc);
+        if employer == 0.0 {
+            return vec![];
+        }
+        vec![DeductionLine::new(
+            DeductionKind::PreTax,
+            Self::MATCH,
+            employer,
+        )]
+    }
+    fn record_payment(&mut self, pc: &Paycheck) {
+        let employee = deducted(pc);
+        let employer = pc
+            .get_employer_contributions()
+            .iter()
+            .filter(|l| l.get_description() == Self::MATCH)
+            .fold(0.0, |acc, l| acc + l.get_amount());
+        // a payday run again replaces what it recorded before
+        self.contributions.insert(
+            *pc.get_pay_period().end(),
+            Contribution { employee, employer },
+        );
+    }
+}
+#[cfg(test)]
+mod test_retirement_contribution {
+    use super::*;
+    use std::sync::{Arc, RwLock};
+
+    use payroll_domain::Employee;
+
+    use crate::{
+        classification::SalariedClassification, method::HoldMethod, schedule::MonthlySchedule,
+    };
+
+    fn election(rate: f32, annual_cap: f32, match_rate: f32) -> RetirementElection {
+        RetirementElection {
+            rate,
+            annual_cap,
+            match_rate,
+            match_limit: 0.04,
+            employer_annual_cap: 30.0,
+        }
+    }
+    fn employee(election: RetirementElection, net_pay_floor: f32) -> Employee {
+        let mut emp = Employee::new(
+            1,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(SalariedClassification::new(1000.0))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![Arc::new(RwLock::new(RetirementContribution::new(election)))],
+        );
+        emp.set_net_pay_floor(net_pay_floor);
+        emp
+    }
+    // the contribution deducted and the employer's match
+    fn make_out(emp: &mut Employee, year: i32, month: u32) -> (f32, f32) {
+        let pay_date = NaiveDate::from_ymd_opt(year, month + 1, 1)
+            .unwrap_or(NaiveDate::from_ymd_opt(year + 1, 1, 1).unwrap())
+            .pred_opt()
+            .unwrap();
+        let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+        emp.make_out(&mut pc);
+        let employer = pc
+            .get_employer_contributions()
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount());
+        (deducted(&pc), employer)
+    }
+    fn year_to_date(emp: &Employee, pay_date: NaiveDate) -> (f32, f32) {
+        let affiliation = emp.get_affiliation(RetirementContribution::NAME).unwrap();
+        let affiliation = affiliation.read().unwrap();
+        affiliation
+            .as_any()
+            .downcast_ref::<RetirementContribution>()
+            .unwrap()
+            .get_year_to_date(pay_date)
+    }
+
+    #[test]
+    fn test_annual_caps() {
+        let mut emp = employee(election(0.1, 250.0, 0.5), 0.0);
+        let contributions = (1..=4)
+            .map(|month| make_out(&mut emp, 2024, month))
+            .collect::<Vec<_>>();
+        // 250 of the employee's and 30 of the employer's in the year
+        assert_eq!(
+            contributions,
+            vec![(100.0, 20.0), (100.0, 10.0), (50.0, 0.0), (0.0, 0.0)]
+        );
+        let year_end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        assert_eq!(year_to_date(&emp, year_end), (250.0, 30.0));
+        // both start over in the next year
+        assert_eq!(make_out(&mut emp, 2025, 1), (100.0, 20.0));
+    }
+    #[test]
+    fn test_match_limit() {
+        // 60 is contributed, but only 4% of the pay is matched
+        let mut emp = employee(election(0.06, 1000.0, 1.0), 0.0);
+        assert_eq!(make_out(&mut emp, 2024, 1), (60.0, 30.0));
+        let mut emp = employee(election(0.02, 1000.0, 1.0), 0.0);
+        assert_eq!(make_out(&mut emp, 2024, 1), (20.0, 20.0));
+    }
+    #[test]
+    fn test_net_pay_floor() {
+        // the floor leaves 15 of the 60 requested, which is what is matched
+        let mut emp = employee(election(0.06, 1000.0, 1.0), 985.0);
+        assert_eq!(make_out(&mut emp, 2024, 1), (15.0, 15.0));
+        // nothing deducted, nothing matched, and nothing made up for later
+        let mut emp = employee(election(0.06, 1000.0, 1.0), 1000.0);
+        assert_eq!(make_out(&mut emp, 2024, 1), (0.0, 0.0));
+        assert!(emp.get_arrears().is_empty());
+        let pay_date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(year_to_date(&emp, pay_date), (0.0, 0.0));
+    }
+}
@@ -1,48 +1,168 @@
-use chrono::{Datelike, NaiveDate, Weekday};
-use std::any::Any;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, ops::RangeInclusive};
 
-use payroll_domain::{Affiliation, MemberId, Paycheck};
+use payroll_domain::{
+    Affiliation, DeductionKind, DeductionLine, DuesPolicy, Employee, MemberId, Paycheck,
+    ServiceChargeId,
+};
 
-#[derive(Debug, Clone, PartialEq)]
-struct ServiceCharge {
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServiceCharge {
+    id: ServiceChargeId,
     date: NaiveDate,
     amount: f32,
+    reversed_on: Option<NaiveDate>,
+    // the pay dates of the paychecks it was deducted on and refunded on
+    #[serde(default)]
+    deducted_on: Option<NaiveDate>,
+    #[serde(default)]
+    refunded_on: Option<NaiveDate>,
 }
 impl ServiceCharge {
-    fn new(date: NaiveDate, amount: f32) -> Self {
-        Self { date, amount }
+    fn new(id: ServiceChargeId, date: NaiveDate, amount: f32) -> Self {
+        Self {
+            id,
+            date,
+            amount,
+            reversed_on: None,
+            deducted_on: None,
+            refunded_on: None,
+        }
+    }
+    pub fn get_id(&self) -> ServiceChargeId {
+        self.id
     }
-    fn get_date(&self) -> NaiveDate {
+    pub fn get_date(&self) -> NaiveDate {
         self.date
     }
-    fn get_amount(&self) -> f32 {
+    pub fn get_amount(&self) -> f32 {
         self.amount
     }
+    pub fn get_reversed_on(&self) -> Option<NaiveDate> {
+        self.reversed_on
+    }
+    pub fn get_deducted_on(&self) -> Option<NaiveDate> {
+        self.deducted_on
+    }
+    pub fn get_refunded_on(&self) -> Option<NaiveDate> {
+        self.refunded_on
+    }
+    // charged in its pay period unless reversed by the pay date; a payday run again
+    // deducts it again
+    fn is_charged(&self, pay_period: &RangeInclusive<NaiveDate>) -> bool {
+        let pay_date = *pay_period.end();
+        pay_period.contains(&self.date)
+            && self.reversed_on.is_none_or(|d| d > pay_date)
+            && self.deducted_on.is_none_or(|d| d == pay_date)
+    }
+    // refunded on the first paycheck after the reversal of one deducted before,
+    // whatever the date of the reversal
+    fn is_refunded(&self, pay_date: NaiveDate) -> bool {
+        self.deducted_on.is_some_and(|d| d < pay_date)
+            && self.reversed_on.is_some_and(|d| d <= pay_date)
+            && self.refunded_on.is_none_or(|d| d == pay_date)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct DuesChange {
+    effective: NaiveDate,
+    dues: DuesPolicy,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct UnionAffiliation {
     member_id: MemberId,
-    dues: f32,
+    dues: DuesPolicy,
+    // scheduled changes of dues, sorted by effective date
+    dues_changes: Vec<DuesChange>,
 
     service_charges: Vec<ServiceCharge>,
 }
 impl UnionAffiliation {
-    pub fn new(member_id: MemberId, dues: f32) -> Self {
+    pub const NAME: &'static str = "Union";
+
+    pub fn new(member_id: MemberId, dues: DuesPolicy) -> Self {
         Self {
             member_id,
             dues,
+            dues_changes: vec![],
             service_charges: vec![],
         }
     }
+    // none for an employee not in the union
+    pub fn member_id_of(emp: &Employee) -> Option<MemberId> {
+        emp.get_affiliation(Self::NAME).and_then(|a| {
+            a.read()
+                .unwrap()
+                .as_any()
+                .downcast_ref::<Self>()
+                .map(|a| a.get_member_id())
+        })
+    }
     pub fn get_member_id(&self) -> MemberId {
         self.member_id
     }
-    pub fn get_dues(&self) -> f32 {
-        self.dues
+    pub fn set_member_id(&mut self, member_id: MemberId) {
+        self.member_id = member_id;
+    }
+    pub fn get_dues(&self) -> DuesPolicy {
+        self.dues.clone()
+    }
+    // the scheduled changes by effective date
+    pub fn get_dues_changes(&self) -> Vec<(NaiveDate, DuesPolicy)> {
+        self.dues_changes
+            .iter()
+            .map(|c| (c.effective, c.dues.clone()))
+            .collect()
+    }
+    pub fn get_dues_at(&self, date: NaiveDate) -> DuesPolicy {
+        self.dues_changes
+            .iter()
+            .rev()
+            .find(|c| c.effective <= date)
+            .map_or(self.dues.clone(), |c| c.dues.clone())
+    }
+    // a dated change supersedes any change scheduled on or after that date,
+    // an undated one replaces the whole schedule
+    pub fn change_dues(&mut self, dues: DuesPolicy, effective: Option<NaiveDate>) {
+        match effective {
+            Some(effective) => {
+                self.dues_changes.retain(|c| c.effective < effective);
+                self.dues_changes.push(DuesChange { effective, dues });
+            }
+            None => {
+                self.dues = dues;
+                self.dues_changes.clear();
+            }
+        }
+    }
+    pub fn add_service_charge(&mut self, date: NaiveDate, amount: f32) -> ServiceChargeId {
+        let id = self
+            .service_charges
+            .iter()
+            .map(|sc| sc.get_id())
+            .max()
+            .unwrap_or(0)
+            + 1;
+        self.service_charges
+            .push(ServiceCharge::new(id, date, amount));
+        id
     }
-    pub fn add_service_charge(&mut self, date: NaiveDate, amount: f32) {
-        self.service_charges.push(ServiceCharge::new(date, amount));
+    pub fn get_service_charges(&self) -> &[ServiceCharge] {
+        &self.service_charges
+    }
+    pub fn get_service_charge(&self, id: ServiceChargeId) -> Option<ServiceCharge> {
+        self.service_charges
+            .iter()
+            .find(|sc| sc.get_id() == id)
+            .cloned()
+    }
+    pub fn reverse_service_charge(&mut self, id: ServiceChargeId, date: NaiveDate) {
+        if let Some(sc) = self.service_charges.iter_mut().find(|sc| sc.id == id) {
+            sc.reversed_on = Some(date);
+        }
     }
 }
 impl Affiliation for UnionAffiliation {
@@ -52,22 +172,111 @@ impl Affiliation for UnionAffiliation {
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
     fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
-        let mut total_deductions = 0.0;
+        self.calculate_deduction_lines(pc)
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
         let pay_period = pc.get_pay_period();
-        for d in pc.get_pay_period().start().iter_days() {
-            if d > *pay_period.end() {
-                break;
+        let mut lines = vec![];
+        // the dues in effect on the pay date apply to the whole period
+        let dues = self.get_dues_at(*pay_period.end()).calculate_dues(pc);
+        if dues != 0.0 {
+            lines.push(DeductionLine::new(
+                DeductionKind::General,
+                "Union dues",
+                dues,
+            ));
+        }
+        for sc in self.service_charges.iter() {
+            if sc.is_charged(&pay_period) {
+                lines.push(DeductionLine::new(
+                    DeductionKind::General,
+                    &format!("Service charge #{}", sc.get_id()),
+                    sc.get_amount(),
+                ));
             }
-            if d.weekday() == Weekday::Fri {
-                total_deductions += self.get_dues();
+            if sc.is_refunded(*pay_period.end()) {
+                lines.push(DeductionLine::new(
+                    DeductionKind::General,
+                    &format!("Refund of service charge #{}", sc.get_id()),
+                    -sc.get_amount(),
+                ));
             }
         }
-        for sc in self.service_charges.iter() {
-            if pay_period.contains(&sc.get_date()) {
-                total_deductions += sc.get_amount();
+        lines
+    }
+    // what could not be deducted is carried as arrears, so a charge counts as deducted
+    // once it is on a paycheck
+    fn record_payment(&mut self, pc: &Paycheck) {
+        let pay_period = pc.get_pay_period();
+        let pay_date = *pay_period.end();
+        for sc in self.service_charges.iter_mut() {
+            if sc.is_charged(&pay_period) {
+                sc.deducted_on = Some(pay_date);
+            } else if sc.deducted_on == Some(pay_date) {
+                sc.deducted_on = None;
             }
+            if sc.is_refunded(pay_date) {
+                sc.refunded_on = Some(pay_date);
+            } else if sc.refunded_on == Some(pay_date) {
+                sc.refunded_on = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_union_affiliation {
+    use super::*;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+    // weekly, paid on Friday
+    fn pay(affiliation: &mut UnionAffiliation, pay_date: NaiveDate) -> Vec<(String, f32)> {
+        let mut pc = Paycheck::new(pay_date - chrono::Days::new(6)..=pay_date);
+        for line in affiliation.calculate_deduction_lines(&pc) {
+            pc.add_deduction_line(line);
         }
-        total_deductions
+        affiliation.record_payment(&pc);
+        pc.get_deduction_lines()
+            .iter()
+            .map(|l| (l.get_description().to_string(), l.get_amount()))
+            .collect()
+    }
+
+    #[test]
+    fn test_reversed_before_payday() {
+        let mut affiliation = UnionAffiliation::new(7234, DuesPolicy::PerPayPeriod { dues: 0.0 });
+        let id = affiliation.add_service_charge(date(8, 5), 12.5);
+        affiliation.reverse_service_charge(id, date(8, 8));
+        assert_eq!(pay(&mut affiliation, date(8, 9)), vec![]);
+        assert_eq!(pay(&mut affiliation, date(8, 16)), vec![]);
+    }
+    #[test]
+    fn test_backdated_reversal() {
+        let mut affiliation = UnionAffiliation::new(7234, DuesPolicy::PerPayPeriod { dues: 0.0 });
+        let id = affiliation.add_service_charge(date(8, 5), 12.5);
+        assert_eq!(
+            pay(&mut affiliation, date(8, 9)),
+            vec![("Service charge #1".to_string(), 12.5)]
+        );
+        // reversed after the payday, as of a date in the period already paid
+        affiliation.reverse_service_charge(id, date(8, 6));
+        assert_eq!(
+            pay(&mut affiliation, date(8, 16)),
+            vec![("Refund of service charge #1".to_string(), -12.5)]
+        );
+        // the same if the payday is run again, but never on a later one
+        assert_eq!(
+            pay(&mut affiliation, date(8, 16)),
+            vec![("Refund of service charge #1".to_string(), -12.5)]
+        );
+        assert_eq!(pay(&mut affiliation, date(8, 23)), vec![]);
     }
 }
@@ -1,7 +1,12 @@
 mod commissioned_classification;
+mod composite_classification;
 mod hourly_classification;
 mod salaried_classification;
 
 pub use commissioned_classification::CommissionedClassification;
+pub use composite_classification::{
+    component_classification, component_schedule, find_classification_mut, schedule_of,
+    CompositeClassification,
+};
 pub use hourly_classification::HourlyClassification;
 pub use salaried_classification::SalariedClassification;
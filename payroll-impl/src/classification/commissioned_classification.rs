@@ -1,9 +1,10 @@
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 
 use payroll_domain::{Paycheck, PaymentClassification};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct SalesReceipt {
     date: NaiveDate,
     amount: f32,
@@ -20,13 +21,16 @@ impl SalesReceipt {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CommissionedClassification {
     salary: f32,
     commission_rate: f32,
     sales_receipts: Vec<SalesReceipt>,
 }
 impl PaymentClassification for CommissionedClassification {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -52,6 +56,13 @@ impl CommissionedClassification {
     pub fn add_sales_receipt(&mut self, date: NaiveDate, amount: f32) {
         self.sales_receipts.push(SalesReceipt::new(date, amount));
     }
+    // the date and amount of each sales receipt in the order they were posted
+    pub fn get_sales_receipts(&self) -> Vec<(NaiveDate, f32)> {
+        self.sales_receipts
+            .iter()
+            .map(|sr| (sr.get_date(), sr.get_amount()))
+            .collect()
+    }
     fn calculate_pay_for_sales_receipt(&self, sr: &SalesReceipt) -> f32 {
         self.commission_rate * sr.get_amount()
     }
@@ -0,0 +1,130 @@
+use std::{
+    any::Any,
+    sync::{Arc, RwLock},
+};
+
+use payroll_domain::{Component, Paycheck, PaymentClassification, PaymentSchedule};
+
+use crate::classification::{
+    CommissionedClassification, HourlyClassification, SalariedClassification,
+};
+use crate::record::ScheduleRecord;
+use crate::schedule::{BiweeklySchedule, MonthlySchedule, WeeklySchedule};
+
+pub fn component_classification(component: &Component) -> Box<dyn PaymentClassification> {
+    match *component {
+        Component::Salaried { salary } => Box::new(SalariedClassification::new(salary)),
+        Component::Hourly { hourly_rate } => Box::new(HourlyClassification::new(hourly_rate)),
+        Component::Commissioned {
+            salary,
+            commission_rate,
+        } => Box::new(CommissionedClassification::new(salary, commission_rate)),
+    }
+}
+pub fn component_schedule(component: &Component) -> Arc<RwLock<dyn PaymentSchedule>> {
+    match component {
+        Component::Salaried { .. } => Arc::new(RwLock::new(MonthlySchedule)),
+        Component::Hourly { .. } => Arc::new(RwLock::new(WeeklySchedule)),
+        Component::Commissioned { .. } => Arc::new(RwLock::new(BiweeklySchedule)),
+    }
+}
+// the schedule the components are paid on together; a salary is fixed for the
+// period of its schedule, while hours and sales are paid for whatever period they
+// fall in, so that only the salaries have to agree
+pub fn schedule_of(components: &[Component]) -> Result<Arc<RwLock<dyn PaymentSchedule>>, String> {
+    let first = components.first().ok_or("no components")?;
+    let mut salaries = components.iter().filter_map(salary_schedule);
+    match salaries.next() {
+        None => Ok(component_schedule(first)),
+        Some(schedule) if salaries.all(|s| s == schedule) => Ok(schedule.into_schedule()),
+        Some(_) => Err("salaries paid on different schedules".to_string()),
+    }
+}
+fn salary_schedule(component: &Component) -> Option<ScheduleRecord> {
+    match *component {
+        Component::Salaried { .. } => Some(ScheduleRecord::Monthly),
+        Component::Hourly { .. } => None,
+        Component::Commissioned { salary, .. } => {
+            (salary != 0.0).then_some(ScheduleRecord::Biweekly)
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompositeClassification {
+    components: Vec<Box<dyn PaymentClassification>>,
+}
+impl PaymentClassification for CompositeClassification {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn calculate_pay(&self, pc: &Paycheck) -> f32 {
+        self.components.iter().map(|c| c.calculate_pay(pc)).sum()
+    }
+}
+impl CompositeClassification {
+    pub fn new(components: Vec<Box<dyn PaymentClassification>>) -> Self {
+        Self { components }
+    }
+    pub fn get_components(&self) -> &[Box<dyn PaymentClassification>] {
+        &self.components
+    }
+    pub fn component_mut<T: PaymentClassification + 'static>(&mut self) -> Option<&mut T> {
+        self.components
+            .iter_mut()
+            .find_map(|c| find_classification_mut::<T>(c.as_mut()))
+    }
+}
+
+// the classification itself if it is a `T`, otherwise the first `T` component of a composite
+pub fn find_classification_mut<T: PaymentClassification + 'static>(
+    classification: &mut dyn PaymentClassification,
+) -> Option<&mut T> {
+    if classification.as_any_mut().is::<T>() {
+        return classification.as_any_mut().downcast_mut::<T>();
+    }
+    classification
+        .as_any_mut()
+        .downcast_mut::<CompositeClassification>()?
+        .component_mut::<T>()
+}
+#[cfg(test)]
+mod test_composite_classification {
+    use super::*;
+
+    fn schedule(components: &[Component]) -> Result<ScheduleRecord, String> {
+        schedule_of(components).map(|s| ScheduleRecord::from_schedule(&*s.read().unwrap()).unwrap())
+    }
+
+    #[test]
+    fn test_schedule_of() {
+        let salaried = Component::Salaried { salary: 1800.0 };
+        let hourly = Component::Hourly { hourly_rate: 12.5 };
+        let commission = Component::Commissioned {
+            salary: 0.0,
+            commission_rate: 0.05,
+        };
+        let commissioned = Component::Commissioned {
+            salary: 1000.0,
+            commission_rate: 0.05,
+        };
+        assert_eq!(
+            schedule(&[salaried.clone(), hourly.clone(), commission.clone()]),
+            Ok(ScheduleRecord::Monthly)
+        );
+        assert_eq!(
+            schedule(&[hourly.clone(), commissioned.clone()]),
+            Ok(ScheduleRecord::Biweekly)
+        );
+        // as the leading one alone without any salary
+        assert_eq!(
+            schedule(&[hourly.clone(), commission]),
+            Ok(ScheduleRecord::Weekly)
+        );
+        assert!(schedule(&[salaried, hourly, commissioned]).is_err());
+        assert!(schedule(&[]).is_err());
+    }
+}
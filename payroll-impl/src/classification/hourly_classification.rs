@@ -1,9 +1,10 @@
 use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 
 use payroll_domain::{Paycheck, PaymentClassification};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct TimeCard {
     date: NaiveDate,
     hours: f32,
@@ -20,12 +21,15 @@ impl TimeCard {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HourlyClassification {
     hourly_rate: f32,
     timecards: Vec<TimeCard>,
 }
 impl PaymentClassification for HourlyClassification {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -50,6 +54,13 @@ impl HourlyClassification {
     pub fn add_timecard(&mut self, date: NaiveDate, hours: f32) {
         self.timecards.push(TimeCard::new(date, hours));
     }
+    // the date and hours of each timecard in the order they were posted
+    pub fn get_timecards(&self) -> Vec<(NaiveDate, f32)> {
+        self.timecards
+            .iter()
+            .map(|tc| (tc.get_date(), tc.get_hours()))
+            .collect()
+    }
     fn calculate_pay_for_timecard(&self, tc: &TimeCard) -> f32 {
         let hours = tc.get_hours();
         let overtime = (hours - 8.0).max(0.0);
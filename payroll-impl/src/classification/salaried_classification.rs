@@ -1,12 +1,16 @@
+use serde::{Deserialize, Serialize};
 use std::any::Any;
 
 use payroll_domain::{Paycheck, PaymentClassification};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SalariedClassification {
     salary: f32,
 }
 impl PaymentClassification for SalariedClassification {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -0,0 +1,5 @@
+mod social_insurance;
+mod social_insurance_table;
+
+pub use social_insurance::SocialInsurance;
+pub use social_insurance_table::SocialInsuranceTable;
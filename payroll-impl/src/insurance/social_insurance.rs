@@ -0,0 +1,168 @@
+use serde::{Deserialize, Serialize};
+use std::{any::Any, sync::Arc};
+
+use crate::insurance::SocialInsuranceTable;
+use crate::table::SharedTable;
+use payroll_domain::{Affiliation, DeductionKind, DeductionLine, Paycheck};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SocialInsurance {
+    table: SharedTable<SocialInsuranceTable>,
+    prefecture: String,
+    grade: u32,
+}
+impl SocialInsurance {
+    pub const NAME: &'static str = "Social insurance";
+
+    pub fn new(table: SharedTable<SocialInsuranceTable>, prefecture: &str, grade: u32) -> Self {
+        Self {
+            table,
+            prefecture: prefecture.to_string(),
+            grade,
+        }
+    }
+    pub fn get_table(&self) -> Arc<SocialInsuranceTable> {
+        self.table.get()
+    }
+    pub fn get_grade(&self) -> u32 {
+        self.grade
+    }
+    pub fn set_grade(&mut self, grade: u32) {
+        self.grade = grade;
+    }
+}
+impl Affiliation for SocialInsurance {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::SocialInsurance
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.calculate_deduction_lines(pc)
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let table = self.table.get();
+        let mut premiums = vec![];
+        if pc.get_month_end().is_some() {
+            premiums.push((
+                "Health insurance",
+                table.health_premium(self.grade, &self.prefecture),
+            ));
+            premiums.push(("Welfare pension", table.pension_premium(self.grade)));
+        }
+        premiums.push((
+            "Employment insurance",
+            table.employment_premium(pc.get_gross_pay()),
+        ));
+        premiums
+            .into_iter()
+            .filter(|(_, amount)| *amount != 0.0)
+            .map(|(description, amount)| {
+                DeductionLine::new(DeductionKind::SocialInsurance, description, amount)
+            })
+            .collect()
+    }
+}
+#[cfg(test)]
+mod test_social_insurance {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn date(m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, m, d).unwrap()
+    }
+    fn premiums(
+        insurance: &SocialInsurance,
+        first: NaiveDate,
+        last: NaiveDate,
+        gross_pay: f32,
+    ) -> Vec<(String, f32)> {
+        let mut pc = Paycheck::new(first..=last);
+        pc.set_gross_pay(gross_pay);
+        insurance
+            .calculate_deduction_lines(&pc)
+            .iter()
+            .map(|l| (l.get_description().to_string(), l.get_amount()))
+            .collect()
+    }
+    fn table() -> SharedTable<SocialInsuranceTable> {
+        let rules = r#"Grade 22 300000
+            Pension 88000 650000 18.3%
+            Health "Tokyo" 9.98%
+            Health "Osaka" 10.34%
+            Employment 0.6%"#;
+        SharedTable::insert(
+            "test_social_insurance/insurance.tbl",
+            rules.parse::<SocialInsuranceTable>().unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_by_prefecture() {
+        let tokyo = SocialInsurance::new(table(), "Tokyo", 22);
+        assert_eq!(
+            premiums(&tokyo, date(8, 1), date(8, 31), 310000.0),
+            vec![
+                ("Health insurance".to_string(), 14970.0),
+                ("Welfare pension".to_string(), 27450.0),
+                ("Employment insurance".to_string(), 1860.0),
+            ]
+        );
+        let osaka = SocialInsurance::new(table(), "Osaka", 22);
+        assert_eq!(
+            premiums(&osaka, date(8, 1), date(8, 31), 310000.0),
+            vec![
+                ("Health insurance".to_string(), 15510.0),
+                ("Welfare pension".to_string(), 27450.0),
+                ("Employment insurance".to_string(), 1860.0),
+            ]
+        );
+        assert_eq!(
+            osaka.calculate_deductions(&{
+                let mut pc = Paycheck::new(date(8, 1)..=date(8, 31));
+                pc.set_gross_pay(310000.0);
+                pc
+            }),
+            15510.0 + 27450.0 + 1860.0
+        );
+    }
+    #[test]
+    fn test_monthly_premiums_once_a_month() {
+        let insurance = SocialInsurance::new(table(), "Tokyo", 22);
+        // a week not covering the end of the month
+        assert_eq!(
+            premiums(&insurance, date(8, 3), date(8, 9), 70000.0),
+            vec![("Employment insurance".to_string(), 420.0)]
+        );
+        assert_eq!(
+            premiums(&insurance, date(8, 31), date(9, 6), 70000.0).len(),
+            3
+        );
+    }
+    #[test]
+    fn test_unknown() {
+        // only the employment insurance, which does not depend on either
+        let unknown_grade = SocialInsurance::new(table(), "Tokyo", 99);
+        assert_eq!(
+            premiums(&unknown_grade, date(8, 1), date(8, 31), 310000.0),
+            vec![("Employment insurance".to_string(), 1860.0)]
+        );
+        let unknown_prefecture = SocialInsurance::new(table(), "Hokkaido", 22);
+        assert_eq!(
+            premiums(&unknown_prefecture, date(8, 1), date(8, 31), 310000.0),
+            vec![
+                ("Welfare pension".to_string(), 27450.0),
+                ("Employment insurance".to_string(), 1860.0),
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use crate::table::Table;
+
+// 標準報酬月額 grades and premium rates; the employee pays half of the health
+// insurance and welfare pension premiums and the employment insurance rate as is
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SocialInsuranceTable {
+    // standard monthly remuneration by grade
+    #[serde(with = "crate::record::pairs")]
+    grades: BTreeMap<u32, f32>,
+    // welfare pension uses the remuneration limited to this range
+    pension_floor: f32,
+    pension_ceiling: f32,
+    pension_rate: f32,
+    // health insurance rate by prefecture
+    health_rates: BTreeMap<String, f32>,
+    employment_rate: f32,
+}
+impl SocialInsuranceTable {
+    pub fn get_remuneration(&self, grade: u32) -> Option<f32> {
+        self.grades.get(&grade).copied()
+    }
+    pub fn has_prefecture(&self, prefecture: &str) -> bool {
+        self.health_rates.contains_key(prefecture)
+    }
+    pub fn health_premium(&self, grade: u32, prefecture: &str) -> f32 {
+        let remuneration = self.get_remuneration(grade).unwrap_or(0.0);
+        let rate = self.health_rates.get(prefecture).copied().unwrap_or(0.0);
+        employee_share(remuneration * rate / 2.0)
+    }
+    pub fn pension_premium(&self, grade: u32) -> f32 {
+        // none for a grade not in the table, rather than that of the floor
+        let remuneration = self
+            .get_remuneration(grade)
+            .map_or(0.0, |r| r.clamp(self.pension_floor, self.pension_ceiling));
+        employee_share(remuneration * self.pension_rate / 2.0)
+    }
+    pub fn employment_premium(&self, gross_pay: f32) -> f32 {
+        employee_share(gross_pay * self.employment_rate)
+    }
+}
+
+impl Table for SocialInsuranceTable {
+    fn tables() -> &'static RwLock<BTreeMap<String, Arc<Self>>> {
+        static TABLES: RwLock<BTreeMap<String, Arc<SocialInsuranceTable>>> =
+            RwLock::new(BTreeMap::new());
+        &TABLES
+    }
+}
+
+// fractions up to 50 sen are dropped, those over are rounded up to 1 yen
+fn employee_share(premium: f32) -> f32 {
+    (premium - 0.5).ceil().max(0.0)
+}
+
+// one rule per line, `#` starts a comment:
+//   Grade 22 300000
+//   Pension 88000 650000 18.3%
+//   Health "Tokyo" 9.98%
+//   Employment 0.6%
+impl FromStr for SocialInsuranceTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = SocialInsuranceTable {
+            grades: BTreeMap::new(),
+            pension_floor: 0.0,
+            pension_ceiling: f32::INFINITY,
+            pension_rate: 0.0,
+            health_rates: BTreeMap::new(),
+            employment_rate: 0.0,
+        };
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let amount = |i: usize| -> Result<f32, String> {
+                words
+                    .get(i)
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .ok_or(err("amount expected"))
+            };
+            let rate = |i: usize| -> Result<f32, String> {
+                words
+                    .get(i)
+                    .and_then(|w| w.strip_suffix('%'))
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .map(|r| r / 100.0)
+                    .ok_or(err("rate expected"))
+            };
+            let arity = match words[0] {
+                "Employment" => 2,
+                "Grade" | "Health" => 3,
+                _ => 4,
+            };
+            if words.len() > arity {
+                return Err(err("unexpected words"));
+            }
+            match words[0] {
+                "Grade" => {
+                    let grade = words
+                        .get(1)
+                        .and_then(|w| w.parse::<u32>().ok())
+                        .ok_or(err("grade expected"))?;
+                    table.grades.insert(grade, amount(2)?);
+                }
+                "Pension" => {
+                    table.pension_floor = amount(1)?;
+                    table.pension_ceiling = amount(2)?;
+                    table.pension_rate = rate(3)?;
+                }
+                "Health" => {
+                    let prefecture = words
+                        .get(1)
+                        .and_then(|w| w.strip_prefix('"'))
+                        .and_then(|w| w.strip_suffix('"'))
+                        .ok_or(err("quoted prefecture expected"))?;
+                    table.health_rates.insert(prefecture.to_string(), rate(2)?);
+                }
+                "Employment" => table.employment_rate = rate(1)?,
+                _ => return Err(err("unknown rule")),
+            }
+        }
+        if table.grades.is_empty() {
+            return Err("no grades".to_string());
+        }
+        Ok(table)
+    }
+}
+#[cfg(test)]
+mod test_social_insurance_table {
+    use super::*;
+
+    const RULES: &str = r#"Grade 1 58000
+        Grade 2 300000
+        Grade 3 700000
+        Pension 88000 650000 18.3%
+        Health "Tokyo" 9.98%
+        Health "Osaka" 10.34%
+        Employment 0.6%"#;
+
+    #[test]
+    fn test_premiums() {
+        let table = RULES.parse::<SocialInsuranceTable>().unwrap();
+        // half of the premiums by the rate of the prefecture
+        assert_eq!(table.health_premium(2, "Tokyo"), 14970.0);
+        assert_eq!(table.health_premium(2, "Osaka"), 15510.0);
+        // 58000 * 9.98% / 2 = 2894.2, the fraction dropped
+        assert_eq!(table.health_premium(1, "Tokyo"), 2894.0);
+        // the same wherever the employee lives, within the floor and the ceiling
+        assert_eq!(table.pension_premium(2), 27450.0);
+        assert_eq!(table.pension_premium(1), 8052.0);
+        assert_eq!(table.pension_premium(3), 59475.0);
+        // of the pay actually paid, not of the grade
+        assert_eq!(table.employment_premium(310000.0), 1860.0);
+        assert_eq!(table.employment_premium(0.0), 0.0);
+    }
+    #[test]
+    fn test_unknown() {
+        let table = RULES.parse::<SocialInsuranceTable>().unwrap();
+        assert_eq!(table.get_remuneration(4), None);
+        assert!(!table.has_prefecture("Hokkaido"));
+        assert_eq!(table.health_premium(4, "Tokyo"), 0.0);
+        assert_eq!(table.health_premium(2, "Hokkaido"), 0.0);
+        assert_eq!(table.pension_premium(4), 0.0);
+    }
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "Employment 0.6%".parse::<SocialInsuranceTable>(),
+            Err("no grades".to_string())
+        );
+        assert!("Grade 1 58000\nHealth Tokyo 9.98%"
+            .parse::<SocialInsuranceTable>()
+            .is_err());
+        assert!("Grade 1 58000\nEmployment 0.6"
+            .parse::<SocialInsuranceTable>()
+            .is_err());
+        let data = std::fs::read_to_string("../data/jp_social_insurance.tbl").unwrap();
+        assert!(data.parse::<SocialInsuranceTable>().is_ok());
+    }
+}
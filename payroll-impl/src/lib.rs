@@ -1,4 +1,8 @@
 pub mod affiliation;
 pub mod classification;
+pub mod insurance;
 pub mod method;
+pub mod record;
 pub mod schedule;
+pub mod table;
+pub mod tax;
@@ -1,11 +1,17 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
 use payroll_domain::{Paycheck, PaymentMethod};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct DirectMethod {
     bank: String,
     account: String,
 }
 impl PaymentMethod for DirectMethod {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn pay(&self, pc: &Paycheck) {
         // concrete implementation
         println!("DirectMethod to {}{}: {:#?}", self.bank, self.account, pc);
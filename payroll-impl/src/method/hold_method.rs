@@ -1,8 +1,14 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
 use payroll_domain::{Paycheck, PaymentMethod};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct HoldMethod;
 impl PaymentMethod for HoldMethod {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn pay(&self, pc: &Paycheck) {
         // concrete implementation
         println!("HoldMethod: {:#?}", pc);
@@ -1,10 +1,16 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
 use payroll_domain::{Paycheck, PaymentMethod};
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct MailMethod {
     address: String,
 }
 impl PaymentMethod for MailMethod {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn pay(&self, pc: &Paycheck) {
         // concrete implementation
         println!("MailMethod for {}: {:#?}", self.address, pc);
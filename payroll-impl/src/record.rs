@@ -0,0 +1,12 @@
+mod affiliation_record;
+mod classification_record;
+mod employee_record;
+mod method_record;
+pub(crate) mod pairs;
+mod schedule_record;
+
+pub use affiliation_record::AffiliationRecord;
+pub use classification_record::ClassificationRecord;
+pub use employee_record::EmployeeRecord;
+pub use method_record::MethodRecord;
+pub use schedule_record::ScheduleRecord;
@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::affiliation::{
+    Advances, Garnishments, GeneralDeduction, RetirementContribution, UnionAffiliation,
+};
+use crate::insurance::SocialInsurance;
+use crate::tax::{IncomeTaxWithholding, MonthlyWithholding, ResidentTax, YearEndSettlements};
+use payroll_domain::Affiliation;
+
+// an affiliation tagged by its type, to be stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AffiliationRecord {
+    Union(UnionAffiliation),
+    Deduction(GeneralDeduction),
+    IncomeTax(IncomeTaxWithholding),
+    MonthlyTax(MonthlyWithholding),
+    SocialInsurance(SocialInsurance),
+    ResidentTax(ResidentTax),
+    Retirement(RetirementContribution),
+    Garnishments(Garnishments),
+    Advances(Advances),
+    YearEndSettlements(YearEndSettlements),
+}
+impl AffiliationRecord {
+    pub fn from_affiliation(affiliation: &dyn Affiliation) -> Result<Self, String> {
+        let any = affiliation.as_any();
+        if let Some(a) = any.downcast_ref::<UnionAffiliation>() {
+            return Ok(Self::Union(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<GeneralDeduction>() {
+            return Ok(Self::Deduction(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<IncomeTaxWithholding>() {
+            return Ok(Self::IncomeTax(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<MonthlyWithholding>() {
+            return Ok(Self::MonthlyTax(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<SocialInsurance>() {
+            return Ok(Self::SocialInsurance(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<ResidentTax>() {
+            return Ok(Self::ResidentTax(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<RetirementContribution>() {
+            return Ok(Self::Retirement(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<Garnishments>() {
+            return Ok(Self::Garnishments(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<Advances>() {
+            return Ok(Self::Advances(a.clone()));
+        }
+        if let Some(a) = any.downcast_ref::<YearEndSettlements>() {
+            return Ok(Self::YearEndSettlements(a.clone()));
+        }
+        Err(format!("unknown affiliation: {:?}", affiliation))
+    }
+    pub fn get_type(&self) -> &str {
+        match self {
+            Self::Union(_) => "Union",
+            Self::Deduction(_) => "Deduction",
+            Self::IncomeTax(_) => "IncomeTax",
+            Self::MonthlyTax(_) => "MonthlyTax",
+            Self::SocialInsurance(_) => "SocialInsurance",
+            Self::ResidentTax(_) => "ResidentTax",
+            Self::Retirement(_) => "Retirement",
+            Self::Garnishments(_) => "Garnishments",
+            Self::Advances(_) => "Advances",
+            Self::YearEndSettlements(_) => "YearEndSettlements",
+        }
+    }
+    // as the affiliation is known by the employee
+    pub fn get_name(&self) -> &str {
+        match self {
+            Self::Union(a) => a.get_name(),
+            Self::Deduction(a) => a.get_name(),
+            Self::IncomeTax(a) => a.get_name(),
+            Self::MonthlyTax(a) => a.get_name(),
+            Self::SocialInsurance(a) => a.get_name(),
+            Self::ResidentTax(a) => a.get_name(),
+            Self::Retirement(a) => a.get_name(),
+            Self::Garnishments(a) => a.get_name(),
+            Self::Advances(a) => a.get_name(),
+            Self::YearEndSettlements(a) => a.get_name(),
+        }
+    }
+    pub fn into_affiliation(self) -> Arc<RwLock<dyn Affiliation>> {
+        match self {
+            Self::Union(a) => Arc::new(RwLock::new(a)),
+            Self::Deduction(a) => Arc::new(RwLock::new(a)),
+            Self::IncomeTax(a) => Arc::new(RwLock::new(a)),
+            Self::MonthlyTax(a) => Arc::new(RwLock::new(a)),
+            Self::SocialInsurance(a) => Arc::new(RwLock::new(a)),
+            Self::ResidentTax(a) => Arc::new(RwLock::new(a)),
+            Self::Retirement(a) => Arc::new(RwLock::new(a)),
+            Self::Garnishments(a) => Arc::new(RwLock::new(a)),
+            Self::Advances(a) => Arc::new(RwLock::new(a)),
+            Self::YearEndSettlements(a) => Arc::new(RwLock::new(a)),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::classification::{
+    CommissionedClassification, CompositeClassification, HourlyClassification,
+    SalariedClassification,
+};
+use payroll_domain::PaymentClassification;
+
+// a classification tagged by its type, to be stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ClassificationRecord {
+    Salaried(SalariedClassification),
+    Hourly(HourlyClassification),
+    Commissioned(CommissionedClassification),
+    Composite {
+        components: Vec<ClassificationRecord>,
+    },
+}
+impl ClassificationRecord {
+    pub fn from_classification(classification: &dyn PaymentClassification) -> Result<Self, String> {
+        let any = classification.as_any();
+        if let Some(c) = any.downcast_ref::<SalariedClassification>() {
+            return Ok(Self::Salaried(c.clone()));
+        }
+        if let Some(c) = any.downcast_ref::<HourlyClassification>() {
+            return Ok(Self::Hourly(c.clone()));
+        }
+        if let Some(c) = any.downcast_ref::<CommissionedClassification>() {
+            return Ok(Self::Commissioned(c.clone()));
+        }
+        if let Some(c) = any.downcast_ref::<CompositeClassification>() {
+            let components = c
+                .get_components()
+                .iter()
+                .map(|c| Self::from_classification(c.as_ref()))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(Self::Composite { components });
+        }
+        Err(format!("unknown classification: {:?}", classification))
+    }
+    // the type it would be stored as, without copying the timecards or sales receipts
+    pub fn type_of(classification: &dyn PaymentClassification) -> Option<&'static str> {
+        let any = classification.as_any();
+        if any.is::<SalariedClassification>() {
+            return Some("Salaried");
+        }
+        if any.is::<HourlyClassification>() {
+            return Some("Hourly");
+        }
+        if any.is::<CommissionedClassification>() {
+            return Some("Commissioned");
+        }
+        if any.is::<CompositeClassification>() {
+            return Some("Composite");
+        }
+        None
+    }
+    pub fn get_type(&self) -> &str {
+        match self {
+            Self::Salaried(_) => "Salaried",
+            Self::Hourly(_) => "Hourly",
+            Self::Commissioned(_) => "Commissioned",
+            Self::Composite { .. } => "Composite",
+        }
+    }
+    pub fn into_classification(self) -> Arc<RwLock<dyn PaymentClassification>> {
+        match self {
+            Self::Salaried(c) => Arc::new(RwLock::new(c)),
+            Self::Hourly(c) => Arc::new(RwLock::new(c)),
+            Self::Commissioned(c) => Arc::new(RwLock::new(c)),
+            Self::Composite { components } => Arc::new(RwLock::new(Self::composite(components))),
+        }
+    }
+    fn into_boxed(self) -> Box<dyn PaymentClassification> {
+        match self {
+            Self::Salaried(c) => Box::new(c),
+            Self::Hourly(c) => Box::new(c),
+            Self::Commissioned(c) => Box::new(c),
+            Self::Composite { components } => Box::new(Self::composite(components)),
+        }
+    }
+    fn composite(components: Vec<ClassificationRecord>) -> CompositeClassification {
+        CompositeClassification::new(components.into_iter().map(|c| c.into_boxed()).collect())
+    }
+}
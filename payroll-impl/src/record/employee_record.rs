@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+use crate::record::{AffiliationRecord, ClassificationRecord, MethodRecord, ScheduleRecord};
+use payroll_domain::{DeductionLine, Employee, EmployeeId, MemberId};
+
+// an employee with its trait objects tagged by their types, to be stored
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmployeeRecord {
+    pub emp_id: EmployeeId,
+    pub name: String,
+    pub address: String,
+    pub classification: ClassificationRecord,
+    pub schedule: ScheduleRecord,
+    pub method: MethodRecord,
+    pub affiliations: Vec<AffiliationRecord>,
+    pub net_pay_floor: f32,
+    pub arrears: Vec<DeductionLine>,
+    // absent from records stored before employees were versioned
+    #[serde(default)]
+    pub version: u32,
+}
+impl EmployeeRecord {
+    pub fn from_employee(emp: &Employee) -> Result<Self, String> {
+        let affiliations = emp
+            .get_affiliations()
+            .iter()
+            .map(|a| AffiliationRecord::from_affiliation(&*a.read().unwrap()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            emp_id: emp.get_emp_id(),
+            name: emp.get_name().to_string(),
+            address: emp.get_address().to_string(),
+            classification: ClassificationRecord::from_classification(
+                &*emp.get_classification().read().unwrap(),
+            )?,
+            schedule: ScheduleRecord::from_schedule(&*emp.get_schedule().read().unwrap())?,
+            method: MethodRecord::from_method(&*emp.get_method().read().unwrap())?,
+            affiliations,
+            net_pay_floor: emp.get_net_pay_floor(),
+            arrears: emp.get_arrears().to_vec(),
+            version: emp.get_version(),
+        })
+    }
+    // none for an employee not in the union
+    pub fn member_id(&self) -> Option<MemberId> {
+        self.affiliations.iter().find_map(|a| match a {
+            AffiliationRecord::Union(a) => Some(a.get_member_id()),
+            _ => None,
+        })
+    }
+    pub fn into_employee(self) -> Employee {
+        let mut emp = Employee::new(
+            self.emp_id,
+            &self.name,
+            &self.address,
+            self.classification.into_classification(),
+            self.schedule.into_schedule(),
+            self.method.into_method(),
+            self.affiliations
+                .into_iter()
+                .map(|a| a.into_affiliation())
+                .collect(),
+        );
+        emp.set_net_pay_floor(self.net_pay_floor);
+        emp.set_arrears(self.arrears);
+        emp.set_version(self.version);
+        emp
+    }
+}
@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::method::{DirectMethod, HoldMethod, MailMethod};
+use payroll_domain::PaymentMethod;
+
+// a payment method tagged by its type, to be stored
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum MethodRecord {
+    Hold,
+    Direct(DirectMethod),
+    Mail(MailMethod),
+}
+impl MethodRecord {
+    pub fn from_method(method: &dyn PaymentMethod) -> Result<Self, String> {
+        let any = method.as_any();
+        if any.is::<HoldMethod>() {
+            return Ok(Self::Hold);
+        }
+        if let Some(m) = any.downcast_ref::<DirectMethod>() {
+            return Ok(Self::Direct(m.clone()));
+        }
+        if let Some(m) = any.downcast_ref::<MailMethod>() {
+            return Ok(Self::Mail(m.clone()));
+        }
+        Err(format!("unknown payment method: {:?}", method))
+    }
+    pub fn get_type(&self) -> &str {
+        match self {
+            Self::Hold => "Hold",
+            Self::Direct(_) => "Direct",
+            Self::Mail(_) => "Mail",
+        }
+    }
+    pub fn into_method(self) -> Arc<RwLock<dyn PaymentMethod>> {
+        match self {
+            Self::Hold => Arc::new(RwLock::new(HoldMethod)),
+            Self::Direct(m) => Arc::new(RwLock::new(m)),
+            Self::Mail(m) => Arc::new(RwLock::new(m)),
+        }
+    }
+}
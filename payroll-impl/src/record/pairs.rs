@@ -0,0 +1,35 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+// a map with keys other than strings, stored as a list of key-value pairs; within a
+// record tagged by its type, the keys of a map could only be read back as strings
+pub fn serialize<K, V, S>(map: &BTreeMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(map.iter())
+}
+// a map stored before, with the keys as strings, is read as well
+pub fn deserialize<'de, K, V, D>(deserializer: D) -> Result<BTreeMap<K, V>, D::Error>
+where
+    K: Deserialize<'de> + FromStr + Ord,
+    K::Err: Display,
+    V: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored<K, V> {
+        Pairs(Vec<(K, V)>),
+        Map(BTreeMap<String, V>),
+    }
+    match Stored::<K, V>::deserialize(deserializer)? {
+        Stored::Pairs(pairs) => Ok(pairs.into_iter().collect()),
+        Stored::Map(map) => map
+            .into_iter()
+            .map(|(k, v)| k.parse::<K>().map(|k| (k, v)).map_err(D::Error::custom))
+            .collect(),
+    }
+}
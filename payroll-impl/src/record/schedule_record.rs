@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+use crate::schedule::{BiweeklySchedule, MonthlySchedule, WeeklySchedule};
+use payroll_domain::PaymentSchedule;
+
+// a schedule tagged by its type, to be stored
+#[derive(Debug, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum ScheduleRecord {
+    Monthly,
+    Weekly,
+    Biweekly,
+}
+impl ScheduleRecord {
+    pub fn from_schedule(schedule: &dyn PaymentSchedule) -> Result<Self, String> {
+        let any = schedule.as_any();
+        if any.is::<MonthlySchedule>() {
+            return Ok(Self::Monthly);
+        }
+        if any.is::<WeeklySchedule>() {
+            return Ok(Self::Weekly);
+        }
+        if any.is::<BiweeklySchedule>() {
+            return Ok(Self::Biweekly);
+        }
+        Err(format!("unknown schedule: {:?}", schedule))
+    }
+    pub fn get_type(&self) -> &str {
+        match self {
+            Self::Monthly => "Monthly",
+            Self::Weekly => "Weekly",
+            Self::Biweekly => "Biweekly",
+        }
+    }
+    pub fn into_schedule(self) -> Arc<RwLock<dyn PaymentSchedule>> {
+        match self {
+            Self::Monthly => Arc::new(RwLock::new(MonthlySchedule)),
+            Self::Weekly => Arc::new(RwLock::new(WeeklySchedule)),
+            Self::Biweekly => Arc::new(RwLock::new(BiweeklySchedule)),
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use chrono::{Datelike, Days, NaiveDate, Weekday};
-use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, ops::RangeInclusive};
 
 use payroll_domain::PaymentSchedule;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct BiweeklySchedule;
 impl PaymentSchedule for BiweeklySchedule {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn is_pay_date(&self, date: NaiveDate) -> bool {
         date.weekday() == Weekday::Fri && date.iso_week().week() % 2 == 0
     }
@@ -1,11 +1,15 @@
 use chrono::{Datelike, Days, NaiveDate};
-use std::ops::RangeInclusive;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, ops::RangeInclusive};
 
 use payroll_domain::PaymentSchedule;
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct MonthlySchedule;
 impl PaymentSchedule for MonthlySchedule {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
     fn is_pay_date(&self, date: NaiveDate) -> bool {
         self.is_last_day_of_month(date)
     }
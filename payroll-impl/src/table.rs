@@ -0,0 +1,105 @@
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    collections::BTreeMap,
+    marker::PhantomData,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+// a table of rates read from a file, kept once for all the employees referring to it
+pub trait Table: FromStr<Err = String> + Send + Sync + 'static {
+    // the tables of the kind read so far, by the path they are read from
+    fn tables() -> &'static RwLock<BTreeMap<String, Arc<Self>>>;
+}
+
+// an employee's reference to a table, stored as its path and looked up when the
+// deductions are calculated; a table read again is replaced for everyone referring
+// to it, without any of them to be rewritten
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedTable<T> {
+    path: String,
+    table: PhantomData<fn() -> T>,
+}
+impl<T: Table> SharedTable<T> {
+    // reads the table anew, even if it has been read before
+    pub fn read(path: &str) -> Result<Self, String> {
+        let table = std::fs::read_to_string(path)
+            .map_err(|e| e.to_string())?
+            .parse::<T>()?;
+        Ok(Self::insert(path, table))
+    }
+    // the table read before, read now if it has not been
+    pub fn open(path: &str) -> Result<Self, String> {
+        if T::tables().read().unwrap().contains_key(path) {
+            return Ok(Self::refer(path));
+        }
+        Self::read(path)
+    }
+    // a table given as is, in place of the one at `path`
+    pub fn insert(path: &str, table: T) -> Self {
+        T::tables()
+            .write()
+            .unwrap()
+            .insert(path.to_string(), Arc::new(table));
+        Self::refer(path)
+    }
+    fn refer(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            table: PhantomData,
+        }
+    }
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+    pub fn get(&self) -> Arc<T> {
+        // a reference is made only to a table read already, and tables are never removed
+        T::tables()
+            .read()
+            .unwrap()
+            .get(&self.path)
+            .cloned()
+            .expect("shared table not read")
+    }
+}
+impl<T> Serialize for SharedTable<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.path)
+    }
+}
+// the table of a stored employee is read unless it has been already
+impl<'de, T: Table> Deserialize<'de> for SharedTable<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let path = String::deserialize(deserializer)?;
+        Self::open(&path).map_err(|e| D::Error::custom(format!("{}: {}", path, e)))
+    }
+}
+#[cfg(test)]
+mod test_table {
+    use super::*;
+    use crate::tax::TaxTable;
+
+    const RULES: &str = "Bracket 0.0 10%\nAnnualize 7 52";
+
+    #[test]
+    fn test_shared() {
+        let path = "test_table/test_shared.tbl";
+        let before = SharedTable::insert(path, RULES.parse::<TaxTable>().unwrap());
+        assert_eq!(before.get().annual_tax(1000.0, 0), 100.0);
+        // opened rather than read again, as it has been read
+        let opened = SharedTable::<TaxTable>::open(path).unwrap();
+        assert_eq!(opened, before);
+        // replaced for those referring to it already
+        SharedTable::insert(
+            path,
+            RULES.replace("10%", "20%").parse::<TaxTable>().unwrap(),
+        );
+        assert_eq!(before.get().annual_tax(1000.0, 0), 200.0);
+        assert_eq!(opened.get().annual_tax(1000.0, 0), 200.0);
+    }
+    #[test]
+    fn test_unreadable() {
+        assert!(SharedTable::<TaxTable>::open("test_table/not_found.tbl").is_err());
+        assert!(SharedTable::<TaxTable>::read("../data/jp_social_insurance.tbl").is_err());
+    }
+}
@@ -0,0 +1,16 @@
+mod income_tax_withholding;
+mod monthly_withholding;
+mod monthly_withholding_table;
+mod resident_tax;
+mod tax_table;
+mod year_end_adjustment;
+
+pub use income_tax_withholding::IncomeTaxWithholding;
+pub use monthly_withholding::MonthlyWithholding;
+pub use monthly_withholding_table::MonthlyWithholdingTable;
+pub use resident_tax::{ResidentTax, ResidentTaxNotice, ResidentTaxNotices, ResidentTaxSchedule};
+pub use tax_table::{TaxBracket, TaxTable};
+pub use year_end_adjustment::{
+    YearEndAdjustment, YearEndBasis, YearEndDeclaration, YearEndDeclarations, YearEndSettlement,
+    YearEndSettlements, YearEndTable, YearToDate,
+};
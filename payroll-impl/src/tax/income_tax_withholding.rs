@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+use crate::table::SharedTable;
+use crate::tax::TaxTable;
+use payroll_domain::{Affiliation, DeductionKind, Paycheck};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeTaxWithholding {
+    table: SharedTable<TaxTable>,
+    allowances: u32,
+}
+impl IncomeTaxWithholding {
+    pub const NAME: &'static str = "Income tax";
+
+    pub fn new(table: SharedTable<TaxTable>, allowances: u32) -> Self {
+        Self { table, allowances }
+    }
+    pub fn get_allowances(&self) -> u32 {
+        self.allowances
+    }
+}
+impl Affiliation for IncomeTaxWithholding {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::IncomeTax
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        let period = pc.get_pay_period();
+        let days = (*period.end() - *period.start()).num_days() + 1;
+        self.table
+            .get()
+            .withholding(pc.get_taxable_pay(), days, self.allowances)
+    }
+}
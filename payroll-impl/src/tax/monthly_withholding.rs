@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+
+use crate::schedule::MonthlySchedule;
+use crate::table::SharedTable;
+use crate::tax::{IncomeTaxWithholding, MonthlyWithholdingTable};
+use payroll_domain::{Affiliation, DeductionKind, Paycheck, PaymentSchedule, WithholdingColumn};
+
+// withholding by the monthly table on the pay after social insurance, for those paid
+// monthly only, as the table has no columns for the pay of other periods
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyWithholding {
+    table: SharedTable<MonthlyWithholdingTable>,
+    column: WithholdingColumn,
+    dependents: u32,
+}
+impl MonthlyWithholding {
+    pub fn new(
+        table: SharedTable<MonthlyWithholdingTable>,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> Self {
+        Self {
+            table,
+            column,
+            dependents,
+        }
+    }
+    pub fn is_applicable(schedule: &dyn PaymentSchedule) -> bool {
+        schedule.as_any().is::<MonthlySchedule>()
+    }
+    pub fn get_column(&self) -> WithholdingColumn {
+        self.column
+    }
+    pub fn set_column(&mut self, column: WithholdingColumn) {
+        self.column = column;
+    }
+    pub fn get_dependents(&self) -> u32 {
+        self.dependents
+    }
+    pub fn set_dependents(&mut self, dependents: u32) {
+        self.dependents = dependents;
+    }
+}
+impl Affiliation for MonthlyWithholding {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    // an employee is withheld income tax by one rule only
+    fn get_name(&self) -> &str {
+        IncomeTaxWithholding::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::IncomeTax
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.table
+            .get()
+            .withholding(pc.get_taxable_pay(), self.column, self.dependents)
+    }
+}
@@ -0,0 +1,165 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use crate::table::Table;
+use payroll_domain::WithholdingColumn;
+
+// number of dependents the 甲 column of the table is tabulated for
+const TABULATED_DEPENDENTS: usize = 7;
+// subtracted from the tax per dependent beyond the tabulated ones,
+// and per dependent from the 乙 column
+const PER_DEPENDENT_REDUCTION: f32 = 1610.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Row {
+    from: f32,
+    // exclusive, none for the last open-ended row
+    to: Option<f32>,
+    kou: [f32; TABULATED_DEPENDENTS + 1],
+    otsu: f32,
+    // applied to the pay exceeding `from`
+    kou_rate: f32,
+    otsu_rate: f32,
+}
+
+// 給与所得の源泉徴収税額表 (月額表), looked up by the taxable pay of the month
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MonthlyWithholdingTable {
+    // sorted by from, each starting where the one before ends, from 0 on
+    rows: Vec<Row>,
+}
+impl MonthlyWithholdingTable {
+    pub fn withholding(&self, taxable_pay: f32, column: WithholdingColumn, dependents: u32) -> f32 {
+        // the rows leave no gap, so only a negative pay falls before the first one
+        let Some(row) = self
+            .rows
+            .partition_point(|r| r.from <= taxable_pay)
+            .checked_sub(1)
+            .map(|i| &self.rows[i])
+        else {
+            return 0.0;
+        };
+        let excess = taxable_pay - row.from;
+        let dependents = dependents as usize;
+        let tax = match column {
+            WithholdingColumn::Kou => {
+                let tabulated = dependents.min(TABULATED_DEPENDENTS);
+                let beyond = dependents - tabulated;
+                row.kou[tabulated] + (excess * row.kou_rate).floor()
+                    - beyond as f32 * PER_DEPENDENT_REDUCTION
+            }
+            WithholdingColumn::Otsu => {
+                row.otsu + (excess * row.otsu_rate).floor()
+                    - dependents as f32 * PER_DEPENDENT_REDUCTION
+            }
+        };
+        tax.max(0.0)
+    }
+}
+
+// an importable copy of the official table, one row per line, `#` starts a comment:
+//   from,to,kou0,kou1,...,kou7,otsu[,kou_rate%,otsu_rate%]
+// `to` is left empty on the last row
+impl Table for MonthlyWithholdingTable {
+    fn tables() -> &'static RwLock<BTreeMap<String, Arc<Self>>> {
+        static TABLES: RwLock<BTreeMap<String, Arc<MonthlyWithholdingTable>>> =
+            RwLock::new(BTreeMap::new());
+        &TABLES
+    }
+}
+impl FromStr for MonthlyWithholdingTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rows = vec![];
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let fields = line.split(',').map(|f| f.trim()).collect::<Vec<_>>();
+            if fields.len() != 11 && fields.len() != 13 {
+                return Err(err("11 or 13 fields expected"));
+            }
+            let amount = |f: &str| f.parse::<f32>().map_err(|_| err("amount expected"));
+            let rate = |f: Option<&&str>| -> Result<f32, String> {
+                f.map_or(Ok(0.0), |f| {
+                    f.strip_suffix('%')
+                        .and_then(|f| f.parse::<f32>().ok())
+                        .map(|r| r / 100.0)
+                        .ok_or(err("rate expected"))
+                })
+            };
+            let mut kou = [0.0; TABULATED_DEPENDENTS + 1];
+            for (i, k) in kou.iter_mut().enumerate() {
+                *k = amount(fields[2 + i])?;
+            }
+            rows.push(Row {
+                from: amount(fields[0])?,
+                to: match fields[1] {
+                    "" => None,
+                    to => Some(amount(to)?),
+                },
+                kou,
+                otsu: amount(fields[10])?,
+                kou_rate: rate(fields.get(11))?,
+                otsu_rate: rate(fields.get(12))?,
+            });
+        }
+        if rows.is_empty() {
+            return Err("no rows".to_string());
+        }
+        rows.sort_by(|a, b| a.from.total_cmp(&b.from));
+        // a pay falling in a gap would be withheld nothing
+        if rows[0].from != 0.0 {
+            return Err("the first row must start at 0".to_string());
+        }
+        for pair in rows.windows(2) {
+            if pair[0].to != Some(pair[1].from) {
+                return Err(format!("rows not contiguous at {}", pair[1].from));
+            }
+        }
+        if rows[rows.len() - 1].to.is_some() {
+            return Err("the last row must be open-ended".to_string());
+        }
+        Ok(Self { rows })
+    }
+}
+
+#[cfg(test)]
+mod test_monthly_withholding_table {
+    use super::*;
+
+    const ROWS: &str = "0,88000,0,0,0,0,0,0,0,0,0,0%,3.063%
+        88000,89000,130,0,0,0,0,0,0,0,3200
+        89000,,180,0,0,0,0,0,0,0,3200,10%,20%";
+
+    #[test]
+    fn test_withholding() {
+        let table = ROWS.parse::<MonthlyWithholdingTable>().unwrap();
+        assert_eq!(table.withholding(50000.0, WithholdingColumn::Kou, 0), 0.0);
+        assert_eq!(
+            table.withholding(50000.0, WithholdingColumn::Otsu, 0),
+            1531.0
+        );
+        assert_eq!(table.withholding(88500.0, WithholdingColumn::Kou, 0), 130.0);
+        assert_eq!(table.withholding(90000.0, WithholdingColumn::Kou, 0), 280.0);
+        assert_eq!(table.withholding(-1.0, WithholdingColumn::Kou, 0), 0.0);
+    }
+    #[test]
+    fn test_gaps() {
+        let gap = ROWS.replace("88000,89000", "88000,88500");
+        assert!(gap.parse::<MonthlyWithholdingTable>().is_err());
+        let late_start = ROWS.replace("0,88000,0", "1000,88000,0");
+        assert!(late_start.parse::<MonthlyWithholdingTable>().is_err());
+        let closed = ROWS.replace("89000,,180", "89000,90000,180");
+        assert!(closed.parse::<MonthlyWithholdingTable>().is_err());
+        let data = std::fs::read_to_string("../data/jp_monthly_withholding.csv").unwrap();
+        assert!(data.parse::<MonthlyWithholdingTable>().is_ok());
+    }
+}
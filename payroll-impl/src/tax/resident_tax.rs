@@ -0,0 +1,245 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+use std::{any::Any, collections::BTreeMap, str::FromStr};
+
+use payroll_domain::{Affiliation, DeductionKind, EmployeeId, Paycheck};
+
+// monthly amounts of a fiscal year running from June through May
+pub type ResidentTaxSchedule = [f32; 12];
+
+// 住民税特別徴収: amounts notified by the municipality, deducted monthly
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResidentTax {
+    // by the year in which the fiscal year starts
+    #[serde(with = "crate::record::pairs")]
+    schedules: BTreeMap<i32, ResidentTaxSchedule>,
+}
+impl ResidentTax {
+    pub const NAME: &'static str = "Resident tax";
+
+    pub fn new() -> Self {
+        Self {
+            schedules: BTreeMap::new(),
+        }
+    }
+    pub fn get_schedule(&self, fiscal_year: i32) -> Option<ResidentTaxSchedule> {
+        self.schedules.get(&fiscal_year).copied()
+    }
+    // a later notice for the same fiscal year replaces the earlier one
+    pub fn set_schedule(&mut self, fiscal_year: i32, amounts: ResidentTaxSchedule) {
+        self.schedules.insert(fiscal_year, amounts);
+    }
+}
+impl Default for ResidentTax {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Affiliation for ResidentTax {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::ResidentTax
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        // collected only on the paycheck covering the end of a month
+        let Some(month_end) = pc.get_month_end() else {
+            return 0.0;
+        };
+        let (fiscal_year, index) = if month_end.month() >= 6 {
+            (month_end.year(), month_end.month0() - 5)
+        } else {
+            (month_end.year() - 1, month_end.month0() + 7)
+        };
+        // nothing before the notice of the fiscal year has been imported
+        self.schedules
+            .get(&fiscal_year)
+            .map_or(0.0, |amounts| amounts[index as usize])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResidentTaxNotice {
+    emp_id: EmployeeId,
+    fiscal_year: i32,
+    amounts: ResidentTaxSchedule,
+}
+impl ResidentTaxNotice {
+    pub fn new(emp_id: EmployeeId, fiscal_year: i32, amounts: ResidentTaxSchedule) -> Self {
+        Self {
+            emp_id,
+            fiscal_year,
+            amounts,
+        }
+    }
+    pub fn get_emp_id(&self) -> EmployeeId {
+        self.emp_id
+    }
+    pub fn get_fiscal_year(&self) -> i32 {
+        self.fiscal_year
+    }
+    pub fn get_amounts(&self) -> ResidentTaxSchedule {
+        self.amounts
+    }
+}
+
+// municipal notices, one employee per line, `#` starts a comment:
+//   emp_id,fiscal_year,june,july,...,may
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResidentTaxNotices(pub Vec<ResidentTaxNotice>);
+impl FromStr for ResidentTaxNotices {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut notices = vec![];
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let fields = line.split(',').map(|f| f.trim()).collect::<Vec<_>>();
+            if fields.len() != 14 {
+                return Err(err("14 fields expected"));
+            }
+            let emp_id = fields[0]
+                .parse::<EmployeeId>()
+                .map_err(|_| err("emp_id expected"))?;
+            let fiscal_year = fields[1]
+                .parse::<i32>()
+                .map_err(|_| err("fiscal year expected"))?;
+            let mut amounts = [0.0; 12];
+            for (i, a) in amounts.iter_mut().enumerate() {
+                *a = fields[2 + i]
+                    .parse::<f32>()
+                    .map_err(|_| err("amount expected"))?;
+            }
+            notices.push(ResidentTaxNotice::new(emp_id, fiscal_year, amounts));
+        }
+        Ok(Self(notices))
+    }
+}
+#[cfg(test)]
+mod test_resident_tax {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn deduction(resident_tax: &ResidentTax, first: (i32, u32, u32), last: (i32, u32, u32)) -> f32 {
+        let date = |(y, m, d)| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        resident_tax.calculate_deductions(&Paycheck::new(date(first)..=date(last)))
+    }
+    // June through May, the first month different from the rest
+    fn schedule(first: f32, rest: f32) -> ResidentTaxSchedule {
+        let mut amounts = [rest; 12];
+        amounts[0] = first;
+        amounts
+    }
+
+    #[test]
+    fn test_fiscal_year() {
+        let mut resident_tax = ResidentTax::new();
+        resident_tax.set_schedule(2024, schedule(18500.0, 18000.0));
+        resident_tax.set_schedule(2025, schedule(21000.0, 20000.0));
+        assert_eq!(
+            deduction(&resident_tax, (2024, 6, 1), (2024, 6, 30)),
+            18500.0
+        );
+        assert_eq!(
+            deduction(&resident_tax, (2024, 7, 1), (2024, 7, 31)),
+            18000.0
+        );
+        assert_eq!(
+            deduction(&resident_tax, (2024, 12, 1), (2024, 12, 31)),
+            18000.0
+        );
+        // January through May of the next calendar year
+        assert_eq!(
+            deduction(&resident_tax, (2025, 1, 1), (2025, 1, 31)),
+            18000.0
+        );
+        assert_eq!(
+            deduction(&resident_tax, (2025, 5, 1), (2025, 5, 31)),
+            18000.0
+        );
+        // the next fiscal year from June on
+        assert_eq!(
+            deduction(&resident_tax, (2025, 6, 1), (2025, 6, 30)),
+            21000.0
+        );
+        assert_eq!(
+            deduction(&resident_tax, (2025, 7, 1), (2025, 7, 31)),
+            20000.0
+        );
+    }
+    #[test]
+    fn test_by_month_end() {
+        let mut resident_tax = ResidentTax::new();
+        resident_tax.set_schedule(2024, schedule(18500.0, 18000.0));
+        // a week covering no month end
+        assert_eq!(deduction(&resident_tax, (2024, 8, 3), (2024, 8, 9)), 0.0);
+        // the month of the month end covered, not of the pay date
+        assert_eq!(deduction(&resident_tax, (2024, 5, 27), (2024, 6, 2)), 0.0);
+        assert_eq!(
+            deduction(&resident_tax, (2024, 6, 24), (2024, 7, 5)),
+            18500.0
+        );
+    }
+    #[test]
+    fn test_outside_fiscal_year() {
+        let mut resident_tax = ResidentTax::new();
+        resident_tax.set_schedule(2024, schedule(18500.0, 18000.0));
+        // May of the fiscal year before, and June after the last one notified
+        assert_eq!(deduction(&resident_tax, (2024, 5, 1), (2024, 5, 31)), 0.0);
+        assert_eq!(deduction(&resident_tax, (2025, 6, 1), (2025, 6, 30)), 0.0);
+        assert_eq!(
+            deduction(&ResidentTax::new(), (2024, 6, 1), (2024, 6, 30)),
+            0.0
+        );
+    }
+    #[test]
+    fn test_notice_replaced() {
+        let mut resident_tax = ResidentTax::new();
+        resident_tax.set_schedule(2024, schedule(18500.0, 18000.0));
+        resident_tax.set_schedule(2025, schedule(21000.0, 20000.0));
+        resident_tax.set_schedule(2024, schedule(9500.0, 9000.0));
+        assert_eq!(
+            resident_tax.get_schedule(2024),
+            Some(schedule(9500.0, 9000.0))
+        );
+        assert_eq!(
+            deduction(&resident_tax, (2024, 6, 1), (2024, 6, 30)),
+            9500.0
+        );
+        // the other fiscal year kept
+        assert_eq!(
+            deduction(&resident_tax, (2025, 6, 1), (2025, 6, 30)),
+            21000.0
+        );
+    }
+    #[test]
+    fn test_notices() {
+        let notices = "# emp_id,fiscal_year,june..may
+            42,2024,18500,18000,18000,18000,18000,18000,18000,18000,18000,18000,18000,18000"
+            .parse::<ResidentTaxNotices>()
+            .unwrap();
+        assert_eq!(
+            notices,
+            ResidentTaxNotices(vec![ResidentTaxNotice::new(
+                42,
+                2024,
+                schedule(18500.0, 18000.0)
+            )])
+        );
+        assert!("42,2024,18500".parse::<ResidentTaxNotices>().is_err());
+        assert!("42,FY2024,1,1,1,1,1,1,1,1,1,1,1,1"
+            .parse::<ResidentTaxNotices>()
+            .is_err());
+    }
+}
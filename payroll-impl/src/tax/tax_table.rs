@@ -0,0 +1,228 @@
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use crate::table::Table;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxBracket {
+    pub(crate) threshold: f32,
+    pub(crate) rate: f32,
+}
+impl TaxBracket {
+    pub fn new(threshold: f32, rate: f32) -> Self {
+        Self { threshold, rate }
+    }
+}
+
+// each bracket taxes the part of the income between its threshold and the next one;
+// `brackets` must be sorted by threshold
+pub(crate) fn progressive_tax(brackets: &[TaxBracket], taxable: f32) -> f32 {
+    let uppers = brackets
+        .iter()
+        .skip(1)
+        .map(|b| b.threshold)
+        .chain([f32::INFINITY]);
+    brackets
+        .iter()
+        .zip(uppers)
+        .filter(|(b, _)| taxable > b.threshold)
+        .fold(0.0, |acc, (b, upper)| {
+            acc + (taxable.min(upper) - b.threshold) * b.rate
+        })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+struct Annualization {
+    max_days: i64,
+    periods: u32,
+}
+
+// annual withholding rules; the pay of a period is annualized by the number of
+// periods of its length in a year
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaxTable {
+    standard_deduction: f32,
+    allowance: f32,
+    // sorted by threshold
+    brackets: Vec<TaxBracket>,
+    // sorted by max_days
+    annualizations: Vec<Annualization>,
+}
+impl TaxTable {
+    pub fn periods_per_year(&self, days: i64) -> u32 {
+        self.annualizations
+            .iter()
+            .find(|a| days <= a.max_days)
+            .or(self.annualizations.last())
+            .map_or(1, |a| a.periods)
+    }
+    pub fn annual_tax(&self, annual_pay: f32, allowances: u32) -> f32 {
+        let taxable =
+            (annual_pay - self.standard_deduction - allowances as f32 * self.allowance).max(0.0);
+        progressive_tax(&self.brackets, taxable)
+    }
+    // withholding for a period of `days` days paying `pay`, rounded to cents
+    pub fn withholding(&self, pay: f32, days: i64, allowances: u32) -> f32 {
+        let periods = self.periods_per_year(days) as f32;
+        let tax = self.annual_tax(pay * periods, allowances) / periods;
+        (tax * 100.0).round() / 100.0
+    }
+}
+
+impl Table for TaxTable {
+    fn tables() -> &'static RwLock<BTreeMap<String, Arc<Self>>> {
+        static TABLES: RwLock<BTreeMap<String, Arc<TaxTable>>> = RwLock::new(BTreeMap::new());
+        &TABLES
+    }
+}
+
+// a table is written one rule per line, `#` starts a comment:
+//   StandardDeduction 13850.0
+//   Allowance 4300.0
+//   Bracket 0.0 10%
+//   Annualize 7 52
+impl FromStr for TaxTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = TaxTable {
+            standard_deduction: 0.0,
+            allowance: 0.0,
+            brackets: vec![],
+            annualizations: vec![],
+        };
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let amount = |i: usize| -> Result<f32, String> {
+                words
+                    .get(i)
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .ok_or(err("amount expected"))
+            };
+            let arity = match words[0] {
+                "StandardDeduction" | "Allowance" => 2,
+                _ => 3,
+            };
+            if words.len() > arity {
+                return Err(err("unexpected words"));
+            }
+            match words[0] {
+                "StandardDeduction" => table.standard_deduction = amount(1)?,
+                "Allowance" => table.allowance = amount(1)?,
+                "Bracket" => {
+                    let rate = words
+                        .get(2)
+                        .and_then(|w| w.strip_suffix('%'))
+                        .and_then(|w| w.parse::<f32>().ok())
+                        .ok_or(err("rate expected"))?;
+                    table
+                        .brackets
+                        .push(TaxBracket::new(amount(1)?, rate / 100.0));
+                }
+                "Annualize" => {
+                    let max_days = words
+                        .get(1)
+                        .and_then(|w| w.parse::<i64>().ok())
+                        .ok_or(err("days expected"))?;
+                    let periods = words
+                        .get(2)
+                        .and_then(|w| w.parse::<u32>().ok())
+                        .filter(|p| *p > 0)
+                        .ok_or(err("periods expected"))?;
+                    table
+                        .annualizations
+                        .push(Annualization { max_days, periods });
+                }
+                _ => return Err(err("unknown rule")),
+            }
+        }
+        if table.brackets.is_empty() {
+            return Err("no brackets".to_string());
+        }
+        if table.annualizations.is_empty() {
+            return Err("no annualization".to_string());
+        }
+        table
+            .brackets
+            .sort_by(|a, b| a.threshold.total_cmp(&b.threshold));
+        table.annualizations.sort_by_key(|a| a.max_days);
+        Ok(table)
+    }
+}
+#[cfg(test)]
+mod test_tax_table {
+    use super::*;
+
+    const RULES: &str = "StandardDeduction 1000.0
+        Allowance 500.0
+        Bracket 10000.0 20%  # given out of order
+        Bracket 0.0 10%
+        Annualize 7 52
+        Annualize 31 12
+        Annualize 14 26";
+
+    #[test]
+    fn test_annual_tax() {
+        let table = RULES.parse::<TaxTable>().unwrap();
+        // 10% of the first 10000 and 20% of the rest
+        assert_eq!(table.annual_tax(21000.0, 0), 3000.0);
+        assert_eq!(table.annual_tax(11000.0, 0), 1000.0);
+        // each allowance is deducted like the standard deduction
+        assert_eq!(table.annual_tax(21000.0, 2), 2800.0);
+        assert_eq!(table.annual_tax(500.0, 0), 0.0);
+        assert_eq!(table.annual_tax(21000.0, 100), 0.0);
+    }
+    #[test]
+    fn test_periods_per_year() {
+        let table = RULES.parse::<TaxTable>().unwrap();
+        assert_eq!(table.periods_per_year(1), 52);
+        assert_eq!(table.periods_per_year(7), 52);
+        assert_eq!(table.periods_per_year(8), 26);
+        assert_eq!(table.periods_per_year(14), 26);
+        assert_eq!(table.periods_per_year(15), 12);
+        assert_eq!(table.periods_per_year(31), 12);
+        // longer than any, as the longest
+        assert_eq!(table.periods_per_year(365), 12);
+    }
+    #[test]
+    fn test_withholding() {
+        let table = RULES.parse::<TaxTable>().unwrap();
+        // the same annual pay is withheld the same annual tax, whatever the period
+        assert_eq!(table.withholding(21000.0 / 52.0, 7, 0), 57.69);
+        assert_eq!(table.withholding(21000.0 / 26.0, 14, 0), 115.38);
+        assert_eq!(table.withholding(1750.0, 31, 0), 250.0);
+        assert_eq!(table.withholding(1750.0, 30, 2), 233.33);
+        assert_eq!(table.withholding(0.0, 7, 0), 0.0);
+    }
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            "Allowance 500.0\nAnnualize 7 52".parse::<TaxTable>(),
+            Err("no brackets".to_string())
+        );
+        assert_eq!(
+            "Bracket 0.0 10%".parse::<TaxTable>(),
+            Err("no annualization".to_string())
+        );
+        assert!("Bracket 0.0 10\nAnnualize 7 52"
+            .parse::<TaxTable>()
+            .is_err());
+        assert!("Bracket 0.0 10%\nAnnualize 7 0"
+            .parse::<TaxTable>()
+            .is_err());
+        assert!("Brackets 0.0 10%\nAnnualize 7 52"
+            .parse::<TaxTable>()
+            .is_err());
+        let data = std::fs::read_to_string("../data/income_tax.tbl").unwrap();
+        assert!(data.parse::<TaxTable>().is_ok());
+    }
+}
@@ -0,0 +1,690 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::{
+    any::Any,
+    collections::BTreeMap,
+    str::FromStr,
+    sync::{Arc, RwLock},
+};
+
+use crate::table::{SharedTable, Table};
+use crate::tax::{tax_table::progressive_tax, TaxBracket};
+use payroll_domain::{Affiliation, DeductionKind, DeductionLine, EmployeeId, Paycheck};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct EmploymentDeduction {
+    // inclusive, none for the last open-ended rule
+    limit: Option<f32>,
+    rate: f32,
+    amount: f32,
+}
+
+// annual income tax of employment income: 給与所得控除, 基礎控除, 扶養控除, the
+// progressive rates and 復興特別所得税 on top of them
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearEndTable {
+    // sorted by limit
+    employment_deductions: Vec<EmploymentDeduction>,
+    basic_deduction: f32,
+    dependent_deduction: f32,
+    // sorted by threshold
+    brackets: Vec<TaxBracket>,
+    surtax_rate: f32,
+}
+impl YearEndTable {
+    pub fn employment_income_deduction(&self, annual_pay: f32) -> f32 {
+        self.employment_deductions
+            .iter()
+            .find(|d| d.limit.is_none_or(|limit| annual_pay <= limit))
+            .map_or(0.0, |d| annual_pay * d.rate + d.amount)
+            .min(annual_pay)
+    }
+    // taxable income is rounded down to 1,000 yen and the tax to 100 yen
+    pub fn annual_tax(&self, taxable_income: f32) -> f32 {
+        let taxable_income = (taxable_income / 1000.0).floor() * 1000.0;
+        let tax = progressive_tax(&self.brackets, taxable_income) * (1.0 + self.surtax_rate);
+        (tax / 100.0).floor() * 100.0
+    }
+}
+
+impl Table for YearEndTable {
+    fn tables() -> &'static RwLock<BTreeMap<String, Arc<Self>>> {
+        static TABLES: RwLock<BTreeMap<String, Arc<YearEndTable>>> = RwLock::new(BTreeMap::new());
+        &TABLES
+    }
+}
+
+// one rule per line, `#` starts a comment:
+//   EmploymentDeduction 1800000 40% -100000   (pay up to 1800000: 40% of it less 100000)
+//   EmploymentDeduction - 0% 1950000          (any pay above)
+//   BasicDeduction 480000
+//   DependentDeduction 380000
+//   Bracket 1950000 10%
+//   Surtax 2.1%
+impl FromStr for YearEndTable {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut table = YearEndTable {
+            employment_deductions: vec![],
+            basic_deduction: 0.0,
+            dependent_deduction: 0.0,
+            brackets: vec![],
+            surtax_rate: 0.0,
+        };
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            let amount = |i: usize| -> Result<f32, String> {
+                words
+                    .get(i)
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .ok_or(err("amount expected"))
+            };
+            let rate = |i: usize| -> Result<f32, String> {
+                words
+                    .get(i)
+                    .and_then(|w| w.strip_suffix('%'))
+                    .and_then(|w| w.parse::<f32>().ok())
+                    .map(|r| r / 100.0)
+                    .ok_or(err("rate expected"))
+            };
+            let arity = match words[0] {
+                "EmploymentDeduction" => 4,
+                "Bracket" => 3,
+                _ => 2,
+            };
+            if words.len() > arity {
+                return Err(err("unexpected words"));
+            }
+            match words[0] {
+                "EmploymentDeduction" => {
+                    let limit = match words.get(1) {
+                        Some(&"-") => None,
+                        _ => Some(amount(1)?),
+                    };
+                    table.employment_deductions.push(EmploymentDeduction {
+                        limit,
+                        rate: rate(2)?,
+                        amount: amount(3)?,
+                    });
+                }
+                "BasicDeduction" => table.basic_deduction = amount(1)?,
+                "DependentDeduction" => table.dependent_deduction = amount(1)?,
+                "Bracket" => table.brackets.push(TaxBracket::new(amount(1)?, rate(2)?)),
+                "Surtax" => table.surtax_rate = rate(1)?,
+                _ => return Err(err("unknown rule")),
+            }
+        }
+        if table.brackets.is_empty() {
+            return Err("no brackets".to_string());
+        }
+        table
+            .employment_deductions
+            .sort_by(|a, b| match (a.limit, b.limit) {
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                (a, b) => a.is_none().cmp(&b.is_none()),
+            });
+        table
+            .brackets
+            .sort_by(|a, b| a.threshold.total_cmp(&b.threshold));
+        Ok(table)
+    }
+}
+
+// 扶養控除等申告書 and 保険料控除申告書 of an employee for the year
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearEndDeclaration {
+    emp_id: EmployeeId,
+    dependents: u32,
+    // e.g. life insurance premiums, as deductible amounts
+    deductions: Vec<(String, f32)>,
+}
+impl YearEndDeclaration {
+    pub fn new(emp_id: EmployeeId, dependents: u32, deductions: Vec<(String, f32)>) -> Self {
+        Self {
+            emp_id,
+            dependents,
+            deductions,
+        }
+    }
+    pub fn get_emp_id(&self) -> EmployeeId {
+        self.emp_id
+    }
+    pub fn get_dependents(&self) -> u32 {
+        self.dependents
+    }
+    pub fn get_deductions(&self) -> &[(String, f32)] {
+        &self.deductions
+    }
+}
+
+// one employee per line, `#` starts a comment:
+//   emp_id,dependents[,description,amount]...
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearEndDeclarations(pub Vec<YearEndDeclaration>);
+impl FromStr for YearEndDeclarations {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut declarations = vec![];
+        for (n, line) in s.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+            let err = |msg: &str| format!("line {}: {}: {}", n + 1, msg, line);
+            let fields = line.split(',').map(|f| f.trim()).collect::<Vec<_>>();
+            if fields.len() < 2 || fields.len() % 2 != 0 {
+                return Err(err(
+                    "emp_id, dependents and pairs of description and amount expected",
+                ));
+            }
+            let emp_id = fields[0]
+                .parse::<EmployeeId>()
+                .map_err(|_| err("emp_id expected"))?;
+            let dependents = fields[1]
+                .parse::<u32>()
+                .map_err(|_| err("dependents expected"))?;
+            let mut deductions = vec![];
+            for pair in fields[2..].chunks(2) {
+                let amount = pair[1].parse::<f32>().map_err(|_| err("amount expected"))?;
+                deductions.push((pair[0].to_string(), amount));
+            }
+            declarations.push(YearEndDeclaration::new(emp_id, dependents, deductions));
+        }
+        Ok(Self(declarations))
+    }
+}
+
+// the pay and the deductions of paychecks the annual tax is reconciled with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct YearToDate {
+    pay: f32,
+    social_insurance: f32,
+    // deductible as 小規模企業共済等掛金控除, apart from the social insurance
+    pre_tax_contributions: f32,
+    withheld_tax: f32,
+}
+impl YearToDate {
+    // the settlements of adjustments are left out
+    pub fn of<'a>(paychecks: impl IntoIterator<Item = &'a Paycheck>) -> Self {
+        paychecks.into_iter().fold(Self::default(), |mut ytd, pc| {
+            ytd.pay += pc.get_gross_pay();
+            for line in pc.get_deduction_lines().iter().filter(|l| {
+                !l.get_description()
+                    .starts_with(YearEndAdjustment::DESCRIPTION)
+            }) {
+                match line.get_kind() {
+                    DeductionKind::SocialInsurance => ytd.social_insurance += line.get_amount(),
+                    DeductionKind::PreTax => ytd.pre_tax_contributions += line.get_amount(),
+                    DeductionKind::IncomeTax => ytd.withheld_tax += line.get_amount(),
+                    _ => {}
+                }
+            }
+            ytd
+        })
+    }
+    fn add(self, other: Self) -> Self {
+        Self {
+            pay: self.pay + other.pay,
+            social_insurance: self.social_insurance + other.social_insurance,
+            pre_tax_contributions: self.pre_tax_contributions + other.pre_tax_contributions,
+            withheld_tax: self.withheld_tax + other.withheld_tax,
+        }
+    }
+}
+
+// reconciliation of the tax withheld over the year with the annual tax
+#[derive(Debug, Clone, PartialEq)]
+pub struct YearEndAdjustment {
+    annual_pay: f32,
+    social_insurance: f32,
+    pre_tax_contributions: f32,
+    taxable_income: f32,
+    annual_tax: f32,
+    withheld_tax: f32,
+}
+impl YearEndAdjustment {
+    pub const DESCRIPTION: &'static str = "Year-end adjustment";
+
+    pub fn calculate(
+        table: &YearEndTable,
+        declaration: &YearEndDeclaration,
+        year_to_date: &YearToDate,
+    ) -> Self {
+        let annual_pay = year_to_date.pay;
+        let declared = declaration
+            .get_deductions()
+            .iter()
+            .fold(0.0, |acc, (_, amount)| acc + amount);
+        let employment_income = annual_pay - table.employment_income_deduction(annual_pay);
+        let taxable_income = (employment_income
+            - year_to_date.social_insurance
+            - year_to_date.pre_tax_contributions
+            - declared
+            - table.basic_deduction
+            - declaration.get_dependents() as f32 * table.dependent_deduction)
+            .max(0.0);
+        Self {
+            annual_pay,
+            social_insurance: year_to_date.social_insurance,
+            pre_tax_contributions: year_to_date.pre_tax_contributions,
+            taxable_income,
+            annual_tax: table.annual_tax(taxable_income),
+            withheld_tax: year_to_date.withheld_tax,
+        }
+    }
+    pub fn get_annual_pay(&self) -> f32 {
+        self.annual_pay
+    }
+    pub fn get_social_insurance(&self) -> f32 {
+        self.social_insurance
+    }
+    pub fn get_pre_tax_contributions(&self) -> f32 {
+        self.pre_tax_contributions
+    }
+    pub fn get_taxable_income(&self) -> f32 {
+        self.taxable_income
+    }
+    pub fn get_annual_tax(&self) -> f32 {
+        self.annual_tax
+    }
+    pub fn get_withheld_tax(&self) -> f32 {
+        self.withheld_tax
+    }
+    // collected if positive, refunded if negative
+    pub fn get_adjustment(&self) -> f32 {
+        ((self.annual_tax - self.withheld_tax) * 100.0).round() / 100.0
+    }
+}
+
+// what the adjustment is calculated from when the year's last paycheck is made out
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearEndBasis {
+    table: SharedTable<YearEndTable>,
+    declaration: YearEndDeclaration,
+    // the paychecks of the year before the last one, by pay date
+    paid: BTreeMap<NaiveDate, YearToDate>,
+}
+impl YearEndBasis {
+    pub fn get_table(&self) -> &SharedTable<YearEndTable> {
+        &self.table
+    }
+    pub fn get_declaration(&self) -> &YearEndDeclaration {
+        &self.declaration
+    }
+    // the year's last paycheck `pc` counted in
+    fn calculate(&self, pc: &Paycheck) -> YearEndAdjustment {
+        let year_to_date = self
+            .paid
+            .values()
+            .fold(YearToDate::of([pc]), |acc, ytd| acc.add(*ytd));
+        YearEndAdjustment::calculate(&self.table.get(), &self.declaration, &year_to_date)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearEndSettlement {
+    year: i32,
+    // the year's last pay date, or the one after it for a correction
+    pay_date: NaiveDate,
+    // none for a correction, of which the amount is known beforehand
+    basis: Option<YearEndBasis>,
+    // collected if positive, refunded if negative; none until it is calculated
+    amount: Option<f32>,
+    settled: bool,
+}
+impl YearEndSettlement {
+    pub fn get_year(&self) -> i32 {
+        self.year
+    }
+    pub fn get_pay_date(&self) -> NaiveDate {
+        self.pay_date
+    }
+    pub fn get_basis(&self) -> Option<&YearEndBasis> {
+        self.basis.as_ref()
+    }
+    pub fn get_amount(&self) -> Option<f32> {
+        self.amount
+    }
+    pub fn is_settled(&self) -> bool {
+        self.settled
+    }
+    fn description(&self) -> String {
+        format!("{} {}", YearEndAdjustment::DESCRIPTION, self.year)
+    }
+    // a payday run again settles it again
+    fn is_due(&self, pay_date: NaiveDate) -> bool {
+        self.pay_date == pay_date
+    }
+    fn calculate(&self, pc: &Paycheck) -> f32 {
+        match &self.basis {
+            Some(basis) => basis.calculate(pc).get_adjustment(),
+            None => self.amount.unwrap_or(0.0),
+        }
+    }
+}
+
+// year-end adjustments of an employee, settled on the year's last paycheck, which
+// sees the tax withheld by the affiliations added before it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct YearEndSettlements {
+    settlements: Vec<YearEndSettlement>,
+}
+impl YearEndSettlements {
+    pub const NAME: &'static str = "Year-end adjustment";
+
+    pub fn new() -> Self {
+        Self {
+            settlements: vec![],
+        }
+    }
+    pub fn get_settlements(&self) -> &[YearEndSettlement] {
+        &self.settlements
+    }
+    // the adjustment is calculated when the paycheck on `pay_date`, the year's last, is
+    // made out; `paychecks` are the year's before it, and an adjustment made again
+    // before that paycheck replaces the pending one
+    pub fn prepare(
+        &mut self,
+        year: i32,
+        pay_date: NaiveDate,
+        table: SharedTable<YearEndTable>,
+        declaration: YearEndDeclaration,
+        paychecks: &[Paycheck],
+    ) {
+        self.settlements.retain(|s| s.year != year || s.settled);
+        let paid = paychecks
+            .iter()
+            .map(|pc| (*pc.get_pay_period().end(), pc))
+            .filter(|(d, _)| d.year() == year && *d < pay_date)
+            .map(|(d, pc)| (d, YearToDate::of([pc])))
+            .collect();
+        self.settlements.push(YearEndSettlement {
+            year,
+            pay_date,
+            basis: Some(YearEndBasis {
+                table,
+                declaration,
+                paid,
+            }),
+            amount: None,
+            settled: false,
+        });
+    }
+    // an adjustment made again after the year's last paycheck corrects it by the
+    // difference to what has been settled, on the paycheck on `pay_date`
+    pub fn settle(&mut self, adjustment: &YearEndAdjustment, year: i32, pay_date: NaiveDate) {
+        self.settlements.retain(|s| s.year != year || s.settled);
+        let settled = self
+            .settlements
+            .iter()
+            .filter(|s| s.year == year)
+            .fold(0.0, |acc, s| acc + s.amount.unwrap_or(0.0));
+        let amount = ((adjustment.get_adjustment() - settled) * 100.0).round() / 100.0;
+        if amount != 0.0 {
+            self.settlements.push(YearEndSettlement {
+                year,
+                pay_date,
+                basis: None,
+                amount: Some(amount),
+                settled: false,
+            });
+        }
+    }
+}
+impl Default for YearEndSettlements {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl Affiliation for YearEndSettlements {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+    fn get_name(&self) -> &str {
+        Self::NAME
+    }
+    fn get_deduction_kind(&self) -> DeductionKind {
+        DeductionKind::IncomeTax
+    }
+    fn calculate_deductions(&self, pc: &Paycheck) -> f32 {
+        self.calculate_deduction_lines(pc)
+            .iter()
+            .fold(0.0, |acc, l| acc + l.get_amount())
+    }
+    fn calculate_deduction_lines(&self, pc: &Paycheck) -> Vec<DeductionLine> {
+        let pay_date = *pc.get_pay_period().end();
+        self.settlements
+            .iter()
+            .filter(|s| s.is_due(pay_date))
+            .map(|s| (s.description(), s.calculate(pc)))
+            .filter(|(_, amount)| *amount != 0.0)
+            .map(|(description, amount)| {
+                DeductionLine::new(DeductionKind::IncomeTax, &description, amount)
+            })
+            .collect()
+    }
+    // the paychecks before the year's last one are kept for the adjustment; what
+    // could not be collected is carried to the next paycheck as arrears
+    fn record_payment(&mut self, pc: &Paycheck) {
+        let pay_date = *pc.get_pay_period().end();
+        for settlement in self.settlements.iter_mut() {
+            if settlement.is_due(pay_date) {
+                settlement.amount = Some(settlement.calculate(pc));
+                settlement.settled = true;
+            } else if !settlement.settled
+                && pay_date.year() == settlement.year
+                && pay_date < settlement.pay_date
+            {
+                if let Some(basis) = settlement.basis.as_mut() {
+                    basis.paid.insert(pay_date, YearToDate::of([pc]));
+                }
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod test_year_end_adjustment {
+    use super::*;
+    use chrono::Months;
+
+    const RULES: &str = "EmploymentDeduction - 0% 0\nBasicDeduction 480000\nDependentDeduction 380000\nBracket 0 10%";
+
+    fn table() -> SharedTable<YearEndTable> {
+        SharedTable::insert(
+            "test_year_end_adjustment/table.tbl",
+            RULES.parse::<YearEndTable>().unwrap(),
+        )
+    }
+    fn declaration(dependents: u32, deductions: Vec<(String, f32)>) -> YearEndDeclaration {
+        YearEndDeclaration::new(91, dependents, deductions)
+    }
+    // paid 300000 a month, less 40000 of social insurance
+    fn paycheck(year: i32, month: u32, withheld: f32) -> Paycheck {
+        let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap();
+        let last = first
+            .checked_add_months(Months::new(1))
+            .unwrap()
+            .pred_opt()
+            .unwrap();
+        let mut pc = Paycheck::new(first..=last);
+        pc.set_gross_pay(300000.0);
+        pc.add_deduction_line(DeductionLine::new(
+            DeductionKind::SocialInsurance,
+            "Health insurance",
+            40000.0,
+        ));
+        pc.add_deduction_line(DeductionLine::new(
+            DeductionKind::IncomeTax,
+            "Income tax",
+            withheld,
+        ));
+        pc
+    }
+    fn paychecks(withheld: f32) -> Vec<Paycheck> {
+        (1..=12).map(|m| paycheck(2024, m, withheld)).collect()
+    }
+    fn december() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+    }
+    fn settlements(withheld: f32, dependents: u32) -> YearEndSettlements {
+        let mut settlements = YearEndSettlements::new();
+        settlements.prepare(
+            2024,
+            december(),
+            table(),
+            declaration(dependents, vec![]),
+            &paychecks(withheld)[..11],
+        );
+        settlements
+    }
+    // makes out the paycheck as the employee does, with the adjustment after the withholding
+    fn make_out(settlements: &mut YearEndSettlements, mut pc: Paycheck) -> Paycheck {
+        for line in settlements.calculate_deduction_lines(&pc) {
+            pc.add_deduction_line(line);
+        }
+        settlements.record_payment(&pc);
+        pc
+    }
+    fn adjustment_lines(pc: &Paycheck) -> Vec<f32> {
+        pc.get_deduction_lines()
+            .iter()
+            .filter(|l| l.get_description() == "Year-end adjustment 2024")
+            .map(|l| l.get_amount())
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate() {
+        // 3600000 less 480000 of social insurance and 480000 of basic deduction
+        let adjustment = YearEndAdjustment::calculate(
+            &table().get(),
+            &declaration(0, vec![]),
+            &YearToDate::of(&paychecks(20000.0)),
+        );
+        assert_eq!(adjustment.get_annual_pay(), 3600000.0);
+        assert_eq!(adjustment.get_social_insurance(), 480000.0);
+        assert_eq!(adjustment.get_taxable_income(), 2640000.0);
+        assert_eq!(adjustment.get_annual_tax(), 264000.0);
+        assert_eq!(adjustment.get_withheld_tax(), 240000.0);
+        assert_eq!(adjustment.get_adjustment(), 24000.0);
+    }
+    #[test]
+    fn test_declared_deductions() {
+        // less 380000 for a dependent and the declared premiums
+        let adjustment = YearEndAdjustment::calculate(
+            &table().get(),
+            &declaration(
+                1,
+                vec![
+                    ("Life insurance".to_string(), 40000.0),
+                    ("Earthquake insurance".to_string(), 5000.0),
+                ],
+            ),
+            &YearToDate::of(&paychecks(20000.0)),
+        );
+        assert_eq!(adjustment.get_taxable_income(), 2215000.0);
+        assert_eq!(adjustment.get_annual_tax(), 221500.0);
+        assert_eq!(adjustment.get_adjustment(), -18500.0);
+    }
+    #[test]
+    fn test_adjustments_left_out() {
+        let mut pc = paycheck(2025, 1, 20000.0);
+        pc.add_deduction_line(DeductionLine::new(
+            DeductionKind::IncomeTax,
+            "Year-end adjustment 2024",
+            -18500.0,
+        ));
+        let year_to_date = YearToDate::of([&pc]);
+        assert_eq!(year_to_date, YearToDate::of([&paycheck(2025, 1, 20000.0)]));
+    }
+    #[test]
+    fn test_refund() {
+        let mut settlements = settlements(30000.0, 0);
+        // nothing before the year's last paycheck
+        let november = make_out(&mut settlements, paycheck(2024, 11, 30000.0));
+        assert!(adjustment_lines(&november).is_empty());
+        // 264000 of annual tax, 360000 withheld
+        let pc = make_out(&mut settlements, paycheck(2024, 12, 30000.0));
+        assert_eq!(adjustment_lines(&pc), vec![-96000.0]);
+        let settlement = &settlements.get_settlements()[0];
+        assert!(settlement.is_settled());
+        assert_eq!(settlement.get_amount(), Some(-96000.0));
+        // not again on the next year's paychecks
+        let january = make_out(&mut settlements, paycheck(2025, 1, 30000.0));
+        assert!(adjustment_lines(&january).is_empty());
+    }
+    #[test]
+    fn test_collection() {
+        let mut settlements = settlements(20000.0, 0);
+        let pc = make_out(&mut settlements, paycheck(2024, 12, 20000.0));
+        assert_eq!(adjustment_lines(&pc), vec![24000.0]);
+        // a payday run again settles it again, as much
+        let pc = make_out(&mut settlements, paycheck(2024, 12, 20000.0));
+        assert_eq!(adjustment_lines(&pc), vec![24000.0]);
+        assert_eq!(settlements.get_settlements().len(), 1);
+    }
+    #[test]
+    fn test_paid_after_prepared() {
+        let mut settlements = YearEndSettlements::new();
+        settlements.prepare(
+            2024,
+            december(),
+            table(),
+            declaration(0, vec![]),
+            &paychecks(20000.0)[..6],
+        );
+        // the paychecks made out after it are counted in
+        for month in 7..=11 {
+            make_out(&mut settlements, paycheck(2024, month, 20000.0));
+        }
+        let pc = make_out(&mut settlements, paycheck(2024, 12, 20000.0));
+        assert_eq!(adjustment_lines(&pc), vec![24000.0]);
+    }
+    #[test]
+    fn test_prepared_again() {
+        let mut settlements = settlements(20000.0, 0);
+        // the pending adjustment is replaced
+        settlements.prepare(
+            2024,
+            december(),
+            table(),
+            declaration(1, vec![]),
+            &paychecks(20000.0)[..11],
+        );
+        assert_eq!(settlements.get_settlements().len(), 1);
+        // 2260000 taxable
+        let pc = make_out(&mut settlements, paycheck(2024, 12, 20000.0));
+        assert_eq!(adjustment_lines(&pc), vec![-14000.0]);
+    }
+    #[test]
+    fn test_corrected_after_settled() {
+        let mut settlements = settlements(20000.0, 0);
+        make_out(&mut settlements, paycheck(2024, 12, 20000.0));
+        // a dependent declared late: -14000 in all, 24000 collected already
+        let adjustment = YearEndAdjustment::calculate(
+            &table().get(),
+            &declaration(1, vec![]),
+            &YearToDate::of(&paychecks(20000.0)),
+        );
+        let january = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        settlements.settle(&adjustment, 2024, january);
+        // made again before it is settled, it replaces the pending correction
+        settlements.settle(&adjustment, 2024, january);
+        assert_eq!(settlements.get_settlements().len(), 2);
+        let pc = make_out(&mut settlements, paycheck(2025, 1, 20000.0));
+        assert_eq!(adjustment_lines(&pc), vec![-38000.0]);
+        // nothing is left to settle
+        settlements.settle(&adjustment, 2024, january);
+        assert_eq!(settlements.get_settlements().len(), 2);
+    }
+}
@@ -0,0 +1,960 @@
+mod schema;
+
+pub use schema::{migrate, schema_version};
+
+use chrono::NaiveDate;
+use rusqlite::{params, params_from_iter, types::Value, Connection, OptionalExtension};
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    ops::{Deref, RangeInclusive},
+    rc::Rc,
+};
+
+use dao::{CommitError, DaoError, Database, EmployeeDao, EmployeeQuery};
+use payroll_domain::{Employee, EmployeeId, MemberId, Paycheck};
+use payroll_impl::record::{EmployeeRecord, ScheduleRecord};
+
+// the context is the transaction of the connection the dao works in
+#[derive(Debug, Clone, Default)]
+pub struct SqliteDb;
+impl SqliteDb {
+    pub fn new() -> Self {
+        Self
+    }
+    // opens the database at `path`, creating or migrating its schema
+    pub fn open(path: &str) -> rusqlite::Result<Connection> {
+        Self::prepare(Connection::open(path)?)
+    }
+    pub fn open_in_memory() -> rusqlite::Result<Connection> {
+        Self::prepare(Connection::open_in_memory()?)
+    }
+    fn prepare(mut conn: Connection) -> rusqlite::Result<Connection> {
+        conn.pragma_update(None, "foreign_keys", true)?;
+        migrate(&mut conn)?;
+        Ok(conn)
+    }
+}
+
+// the context of SqliteStore, a transaction begun on its connection
+#[derive(Debug)]
+pub struct SqliteTx {
+    conn: Rc<Connection>,
+}
+impl Deref for SqliteTx {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+// a database file the transactions of a front-end run against, one at a time
+#[derive(Debug, Clone)]
+pub struct SqliteStore {
+    conn: Rc<Connection>,
+}
+impl SqliteStore {
+    pub fn open(path: &str) -> rusqlite::Result<Self> {
+        Ok(Self {
+            conn: Rc::new(SqliteDb::open(path)?),
+        })
+    }
+}
+impl Database for SqliteStore {
+    type Ctx = SqliteTx;
+
+    fn dao(&self) -> &impl EmployeeDao<SqliteTx> {
+        &SqliteDb
+    }
+    // a transaction that can't be begun fails as one that can't be committed
+    fn with_transaction<T, E, F>(&self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut SqliteTx) -> Result<T, E>,
+        E: CommitError,
+    {
+        let run = |sql: &str| {
+            self.conn
+                .execute_batch(sql)
+                .map_err(|e| E::commit_failed(storage_error(e)))
+        };
+        run("BEGIN")?;
+        let mut tx = SqliteTx {
+            conn: self.conn.clone(),
+        };
+        let result = f(&mut tx).and_then(|x| run("COMMIT").map(|_| x));
+        if result.is_err() {
+            // still open if it could not be committed
+            let _ = run("ROLLBACK");
+        }
+        result
+    }
+}
+
+fn storage_error(e: rusqlite::Error) -> DaoError {
+    DaoError::storage_error(e)
+}
+fn to_json<T: Serialize>(value: &T) -> Result<String, DaoError> {
+    serde_json::to_string(value).map_err(DaoError::unsupported_data)
+}
+fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, DaoError> {
+    serde_json::from_str(json).map_err(DaoError::unsupported_data)
+}
+
+fn exists(tx: &Connection, emp_id: EmployeeId) -> Result<bool, DaoError> {
+    tx.query_row(
+        "SELECT 1 FROM employees WHERE emp_id = ?1",
+        params![emp_id],
+        |_| Ok(()),
+    )
+    .optional()
+    .map(|found| found.is_some())
+    .map_err(storage_error)
+}
+
+fn stored_version(tx: &Connection, emp_id: EmployeeId) -> Result<Option<u32>, DaoError> {
+    tx.query_row(
+        "SELECT version FROM employees WHERE emp_id = ?1",
+        params![emp_id],
+        |row| row.get(0),
+    )
+    .optional()
+    .map_err(storage_error)
+}
+
+// the types of schedules the employees are paid on
+fn schedule_types(tx: &Connection) -> Result<Vec<(String, ScheduleRecord)>, DaoError> {
+    let mut stmt = tx
+        .prepare("SELECT DISTINCT type, data FROM schedules")
+        .map_err(storage_error)?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })
+        .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+        .map_err(storage_error)?;
+    rows.into_iter()
+        .map(|(t, data)| Ok((t, from_json(&data)?)))
+        .collect()
+}
+
+// fails if the member id of the employee is held by another one
+fn check_member(tx: &Connection, record: &EmployeeRecord) -> Result<(), DaoError> {
+    let Some(member_id) = record.member_id() else {
+        return Ok(());
+    };
+    match find_member(tx, member_id)? {
+        Some(emp_id) if emp_id != record.emp_id => {
+            Err(DaoError::UnionMemberAlreadyExists(member_id))
+        }
+        _ => Ok(()),
+    }
+}
+fn find_member(tx: &Connection, member_id: MemberId) -> Result<Option<EmployeeId>, DaoError> {
+    tx.query_row(
+        "SELECT emp_id FROM union_members WHERE member_id = ?1",
+        params![member_id],
+        |row| row.get::<_, EmployeeId>(0),
+    )
+    .optional()
+    .map_err(storage_error)
+}
+
+// the parts of the employee are removed along with it
+fn remove(tx: &Connection, emp_id: EmployeeId) -> Result<(), DaoError> {
+    tx.execute("DELETE FROM employees WHERE emp_id = ?1", params![emp_id])
+        .map(|_| ())
+        .map_err(storage_error)
+}
+
+fn save(tx: &Connection, record: &EmployeeRecord) -> Result<(), DaoError> {
+    let emp_id = record.emp_id;
+    tx.execute(
+        "INSERT INTO employees (emp_id, name, address, net_pay_floor, arrears, version)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            emp_id,
+            record.name,
+            record.address,
+            record.net_pay_floor,
+            to_json(&record.arrears)?,
+            record.version
+        ],
+    )
+    .map_err(storage_error)?;
+    tx.execute(
+        "INSERT INTO classifications (emp_id, type, data) VALUES (?1, ?2, ?3)",
+        params![
+            emp_id,
+            record.classification.get_type(),
+            to_json(&record.classification)?
+        ],
+    )
+    .map_err(storage_error)?;
+    tx.execute(
+        "INSERT INTO schedules (emp_id, type, data) VALUES (?1, ?2, ?3)",
+        params![
+            emp_id,
+            record.schedule.get_type(),
+            to_json(&record.schedule)?
+        ],
+    )
+    .map_err(storage_error)?;
+    tx.execute(
+        "INSERT INTO methods (emp_id, type, data) VALUES (?1, ?2, ?3)",
+        params![emp_id, record.method.get_type(), to_json(&record.method)?],
+    )
+    .map_err(storage_error)?;
+    for (position, affiliation) in record.affiliations.iter().enumerate() {
+        tx.execute(
+            "INSERT INTO affiliations (emp_id, position, type, data) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                emp_id,
+                position,
+                affiliation.get_type(),
+                to_json(affiliation)?
+            ],
+        )
+        .map_err(storage_error)?;
+    }
+    if let Some(member_id) = record.member_id() {
+        tx.execute(
+            "INSERT INTO union_members (member_id, emp_id) VALUES (?1, ?2)",
+            params![member_id, emp_id],
+        )
+        .map_err(storage_error)?;
+    }
+    Ok(())
+}
+
+fn load(tx: &Connection, emp_id: EmployeeId) -> Result<Option<Employee>, DaoError> {
+    let row = tx
+        .query_row(
+            "SELECT e.name, e.address, e.net_pay_floor, e.arrears, e.version, c.data, s.data, m.data
+             FROM employees e
+             JOIN classifications c ON c.emp_id = e.emp_id
+             JOIN schedules s ON s.emp_id = e.emp_id
+             JOIN methods m ON m.emp_id = e.emp_id
+             WHERE e.emp_id = ?1",
+            params![emp_id],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, f32>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, u32>(4)?,
+                    row.get::<_, String>(5)?,
+                    row.get::<_, String>(6)?,
+                    row.get::<_, String>(7)?,
+                ))
+            },
+        )
+        .optional()
+        .map_err(storage_error)?;
+    let Some((name, address, net_pay_floor, arrears, version, classification, schedule, method)) =
+        row
+    else {
+        return Ok(None);
+    };
+    let mut stmt = tx
+        .prepare("SELECT data FROM affiliations WHERE emp_id = ?1 ORDER BY position")
+        .map_err(storage_error)?;
+    let affiliations = stmt
+        .query_map(params![emp_id], |row| row.get::<_, String>(0))
+        .map_err(storage_error)?
+        .map(|data| from_json(&data.map_err(storage_error)?))
+        .collect::<Result<Vec<_>, _>>()?;
+    let record = EmployeeRecord {
+        emp_id,
+        name,
+        address,
+        classification: from_json(&classification)?,
+        schedule: from_json(&schedule)?,
+        method: from_json(&method)?,
+        affiliations,
+        net_pay_floor,
+        arrears: from_json(&arrears)?,
+        version,
+    };
+    Ok(Some(record.into_employee()))
+}
+
+// works in a transaction on the connection the context derefs to
+impl<Ctx: Deref<Target = Connection>> EmployeeDao<Ctx> for SqliteDb {
+    fn insert(&self, emp: Employee) -> impl tx_rs::Tx<Ctx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            let emp_id = emp.get_emp_id();
+            if exists(tx, emp_id)? {
+                return Err(DaoError::EmployeeAlreadyExists(emp_id));
+            }
+            let record = EmployeeRecord::from_employee(&emp).map_err(DaoError::unsupported_data)?;
+            check_member(tx, &record)?;
+            save(tx, &record)?;
+            Ok(emp_id)
+        })
+    }
+    fn delete(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            if !exists(tx, emp_id)? {
+                return Err(DaoError::EmployeeNotFound(emp_id));
+            }
+            remove(tx, emp_id)
+        })
+    }
+    fn fetch(&self, emp_id: EmployeeId) -> impl tx_rs::Tx<Ctx, Item = Employee, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| match load(tx, emp_id)? {
+            Some(emp) => Ok(emp),
+            None => Err(DaoError::EmployeeNotFound(emp_id)),
+        })
+    }
+    fn update(&self, mut emp: Employee) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            let emp_id = emp.get_emp_id();
+            let Some(version) = stored_version(tx, emp_id)? else {
+                return Err(DaoError::EmployeeNotFound(emp_id));
+            };
+            if version != emp.get_version() {
+                return Err(DaoError::VersionConflict {
+                    emp_id,
+                    version: emp.get_version(),
+                });
+            }
+            emp.set_version(version + 1);
+            let record = EmployeeRecord::from_employee(&emp).map_err(DaoError::unsupported_data)?;
+            // checked before anything is removed
+            check_member(tx, &record)?;
+            remove(tx, emp_id)?;
+            save(tx, &record)
+        })
+    }
+    fn get_all(&self) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            let mut stmt = tx
+                .prepare("SELECT emp_id FROM employees ORDER BY emp_id")
+                .map_err(storage_error)?;
+            let emp_ids = stmt
+                .query_map([], |row| row.get::<_, EmployeeId>(0))
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(storage_error)?;
+            let mut employees = vec![];
+            for emp_id in emp_ids {
+                if let Some(emp) = load(tx, emp_id)? {
+                    employees.push(emp);
+                }
+            }
+            Ok(employees)
+        })
+    }
+    fn get_payable(
+        &self,
+        pay_date: NaiveDate,
+        after: Option<EmployeeId>,
+        limit: usize,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            // all or none of those on a schedule of the same type are paid on the date
+            let payable_types = schedule_types(tx)?
+                .into_iter()
+                .filter(|(_, schedule)| {
+                    schedule
+                        .clone()
+                        .into_schedule()
+                        .read()
+                        .unwrap()
+                        .is_pay_date(pay_date)
+                })
+                .map(|(t, _)| t)
+                .collect::<Vec<_>>();
+            if payable_types.is_empty() {
+                return Ok(vec![]);
+            }
+            let mut stmt = tx
+                .prepare(&format!(
+                    "SELECT emp_id FROM schedules
+                     WHERE type IN ({}) AND emp_id > ? ORDER BY emp_id LIMIT ?",
+                    vec!["?"; payable_types.len()].join(", ")
+                ))
+                .map_err(storage_error)?;
+            let mut values = payable_types
+                .into_iter()
+                .map(Value::Text)
+                .collect::<Vec<_>>();
+            values.push(Value::Integer(after.map_or(-1, i64::from)));
+            values.push(Value::Integer(limit as i64));
+            let emp_ids = stmt
+                .query_map(params_from_iter(values), |row| row.get::<_, EmployeeId>(0))
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(storage_error)?;
+            let mut employees = vec![];
+            for emp_id in emp_ids {
+                if let Some(emp) = load(tx, emp_id)? {
+                    employees.push(emp);
+                }
+            }
+            Ok(employees)
+        })
+    }
+    fn find_employees(
+        &self,
+        query: EmployeeQuery,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            // a condition not given is null and holds for any
+            let mut stmt = tx
+                .prepare(
+                    "SELECT e.emp_id
+                     FROM employees e
+                     JOIN classifications c ON c.emp_id = e.emp_id
+                     JOIN schedules s ON s.emp_id = e.emp_id
+                     JOIN methods m ON m.emp_id = e.emp_id
+                     WHERE (?1 IS NULL OR instr(e.name, ?1) > 0)
+                       AND (?2 IS NULL OR c.type = ?2)
+                       AND (?3 IS NULL OR s.type = ?3)
+                       AND (?4 IS NULL OR m.type = ?4)
+                       AND (?5 IS NULL OR ?5 = EXISTS (
+                           SELECT 1 FROM union_members u WHERE u.emp_id = e.emp_id))
+                     ORDER BY e.emp_id",
+                )
+                .map_err(storage_error)?;
+            let emp_ids = stmt
+                .query_map(
+                    params![
+                        query.name,
+                        query.classification.map(|k| k.get_type()),
+                        query.schedule.map(|k| k.get_type()),
+                        query.method.map(|k| k.get_type()),
+                        query.union_member
+                    ],
+                    |row| row.get::<_, EmployeeId>(0),
+                )
+                .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+                .map_err(storage_error)?;
+            let mut employees = vec![];
+            for emp_id in emp_ids {
+                if let Some(emp) = load(tx, emp_id)? {
+                    employees.push(emp);
+                }
+            }
+            Ok(employees)
+        })
+    }
+
+    fn find_union_member(
+        &self,
+        member_id: MemberId,
+    ) -> impl tx_rs::Tx<Ctx, Item = EmployeeId, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            find_member(tx, member_id)?.ok_or(DaoError::UnionMemberNotFound(member_id))
+        })
+    }
+
+    fn record_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        pc: Paycheck,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            let period = pc.get_pay_period();
+            let data = to_json(&pc)?;
+            tx.execute(
+                "INSERT INTO paychecks
+                 (emp_id, period_start, period_end, gross_pay, deductions, net_pay, data)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    emp_id,
+                    period.start().to_string(),
+                    period.end().to_string(),
+                    pc.get_gross_pay(),
+                    pc.get_deductions(),
+                    pc.get_net_pay(),
+                    data
+                ],
+            )
+            .map(|_| ())
+            .map_err(storage_error)
+        })
+    }
+    fn get_paychecks(
+        &self,
+        emp_id: EmployeeId,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Paycheck>, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            let mut stmt = tx
+                .prepare("SELECT data FROM paychecks WHERE emp_id = ?1 ORDER BY paycheck_id")
+                .map_err(storage_error)?;
+            let paychecks = stmt
+                .query_map(params![emp_id], |row| row.get::<_, String>(0))
+                .map_err(storage_error)?
+                .map(|data| from_json(&data.map_err(storage_error)?))
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(paychecks)
+        })
+    }
+    fn has_paycheck(
+        &self,
+        emp_id: EmployeeId,
+        period: RangeInclusive<NaiveDate>,
+    ) -> impl tx_rs::Tx<Ctx, Item = bool, Err = DaoError> {
+        tx_rs::with_tx(move |tx: &mut Ctx| {
+            tx.query_row(
+                "SELECT EXISTS (SELECT 1 FROM paychecks
+                 WHERE emp_id = ?1 AND period_end = ?2 AND period_start = ?3)",
+                params![emp_id, period.end().to_string(), period.start().to_string()],
+                |row| row.get(0),
+            )
+            .map_err(storage_error)
+        })
+    }
+}
+#[cfg(test)]
+mod test_sqlite_db {
+    use super::*;
+    use chrono::NaiveDate;
+    use dao::{ClassificationKind, MethodKind, ScheduleKind};
+    use std::sync::{Arc, RwLock};
+    use tx_rs::Tx;
+
+    use payroll_domain::{
+        Affiliation, Component, DeductionKind, DeductionLine, DuesPolicy, RetirementElection,
+        WithholdingColumn,
+    };
+    use payroll_impl::{
+        affiliation::{
+            Advance, Advances, GarnishmentOrder, Garnishments, GeneralDeduction,
+            RetirementContribution, UnionAffiliation,
+        },
+        classification::{component_classification, CompositeClassification, HourlyClassification},
+        insurance::{SocialInsurance, SocialInsuranceTable},
+        method::MailMethod,
+        schedule::{MonthlySchedule, WeeklySchedule},
+        table::SharedTable,
+        tax::{
+            IncomeTaxWithholding, MonthlyWithholding, MonthlyWithholdingTable, ResidentTax,
+            TaxTable, YearEndSettlements,
+        },
+    };
+
+    fn hourly_emp(emp_id: EmployeeId) -> Employee {
+        let mut classification = HourlyClassification::new(15.25);
+        classification.add_timecard(NaiveDate::from_ymd_opt(2024, 8, 1).unwrap(), 8.0);
+        // 7237 for 57
+        let union: Arc<RwLock<dyn Affiliation>> = Arc::new(RwLock::new(UnionAffiliation::new(
+            7180 + emp_id,
+            DuesPolicy::PerMonth { dues: 40.0 },
+        )));
+        let mut emp = Employee::new(
+            emp_id,
+            "Bob",
+            "Home",
+            Arc::new(RwLock::new(classification)),
+            Arc::new(RwLock::new(WeeklySchedule)),
+            Arc::new(RwLock::new(MailMethod::new("bob@gmail.com"))),
+            vec![
+                union,
+                Arc::new(RwLock::new(GeneralDeduction::new("Health", 45.0))),
+            ],
+        );
+        emp.set_net_pay_floor(190.0);
+        emp.set_arrears(vec![DeductionLine::new(
+            DeductionKind::General,
+            "Union dues",
+            0.78,
+        )]);
+        emp
+    }
+    fn set_member_id(emp: &Employee, member_id: MemberId) {
+        emp.get_affiliation(UnionAffiliation::NAME)
+            .unwrap()
+            .write()
+            .unwrap()
+            .as_any_mut()
+            .downcast_mut::<UnionAffiliation>()
+            .unwrap()
+            .set_member_id(member_id);
+    }
+    fn record(emp: &Employee) -> EmployeeRecord {
+        EmployeeRecord::from_employee(emp).unwrap()
+    }
+    // one of each type
+    fn affiliations() -> Vec<Arc<RwLock<dyn Affiliation>>> {
+        let date = |m: u32, d: u32| NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+        let mut union = UnionAffiliation::new(7300, DuesPolicy::PerFriday { dues: 9.42 });
+        union.add_service_charge(date(8, 5), 12.5);
+        let mut resident_tax = ResidentTax::new();
+        resident_tax.set_schedule(2024, [18500.0; 12]);
+        let mut garnishments = Garnishments::new();
+        garnishments.add_order(GarnishmentOrder::new("C-1", 1, 0.25, 1000.0));
+        let mut advances = Advances::new();
+        advances.add_advance(Advance::new("A-1", date(8, 1), 500.0, 100.0).unwrap());
+        vec![
+            Arc::new(RwLock::new(union)),
+            Arc::new(RwLock::new(GeneralDeduction::new("Health", 30.0))),
+            Arc::new(RwLock::new(IncomeTaxWithholding::new(
+                SharedTable::<TaxTable>::open("../data/income_tax.tbl").unwrap(),
+                1,
+            ))),
+            Arc::new(RwLock::new(MonthlyWithholding::new(
+                SharedTable::<MonthlyWithholdingTable>::open("../data/jp_monthly_withholding.csv")
+                    .unwrap(),
+                WithholdingColumn::Kou,
+                2,
+            ))),
+            Arc::new(RwLock::new(SocialInsurance::new(
+                SharedTable::<SocialInsuranceTable>::open("../data/jp_social_insurance.tbl")
+                    .unwrap(),
+                "Tokyo",
+                24,
+            ))),
+            Arc::new(RwLock::new(resident_tax)),
+            Arc::new(RwLock::new(RetirementContribution::new(
+                RetirementElection {
+                    rate: 0.06,
+                    annual_cap: 23000.0,
+                    match_rate: 0.5,
+                    match_limit: 0.04,
+                    employer_annual_cap: 10000.0,
+                },
+            ))),
+            Arc::new(RwLock::new(garnishments)),
+            Arc::new(RwLock::new(advances)),
+            Arc::new(RwLock::new(YearEndSettlements::new())),
+        ]
+    }
+
+    #[test]
+    fn test_migrate() {
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 4);
+        // applied already
+        migrate(&mut conn).unwrap();
+        assert_eq!(schema_version(&conn).unwrap(), 4);
+    }
+    #[test]
+    fn test_insert_fetch() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        let emp = hourly_emp(57);
+        assert_eq!(db.insert(emp.clone()).run(&mut tx), Ok(57));
+        assert!(db.insert(emp.clone()).run(&mut tx).is_err());
+        let fetched = db.fetch(57).run(&mut tx).unwrap();
+        assert_eq!(record(&fetched), record(&emp));
+        assert!(db.fetch(42).run(&mut tx).is_err());
+    }
+    #[test]
+    fn test_unreadable_data() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        tx.execute(
+            "UPDATE classifications SET data = '{' WHERE emp_id = 57",
+            [],
+        )
+        .unwrap();
+        let err = db.fetch(57).run(&mut tx).unwrap_err();
+        assert!(matches!(err, DaoError::UnsupportedData(_)));
+        // the error of the format is kept as the source
+        let source = std::error::Error::source(&err).unwrap();
+        assert!(source.to_string().contains("EOF"));
+    }
+    #[test]
+    fn test_affiliation_records() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        let mut types = vec![];
+        for (i, affiliation) in affiliations().into_iter().enumerate() {
+            let mut emp = hourly_emp(100 + i as EmployeeId);
+            emp.remove_affiliation(UnionAffiliation::NAME);
+            emp.add_affiliation(affiliation);
+            db.insert(emp.clone()).run(&mut tx).unwrap();
+            let fetched = db.fetch(emp.get_emp_id()).run(&mut tx).unwrap();
+            assert_eq!(record(&fetched), record(&emp));
+            types.extend(
+                record(&emp)
+                    .affiliations
+                    .iter()
+                    .map(|a| a.get_type().to_string()),
+            );
+        }
+        types.sort();
+        types.dedup();
+        assert_eq!(types.len(), 10);
+    }
+    #[test]
+    fn test_legacy_maps() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        let affiliations = affiliations();
+        let emp = Employee::new(
+            42,
+            "Hanako",
+            "Tokyo",
+            Arc::new(RwLock::new(HourlyClassification::new(1500.0))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(MailMethod::new("Tokyo"))),
+            vec![affiliations[5].clone()],
+        );
+        db.insert(emp.clone()).run(&mut tx).unwrap();
+        // as stored before the maps were stored as pairs, with the keys as strings
+        let to_map = |pairs: &serde_json::Value| {
+            serde_json::Value::Object(
+                pairs
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|p| (p[0].to_string(), p[1].clone()))
+                    .collect(),
+            )
+        };
+        let data: String = tx
+            .query_row(
+                "SELECT data FROM affiliations WHERE emp_id = 42 AND position = 0",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        let mut data: serde_json::Value = serde_json::from_str(&data).unwrap();
+        let map = to_map(data.pointer("/schedules").unwrap());
+        *data.pointer_mut("/schedules").unwrap() = map;
+        tx.execute(
+            "UPDATE affiliations SET data = ?1 WHERE emp_id = 42 AND position = 0",
+            params![data.to_string()],
+        )
+        .unwrap();
+        let fetched = db.fetch(42).run(&mut tx).unwrap();
+        assert_eq!(record(&fetched), record(&emp));
+    }
+    #[test]
+    fn test_update_delete() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        let mut emp = hourly_emp(57);
+        emp.set_name("Robert");
+        emp.set_classification(Arc::new(RwLock::new(CompositeClassification::new(vec![
+            component_classification(&Component::Salaried { salary: 1800.0 }),
+            component_classification(&Component::Hourly { hourly_rate: 12.5 }),
+        ]))));
+        emp.remove_affiliation(UnionAffiliation::NAME);
+        db.update(emp.clone()).run(&mut tx).unwrap();
+        let fetched = db.fetch(57).run(&mut tx).unwrap();
+        emp.set_version(1);
+        assert_eq!(record(&fetched), record(&emp));
+        assert_eq!(db.get_all().run(&mut tx).unwrap().len(), 1);
+
+        db.delete(57).run(&mut tx).unwrap();
+        assert!(db.fetch(57).run(&mut tx).is_err());
+        assert!(db.delete(57).run(&mut tx).is_err());
+        assert!(db.update(emp).run(&mut tx).is_err());
+    }
+    #[test]
+    fn test_version_conflict() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        let mut emp1 = db.fetch(57).run(&mut tx).unwrap();
+        let mut emp2 = db.fetch(57).run(&mut tx).unwrap();
+        emp1.set_name("Robert");
+        db.update(emp1).run(&mut tx).unwrap();
+        emp2.set_address("Office");
+        assert!(matches!(
+            db.update(emp2).run(&mut tx),
+            Err(DaoError::VersionConflict { .. })
+        ));
+
+        let emp = db.fetch(57).run(&mut tx).unwrap();
+        assert_eq!(emp.get_version(), 1);
+        assert_eq!(emp.get_name(), "Robert");
+        assert_eq!(emp.get_address(), "Home");
+    }
+    #[test]
+    fn test_commit_rollback() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        tx.commit().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(58)).run(&mut tx).unwrap();
+        tx.rollback().unwrap();
+
+        let mut tx = conn.transaction().unwrap();
+        let emp_ids = db
+            .get_all()
+            .run(&mut tx)
+            .unwrap()
+            .iter()
+            .map(|e| e.get_emp_id())
+            .collect::<Vec<_>>();
+        assert_eq!(emp_ids, vec![57]);
+    }
+    #[test]
+    fn test_store() {
+        let store = SqliteStore::open(":memory:").unwrap();
+        store
+            .with_transaction(|tx| store.dao().insert(hourly_emp(57)).run(tx))
+            .unwrap();
+        // rolled back as it fails, after the insert
+        let result = store.with_transaction(|tx| {
+            store.dao().insert(hourly_emp(58)).run(tx)?;
+            store.dao().insert(hourly_emp(57)).run(tx)
+        });
+        assert_eq!(result, Err(DaoError::EmployeeAlreadyExists(57)));
+
+        let emp_ids = store
+            .with_transaction(|tx| store.dao().get_all().run(tx))
+            .unwrap()
+            .iter()
+            .map(|e| e.get_emp_id())
+            .collect::<Vec<_>>();
+        assert_eq!(emp_ids, vec![57]);
+    }
+    #[test]
+    fn test_union_member() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        assert_eq!(db.find_union_member(7237).run(&mut tx), Ok(57));
+        // held by another employee
+        let emp = hourly_emp(42);
+        set_member_id(&emp, 7237);
+        assert_eq!(
+            db.insert(emp).run(&mut tx),
+            Err(DaoError::UnionMemberAlreadyExists(7237))
+        );
+        db.insert(hourly_emp(42)).run(&mut tx).unwrap();
+        let emp = db.fetch(42).run(&mut tx).unwrap();
+        set_member_id(&emp, 7237);
+        assert_eq!(
+            db.update(emp).run(&mut tx),
+            Err(DaoError::UnionMemberAlreadyExists(7237))
+        );
+        assert_eq!(db.find_union_member(7222).run(&mut tx), Ok(42));
+        // follows the member id of the affiliation
+        let emp = db.fetch(57).run(&mut tx).unwrap();
+        set_member_id(&emp, 7234);
+        db.update(emp).run(&mut tx).unwrap();
+        assert!(db.find_union_member(7237).run(&mut tx).is_err());
+        assert_eq!(db.find_union_member(7234).run(&mut tx), Ok(57));
+        // released on delete
+        db.delete(57).run(&mut tx).unwrap();
+        assert!(db.find_union_member(7234).run(&mut tx).is_err());
+    }
+    #[test]
+    fn test_find_employees() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        db.insert(hourly_emp(57)).run(&mut tx).unwrap();
+        let mut emp = hourly_emp(58);
+        emp.set_name("Carol");
+        emp.remove_affiliation(UnionAffiliation::NAME);
+        emp.set_classification(Arc::new(RwLock::new(CompositeClassification::new(vec![
+            component_classification(&Component::Salaried { salary: 1800.0 }),
+        ]))));
+        db.insert(emp).run(&mut tx).unwrap();
+
+        let mut find = |query: EmployeeQuery| {
+            db.find_employees(query)
+                .run(&mut tx)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_emp_id())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(find(EmployeeQuery::default()), vec![57, 58]);
+        let query = EmployeeQuery {
+            classification: Some(ClassificationKind::Composite),
+            ..Default::default()
+        };
+        assert_eq!(find(query), vec![58]);
+        let query = EmployeeQuery {
+            name: Some("ob".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(find(query), vec![57]);
+        let query = EmployeeQuery {
+            union_member: Some(false),
+            ..Default::default()
+        };
+        assert_eq!(find(query), vec![58]);
+        let query = EmployeeQuery {
+            schedule: Some(ScheduleKind::Weekly),
+            method: Some(MethodKind::Mail),
+            ..Default::default()
+        };
+        assert_eq!(find(query), vec![57, 58]);
+        let query = EmployeeQuery {
+            schedule: Some(ScheduleKind::Monthly),
+            ..Default::default()
+        };
+        assert_eq!(find(query), Vec::<EmployeeId>::new());
+    }
+    #[test]
+    fn test_get_payable() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        for emp_id in [57, 58, 59] {
+            db.insert(hourly_emp(emp_id)).run(&mut tx).unwrap();
+        }
+        let mut emp = db.fetch(58).run(&mut tx).unwrap();
+        emp.set_schedule(Arc::new(RwLock::new(MonthlySchedule)));
+        db.update(emp).run(&mut tx).unwrap();
+
+        let mut payable = |date, after, limit| {
+            db.get_payable(date, after, limit)
+                .run(&mut tx)
+                .unwrap()
+                .iter()
+                .map(|e| e.get_emp_id())
+                .collect::<Vec<_>>()
+        };
+        let friday = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+        assert_eq!(payable(friday, None, 10), vec![57, 59]);
+        assert_eq!(payable(friday, None, 1), vec![57]);
+        assert_eq!(payable(friday, Some(57), 1), vec![59]);
+        assert_eq!(payable(friday, Some(59), 1), Vec::<EmployeeId>::new());
+        let month_end = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+        assert_eq!(payable(month_end, None, 10), vec![58]);
+        let monday = NaiveDate::from_ymd_opt(2024, 8, 5).unwrap();
+        assert_eq!(payable(monday, None, 10), Vec::<EmployeeId>::new());
+    }
+    #[test]
+    fn test_paychecks() {
+        let db = SqliteDb::new();
+        let mut conn = SqliteDb::open_in_memory().unwrap();
+        let mut tx = conn.transaction().unwrap();
+        let period = |d: u32| {
+            NaiveDate::from_ymd_opt(2024, 8, d).unwrap()
+                ..=NaiveDate::from_ymd_opt(2024, 8, d + 6).unwrap()
+        };
+        let mut pc = Paycheck::new(period(3));
+        pc.set_gross_pay(289.75);
+        pc.add_deduction_line(DeductionLine::new(
+            DeductionKind::General,
+            "Union dues",
+            9.03,
+        ));
+        pc.set_deductions(9.03);
+        pc.set_net_pay(280.72);
+        db.record_paycheck(57, Paycheck::new(period(10)))
+            .run(&mut tx)
+            .unwrap();
+        db.record_paycheck(57, pc.clone()).run(&mut tx).unwrap();
+        assert_eq!(
+            db.get_paychecks(57).run(&mut tx),
+            Ok(vec![Paycheck::new(period(10)), pc.clone()])
+        );
+        assert_eq!(db.get_paychecks(42).run(&mut tx), Ok(vec![]));
+        assert_eq!(db.has_paycheck(57, period(3)).run(&mut tx), Ok(true));
+        assert_eq!(db.has_paycheck(57, period(4)).run(&mut tx), Ok(false));
+        assert_eq!(db.has_paycheck(42, period(3)).run(&mut tx), Ok(false));
+    }
+}
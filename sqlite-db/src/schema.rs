@@ -0,0 +1,89 @@
+use rusqlite::Connection;
+
+// applied in order, the schema version is the number of migrations applied
+const MIGRATIONS: &[&str] = &[
+    // 1: employees and their parts, union members and paychecks
+    r#"
+    CREATE TABLE employees (
+        emp_id        INTEGER PRIMARY KEY,
+        name          TEXT    NOT NULL,
+        address       TEXT    NOT NULL,
+        net_pay_floor REAL    NOT NULL DEFAULT 0.0,
+        arrears       TEXT    NOT NULL DEFAULT '[]'
+    );
+    CREATE TABLE classifications (
+        emp_id INTEGER PRIMARY KEY REFERENCES employees (emp_id) ON DELETE CASCADE,
+        type   TEXT    NOT NULL,
+        data   TEXT    NOT NULL
+    );
+    CREATE TABLE schedules (
+        emp_id INTEGER PRIMARY KEY REFERENCES employees (emp_id) ON DELETE CASCADE,
+        type   TEXT    NOT NULL,
+        data   TEXT    NOT NULL
+    );
+    CREATE TABLE methods (
+        emp_id INTEGER PRIMARY KEY REFERENCES employees (emp_id) ON DELETE CASCADE,
+        type   TEXT    NOT NULL,
+        data   TEXT    NOT NULL
+    );
+    CREATE TABLE affiliations (
+        emp_id   INTEGER NOT NULL REFERENCES employees (emp_id) ON DELETE CASCADE,
+        position INTEGER NOT NULL,
+        type     TEXT    NOT NULL,
+        data     TEXT    NOT NULL,
+        PRIMARY KEY (emp_id, position)
+    );
+    CREATE TABLE union_members (
+        member_id INTEGER PRIMARY KEY,
+        emp_id    INTEGER NOT NULL UNIQUE
+    );
+    CREATE TABLE paychecks (
+        paycheck_id  INTEGER PRIMARY KEY AUTOINCREMENT,
+        emp_id       INTEGER NOT NULL,
+        period_start TEXT    NOT NULL,
+        period_end   TEXT    NOT NULL,
+        gross_pay    REAL    NOT NULL,
+        deductions   REAL    NOT NULL,
+        net_pay      REAL    NOT NULL,
+        data         TEXT    NOT NULL
+    );
+    CREATE INDEX paychecks_emp_id ON paychecks (emp_id, period_end);
+    "#,
+    // 2: versions of employees for optimistic concurrency control
+    r#"
+    ALTER TABLE employees ADD COLUMN version INTEGER NOT NULL DEFAULT 0;
+    "#,
+    // 3: the employees by the schedule they are paid on, for payday
+    r#"
+    CREATE INDEX schedules_type ON schedules (type, emp_id);
+    "#,
+    // 4: union members follow the union affiliation of the employees and go with them
+    r#"
+    CREATE TABLE union_members_new (
+        member_id INTEGER PRIMARY KEY,
+        emp_id    INTEGER NOT NULL UNIQUE REFERENCES employees (emp_id) ON DELETE CASCADE
+    );
+    INSERT OR IGNORE INTO union_members_new (member_id, emp_id)
+        SELECT json_extract(data, '$.member_id'), emp_id FROM affiliations WHERE type = 'Union';
+    DROP TABLE union_members;
+    ALTER TABLE union_members_new RENAME TO union_members;
+    "#,
+];
+
+pub fn schema_version(conn: &Connection) -> rusqlite::Result<usize> {
+    conn.pragma_query_value(None, "user_version", |row| row.get(0))
+}
+
+// brings the schema up to date, migrations already applied are skipped
+pub fn migrate(conn: &mut Connection) -> rusqlite::Result<()> {
+    let version = schema_version(conn)?;
+    if version >= MIGRATIONS.len() {
+        return Ok(());
+    }
+    let tx = conn.transaction()?;
+    for migration in MIGRATIONS.iter().skip(version) {
+        tx.execute_batch(migration)?;
+    }
+    tx.pragma_update(None, "user_version", MIGRATIONS.len())?;
+    tx.commit()
+}
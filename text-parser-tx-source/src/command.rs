@@ -1,6 +1,10 @@
 use chrono::NaiveDate;
 
-use payroll_domain::{EmployeeId, MemberId};
+use dao::EmployeeQuery;
+use payroll_domain::{
+    Component, DuesPolicy, EmployeeId, MemberId, RetirementElection, ServiceChargeId,
+    WithholdingColumn,
+};
 use tx_app::Transaction;
 use tx_factory::TransactionFactory;
 
@@ -25,9 +29,18 @@ pub enum Command {
         salary: f32,
         commission_rate: f32,
     },
+    AddCompositeEmp {
+        emp_id: EmployeeId,
+        name: String,
+        address: String,
+        components: Vec<Component>,
+    },
     DelEmp {
         emp_id: EmployeeId,
     },
+    ListEmp {
+        query: EmployeeQuery,
+    },
     TimeCard {
         emp_id: EmployeeId,
         date: NaiveDate,
@@ -43,6 +56,11 @@ pub enum Command {
         date: NaiveDate,
         amount: f32,
     },
+    ReverseServiceCharge {
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+        date: NaiveDate,
+    },
     ChgName {
         emp_id: EmployeeId,
         name: String,
@@ -51,6 +69,10 @@ pub enum Command {
         emp_id: EmployeeId,
         address: String,
     },
+    ChgNetPayFloor {
+        emp_id: EmployeeId,
+        net_pay_floor: f32,
+    },
     ChgHourly {
         emp_id: EmployeeId,
         hourly_rate: f32,
@@ -64,6 +86,10 @@ pub enum Command {
         salary: f32,
         commission_rate: f32,
     },
+    ChgComposite {
+        emp_id: EmployeeId,
+        components: Vec<Component>,
+    },
     ChgHold {
         emp_id: EmployeeId,
     },
@@ -79,14 +105,88 @@ pub enum Command {
     ChgMember {
         emp_id: EmployeeId,
         member_id: MemberId,
-        dues: f32,
+        dues: DuesPolicy,
     },
     ChgNoMember {
         emp_id: EmployeeId,
     },
+    ChgDues {
+        emp_id: EmployeeId,
+        dues: DuesPolicy,
+        effective: Option<NaiveDate>,
+    },
+    ChgMemberId {
+        emp_id: EmployeeId,
+        member_id: MemberId,
+    },
+    ChgDeduction {
+        emp_id: EmployeeId,
+        name: String,
+        amount: f32,
+    },
+    ChgNoDeduction {
+        emp_id: EmployeeId,
+        name: String,
+    },
+    ChgIncomeTax {
+        emp_id: EmployeeId,
+        table_path: String,
+        allowances: u32,
+    },
+    ChgMonthlyTax {
+        emp_id: EmployeeId,
+        table_path: String,
+        column: WithholdingColumn,
+        dependents: u32,
+    },
+    ChgTaxColumn {
+        emp_id: EmployeeId,
+        column: WithholdingColumn,
+        dependents: u32,
+    },
+    ChgSocialInsurance {
+        emp_id: EmployeeId,
+        table_path: String,
+        prefecture: String,
+        grade: u32,
+    },
+    ChgGrade {
+        emp_id: EmployeeId,
+        grade: u32,
+    },
+    ImportResidentTax {
+        notices_path: String,
+    },
+    ChgRetirement {
+        emp_id: EmployeeId,
+        election: RetirementElection,
+    },
+    AddGarnishment {
+        emp_id: EmployeeId,
+        case_id: String,
+        priority: u32,
+        limit_rate: f32,
+        total: f32,
+    },
+    ReleaseGarnishment {
+        emp_id: EmployeeId,
+        case_id: String,
+    },
+    Advance {
+        emp_id: EmployeeId,
+        advance_id: String,
+        date: NaiveDate,
+        principal: f32,
+        installment: f32,
+    },
     Payday {
         pay_date: NaiveDate,
     },
+    YearEndAdjustment {
+        year: i32,
+        table_path: String,
+        declarations_path: String,
+    },
 }
 impl Command {
     pub fn convert<Ctx>(
@@ -119,7 +219,14 @@ impl Command {
                 salary,
                 commission_rate,
             ),
+            Command::AddCompositeEmp {
+                emp_id,
+                name,
+                address,
+                components,
+            } => tx_factory.mk_add_composite_employee_tx(emp_id, name, address, components),
             Command::DelEmp { emp_id } => tx_factory.mk_delete_employee_tx(emp_id),
+            Command::ListEmp { query } => tx_factory.mk_list_employees_tx(query),
             Command::TimeCard {
                 emp_id,
                 date,
@@ -135,10 +242,19 @@ impl Command {
                 date,
                 amount,
             } => tx_factory.mk_service_charge_tx(member_id, date, amount),
+            Command::ReverseServiceCharge {
+                member_id,
+                charge_id,
+                date,
+            } => tx_factory.mk_reverse_service_charge_tx(member_id, charge_id, date),
             Command::ChgName { emp_id, name } => tx_factory.mk_change_name_tx(emp_id, name),
             Command::ChgAddress { emp_id, address } => {
                 tx_factory.mk_change_address_tx(emp_id, address)
             }
+            Command::ChgNetPayFloor {
+                emp_id,
+                net_pay_floor,
+            } => tx_factory.mk_change_net_pay_floor_tx(emp_id, net_pay_floor),
             Command::ChgSalaried { emp_id, salary } => {
                 tx_factory.mk_change_salaried_tx(emp_id, salary)
             }
@@ -151,6 +267,9 @@ impl Command {
                 salary,
                 commission_rate,
             } => tx_factory.mk_change_commissioned_tx(emp_id, salary, commission_rate),
+            Command::ChgComposite { emp_id, components } => {
+                tx_factory.mk_change_composite_tx(emp_id, components)
+            }
             Command::ChgHold { emp_id } => tx_factory.mk_change_hold_tx(emp_id),
             Command::ChgDirect {
                 emp_id,
@@ -164,7 +283,74 @@ impl Command {
                 dues,
             } => tx_factory.mk_change_union_member_tx(emp_id, member_id, dues),
             Command::ChgNoMember { emp_id } => tx_factory.mk_change_unaffiliated_tx(emp_id),
+            Command::ChgDues {
+                emp_id,
+                dues,
+                effective,
+            } => tx_factory.mk_change_union_dues_tx(emp_id, dues, effective),
+            Command::ChgMemberId { emp_id, member_id } => {
+                tx_factory.mk_change_member_id_tx(emp_id, member_id)
+            }
+            Command::ChgDeduction {
+                emp_id,
+                name,
+                amount,
+            } => tx_factory.mk_change_deduction_tx(emp_id, name, amount),
+            Command::ChgNoDeduction { emp_id, name } => {
+                tx_factory.mk_change_no_deduction_tx(emp_id, name)
+            }
+            Command::ChgIncomeTax {
+                emp_id,
+                table_path,
+                allowances,
+            } => tx_factory.mk_change_income_tax_tx(emp_id, table_path, allowances),
+            Command::ChgMonthlyTax {
+                emp_id,
+                table_path,
+                column,
+                dependents,
+            } => tx_factory.mk_change_monthly_tax_tx(emp_id, table_path, column, dependents),
+            Command::ChgTaxColumn {
+                emp_id,
+                column,
+                dependents,
+            } => tx_factory.mk_change_tax_column_tx(emp_id, column, dependents),
+            Command::ChgSocialInsurance {
+                emp_id,
+                table_path,
+                prefecture,
+                grade,
+            } => tx_factory.mk_change_social_insurance_tx(emp_id, table_path, prefecture, grade),
+            Command::ChgGrade { emp_id, grade } => tx_factory.mk_change_grade_tx(emp_id, grade),
+            Command::ImportResidentTax { notices_path } => {
+                tx_factory.mk_import_resident_tax_tx(notices_path)
+            }
+            Command::ChgRetirement { emp_id, election } => {
+                tx_factory.mk_change_retirement_tx(emp_id, election)
+            }
+            Command::AddGarnishment {
+                emp_id,
+                case_id,
+                priority,
+                limit_rate,
+                total,
+            } => tx_factory.mk_add_garnishment_tx(emp_id, case_id, priority, limit_rate, total),
+            Command::ReleaseGarnishment { emp_id, case_id } => {
+                tx_factory.mk_release_garnishment_tx(emp_id, case_id)
+            }
+            Command::Advance {
+                emp_id,
+                advance_id,
+                date,
+                principal,
+                installment,
+            } => tx_factory.mk_advance_tx(emp_id, advance_id, date, principal, installment),
             Command::Payday { pay_date } => tx_factory.mk_payday_tx(pay_date),
+            Command::YearEndAdjustment {
+                year,
+                table_path,
+                declarations_path,
+            } => tx_factory.mk_year_end_adjustment_tx(year, table_path, declarations_path),
         }
     }
 }
@@ -3,6 +3,8 @@ use parsec_rs::{char, float32, int32, keyword, pred, spaces, string, uint32, Par
 use std::collections::VecDeque;
 
 use crate::command::Command;
+use dao::{ClassificationKind, EmployeeQuery, MethodKind, ScheduleKind};
+use payroll_domain::{Component, DuesPolicy, RetirementElection, WithholdingColumn};
 
 pub fn read_commands(script: &str) -> VecDeque<Command> {
     let commands: VecDeque<Command> = transactions()
@@ -21,21 +23,41 @@ pub fn transaction() -> impl Parser<Item = Command> {
         add_salary_emp()
             .or(add_hourly_emp())
             .or(add_commissioned_emp())
+            .or(add_composite_emp())
             .or(del_emp())
+            .or(list_emp())
             .or(time_card())
             .or(sales_receipt())
             .or(service_charge())
+            .or(reverse_service_charge())
             .or(chg_name())
             .or(chg_address())
+            .or(chg_net_pay_floor())
             .or(chg_hourly())
             .or(chg_salaried())
             .or(chg_commissioned())
+            .or(chg_composite())
             .or(chg_hold())
             .or(chg_direct())
             .or(chg_mail())
             .or(chg_member())
             .or(chg_no_member())
-            .or(payday()),
+            .or(chg_dues())
+            .or(chg_member_id())
+            .or(chg_deduction())
+            .or(chg_no_deduction())
+            .or(chg_income_tax())
+            .or(chg_monthly_tax())
+            .or(chg_tax_column())
+            .or(chg_social_insurance())
+            .or(chg_grade())
+            .or(import_resident_tax())
+            .or(chg_retirement())
+            .or(add_garnishment())
+            .or(release_garnishment())
+            .or(advance())
+            .or(payday())
+            .or(year_end_adjustment()),
     )
 }
 #[cfg(test)]
@@ -123,6 +145,30 @@ mod test_transaction {
         );
     }
     #[test]
+    fn test_add_composite_emp() {
+        let input = r#"AddEmp 42 "Bob" "Home" M S 1000.0 H 15.25 C 0.0 0.1"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::AddCompositeEmp {
+                    emp_id: 42,
+                    name: "Bob".to_string(),
+                    address: "Home".to_string(),
+                    components: vec![
+                        Component::Salaried { salary: 1000.0 },
+                        Component::Hourly { hourly_rate: 15.25 },
+                        Component::Commissioned {
+                            salary: 0.0,
+                            commission_rate: 0.1
+                        },
+                    ]
+                },
+                ""
+            ))
+        );
+    }
+    #[test]
     fn test_del_emp() {
         let input = r#"DelEmp 42"#;
         let result = transaction().parse(input);
@@ -177,6 +223,22 @@ mod test_transaction {
         );
     }
     #[test]
+    fn test_reverse_service_charge() {
+        let input = r#"ReverseServiceCharge 42 3 2021-01-08"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ReverseServiceCharge {
+                    member_id: 42,
+                    charge_id: 3,
+                    date: NaiveDate::from_ymd_opt(2021, 1, 8).unwrap(),
+                },
+                ""
+            ))
+        );
+    }
+    #[test]
     fn test_chg_name() {
         let input = r#"ChgEmp 42 Name "Bob""#;
         let result = transaction().parse(input);
@@ -207,6 +269,21 @@ mod test_transaction {
         );
     }
     #[test]
+    fn test_chg_net_pay_floor() {
+        let input = r#"ChgEmp 42 NetPayFloor 100.0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgNetPayFloor {
+                    emp_id: 42,
+                    net_pay_floor: 100.0
+                },
+                ""
+            ))
+        );
+    }
+    #[test]
     fn test_chg_hourly() {
         let input = r#"ChgEmp 42 Hourly 1000.0"#;
         let result = transaction().parse(input);
@@ -253,6 +330,24 @@ mod test_transaction {
         );
     }
     #[test]
+    fn test_chg_composite() {
+        let input = r#"ChgEmp 42 Composite S 1000.0 H 15.25"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgComposite {
+                    emp_id: 42,
+                    components: vec![
+                        Component::Salaried { salary: 1000.0 },
+                        Component::Hourly { hourly_rate: 15.25 },
+                    ]
+                },
+                ""
+            ))
+        );
+    }
+    #[test]
     fn test_chg_hold() {
         let input = r#"ChgEmp 42 Hold"#;
         let result = transaction().parse(input);
@@ -299,7 +394,26 @@ mod test_transaction {
                 Command::ChgMember {
                     emp_id: 42,
                     member_id: 7234,
-                    dues: 9.45,
+                    dues: DuesPolicy::PerFriday { dues: 9.45 },
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_member_percent_of_gross() {
+        let input = r#"ChgEmp 42 Member 7234 Dues 1.5% Cap 30.0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgMember {
+                    emp_id: 42,
+                    member_id: 7234,
+                    dues: DuesPolicy::PercentOfGross {
+                        rate: 0.015,
+                        cap: 30.0
+                    },
                 },
                 "",
             ))
@@ -311,6 +425,251 @@ mod test_transaction {
         let result = transaction().parse(input);
         assert_eq!(result, Ok((Command::ChgNoMember { emp_id: 42 }, "")));
     }
+    #[test]
+    fn test_chg_dues() {
+        let input = r#"ChgEmp 42 Dues 10.0 PerPeriod From 2024-09-01"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgDues {
+                    emp_id: 42,
+                    dues: DuesPolicy::PerPayPeriod { dues: 10.0 },
+                    effective: NaiveDate::from_ymd_opt(2024, 9, 1),
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_member_id() {
+        let input = r#"ChgEmp 42 MemberId 8234"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgMemberId {
+                    emp_id: 42,
+                    member_id: 8234,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_deduction() {
+        let input = r#"ChgEmp 42 Deduction "Health" 25.5"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgDeduction {
+                    emp_id: 42,
+                    name: "Health".to_string(),
+                    amount: 25.5,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_no_deduction() {
+        let input = r#"ChgEmp 42 NoDeduction "Health""#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgNoDeduction {
+                    emp_id: 42,
+                    name: "Health".to_string(),
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_income_tax() {
+        let input = r#"ChgEmp 42 IncomeTax "data/income_tax.tbl" Allowances 2"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgIncomeTax {
+                    emp_id: 42,
+                    table_path: "data/income_tax.tbl".to_string(),
+                    allowances: 2,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_monthly_tax() {
+        let input = r#"ChgEmp 42 MonthlyTax "data/jp_monthly_withholding.csv" Kou Dependents 2"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgMonthlyTax {
+                    emp_id: 42,
+                    table_path: "data/jp_monthly_withholding.csv".to_string(),
+                    column: WithholdingColumn::Kou,
+                    dependents: 2,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_tax_column() {
+        let input = r#"ChgEmp 42 MonthlyTax Otsu Dependents 0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgTaxColumn {
+                    emp_id: 42,
+                    column: WithholdingColumn::Otsu,
+                    dependents: 0,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_social_insurance() {
+        let input = r#"ChgEmp 42 SocialInsurance "data/jp_social_insurance.tbl" "Tokyo" Grade 22"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgSocialInsurance {
+                    emp_id: 42,
+                    table_path: "data/jp_social_insurance.tbl".to_string(),
+                    prefecture: "Tokyo".to_string(),
+                    grade: 22,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_grade() {
+        let input = r#"ChgEmp 42 Grade 23"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgGrade {
+                    emp_id: 42,
+                    grade: 23
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_import_resident_tax() {
+        let input = r#"ImportResidentTax "data/resident_tax_2024.csv""#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ImportResidentTax {
+                    notices_path: "data/resident_tax_2024.csv".to_string(),
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_chg_retirement() {
+        let input = r#"ChgEmp 42 Retirement 5.0% Cap 23000.0 Match 50.0% UpTo 6.0% Cap 10000.0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgRetirement {
+                    emp_id: 42,
+                    election: RetirementElection {
+                        rate: 0.05,
+                        annual_cap: 23000.0,
+                        match_rate: 0.5,
+                        match_limit: 0.06,
+                        employer_annual_cap: 10000.0,
+                    },
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_add_garnishment() {
+        let input = r#"ChgEmp 42 Garnishment "CV-2024-118" Priority 1 25.0% Total 500.0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::AddGarnishment {
+                    emp_id: 42,
+                    case_id: "CV-2024-118".to_string(),
+                    priority: 1,
+                    limit_rate: 0.25,
+                    total: 500.0,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_release_garnishment() {
+        let input = r#"ChgEmp 42 NoGarnishment "CV-2024-118""#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ReleaseGarnishment {
+                    emp_id: 42,
+                    case_id: "CV-2024-118".to_string(),
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_advance() {
+        let input = r#"Advance 42 "ADV-1" 2024-08-05 300.0 Installment 100.0"#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::Advance {
+                    emp_id: 42,
+                    advance_id: "ADV-1".to_string(),
+                    date: NaiveDate::from_ymd_opt(2024, 8, 5).unwrap(),
+                    principal: 300.0,
+                    installment: 100.0,
+                },
+                "",
+            ))
+        );
+    }
+    #[test]
+    fn test_year_end_adjustment() {
+        let input = r#"YearEndAdjustment 2024 "data/jp_year_end.tbl" "data/year_end_2024.csv""#;
+        let result = transaction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::YearEndAdjustment {
+                    year: 2024,
+                    table_path: "data/jp_year_end.tbl".to_string(),
+                    declarations_path: "data/year_end_2024.csv".to_string(),
+                },
+                "",
+            ))
+        );
+    }
 }
 
 fn go_through() -> impl Parser<Item = ()> {
@@ -460,27 +819,238 @@ mod test_add_commissioned_emp {
     }
 }
 
-fn del_emp() -> impl Parser<Item = Command> {
-    let prefix = keyword("DelEmp").skip(spaces());
-    let emp_id = uint32();
+fn add_composite_emp() -> impl Parser<Item = Command> {
+    let prefix = keyword("AddEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let name = string().with(spaces());
+    let address = string().with(spaces());
+    let components = char('M').skip(components());
 
-    prefix.skip(emp_id).map(|emp_id| Command::DelEmp { emp_id })
+    prefix
+        .skip(emp_id)
+        .join(name)
+        .join(address)
+        .join(components)
+        .map(
+            |(((emp_id, name), address), components)| Command::AddCompositeEmp {
+                emp_id,
+                name,
+                address,
+                components,
+            },
+        )
 }
 #[cfg(test)]
-mod test_del_emp {
+mod test_add_composite_emp {
     use super::*;
     use parsec_rs::Parser;
 
     #[test]
     fn test() {
-        let input = r#"DelEmp 1"#;
-        let result = del_emp().parse(input);
-        assert_eq!(result, Ok((Command::DelEmp { emp_id: 1 }, "")));
+        let input = r#"AddEmp 1 "Bob" "Home" M S 1000.0 C 0.0 0.1"#;
+        let result = add_composite_emp().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::AddCompositeEmp {
+                    emp_id: 1,
+                    name: "Bob".to_string(),
+                    address: "Home".to_string(),
+                    components: vec![
+                        Component::Salaried { salary: 1000.0 },
+                        Component::Commissioned {
+                            salary: 0.0,
+                            commission_rate: 0.1
+                        },
+                    ]
+                },
+                ""
+            ))
+        );
     }
 }
 
-fn date() -> impl Parser<Item = NaiveDate> {
-    let year = int32().with(char('-'));
+fn components() -> impl Parser<Item = Vec<Component>> {
+    let salaried = char('S')
+        .skip(spaces())
+        .skip(float32())
+        .map(|salary| Component::Salaried { salary });
+    let hourly = char('H')
+        .skip(spaces())
+        .skip(float32())
+        .map(|hourly_rate| Component::Hourly { hourly_rate });
+    let commissioned = char('C')
+        .skip(spaces())
+        .skip(float32())
+        .with(spaces())
+        .join(float32())
+        .map(|(salary, commission_rate)| Component::Commissioned {
+            salary,
+            commission_rate,
+        });
+
+    spaces().skip(salaried.or(hourly).or(commissioned)).many1()
+}
+#[cfg(test)]
+mod test_components {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#" H 15.25 C 100.0 0.2"#;
+        let result = components().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                vec![
+                    Component::Hourly { hourly_rate: 15.25 },
+                    Component::Commissioned {
+                        salary: 100.0,
+                        commission_rate: 0.2
+                    },
+                ],
+                ""
+            ))
+        );
+    }
+}
+
+fn del_emp() -> impl Parser<Item = Command> {
+    let prefix = keyword("DelEmp").skip(spaces());
+    let emp_id = uint32();
+
+    prefix.skip(emp_id).map(|emp_id| Command::DelEmp { emp_id })
+}
+#[cfg(test)]
+mod test_del_emp {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"DelEmp 1"#;
+        let result = del_emp().parse(input);
+        assert_eq!(result, Ok((Command::DelEmp { emp_id: 1 }, "")));
+    }
+}
+
+// a condition of the query, the same kind given twice is the last one
+#[derive(Debug, Clone, PartialEq)]
+enum QueryTerm {
+    Name(String),
+    Classification(ClassificationKind),
+    Schedule(ScheduleKind),
+    Method(MethodKind),
+    UnionMember(bool),
+}
+fn query_term() -> impl Parser<Item = QueryTerm> {
+    let name = keyword("Name")
+        .skip(spaces())
+        .skip(string())
+        .map(QueryTerm::Name);
+    let classification = keyword("Salaried")
+        .map(|_| ClassificationKind::Salaried)
+        .or(keyword("Hourly").map(|_| ClassificationKind::Hourly))
+        .or(keyword("Commissioned").map(|_| ClassificationKind::Commissioned))
+        .or(keyword("Composite").map(|_| ClassificationKind::Composite))
+        .map(QueryTerm::Classification);
+    let schedule = keyword("Monthly")
+        .map(|_| ScheduleKind::Monthly)
+        .or(keyword("Weekly").map(|_| ScheduleKind::Weekly))
+        .or(keyword("Biweekly").map(|_| ScheduleKind::Biweekly))
+        .map(QueryTerm::Schedule);
+    let method = keyword("Hold")
+        .map(|_| MethodKind::Hold)
+        .or(keyword("Direct").map(|_| MethodKind::Direct))
+        .or(keyword("Mail").map(|_| MethodKind::Mail))
+        .map(QueryTerm::Method);
+    let union_member = keyword("NoMember")
+        .map(|_| false)
+        .or(keyword("Member").map(|_| true))
+        .map(QueryTerm::UnionMember);
+
+    name.or(classification)
+        .or(schedule)
+        .or(method)
+        .or(union_member)
+}
+
+fn list_emp() -> impl Parser<Item = Command> {
+    let prefix = keyword("ListEmp");
+    let terms = spaces().skip(query_term()).many0();
+
+    prefix.skip(terms).map(|terms| {
+        let mut query = EmployeeQuery::default();
+        for term in terms {
+            match term {
+                QueryTerm::Name(name) => query.name = Some(name),
+                QueryTerm::Classification(kind) => query.classification = Some(kind),
+                QueryTerm::Schedule(kind) => query.schedule = Some(kind),
+                QueryTerm::Method(kind) => query.method = Some(kind),
+                QueryTerm::UnionMember(member) => query.union_member = Some(member),
+            }
+        }
+        Command::ListEmp { query }
+    })
+}
+#[cfg(test)]
+mod test_list_emp {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ListEmp"#;
+        let result = list_emp().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ListEmp {
+                    query: EmployeeQuery::default()
+                },
+                ""
+            ))
+        );
+
+        let input = r#"ListEmp Name "Bo" Hourly Weekly Mail Member"#;
+        let result = list_emp().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ListEmp {
+                    query: EmployeeQuery {
+                        name: Some("Bo".to_string()),
+                        classification: Some(ClassificationKind::Hourly),
+                        schedule: Some(ScheduleKind::Weekly),
+                        method: Some(MethodKind::Mail),
+                        union_member: Some(true),
+                    }
+                },
+                ""
+            ))
+        );
+
+        let input = r#"ListEmp NoMember Composite"#;
+        let result = list_emp().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ListEmp {
+                    query: EmployeeQuery {
+                        classification: Some(ClassificationKind::Composite),
+                        union_member: Some(false),
+                        ..Default::default()
+                    }
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn date() -> impl Parser<Item = NaiveDate> {
+    let year = int32().with(char('-'));
     let month = uint32().with(char('-'));
     let day = uint32();
 
@@ -621,6 +1191,47 @@ mod test_service_charge {
     }
 }
 
+fn reverse_service_charge() -> impl Parser<Item = Command> {
+    let prefix = keyword("ReverseServiceCharge").skip(spaces());
+    let member_id = uint32().with(spaces());
+    let charge_id = uint32().with(spaces());
+    let date = date();
+
+    prefix
+        .skip(member_id)
+        .join(charge_id)
+        .join(date)
+        .map(
+            |((member_id, charge_id), date)| Command::ReverseServiceCharge {
+                member_id,
+                charge_id,
+                date,
+            },
+        )
+}
+#[cfg(test)]
+mod test_reverse_service_charge {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ReverseServiceCharge 1 2 2021-01-08"#;
+        let result = reverse_service_charge().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ReverseServiceCharge {
+                    member_id: 1,
+                    charge_id: 2,
+                    date: NaiveDate::from_ymd_opt(2021, 1, 8).unwrap()
+                },
+                ""
+            ))
+        );
+    }
+}
+
 fn chg_name() -> impl Parser<Item = Command> {
     let prefix = keyword("ChgEmp").skip(spaces());
     let emp_id = uint32().with(spaces());
@@ -685,6 +1296,41 @@ mod test_chg_address {
     }
 }
 
+fn chg_net_pay_floor() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let net_pay_floor = keyword("NetPayFloor").skip(spaces()).skip(float32());
+
+    prefix
+        .skip(emp_id)
+        .join(net_pay_floor)
+        .map(|(emp_id, net_pay_floor)| Command::ChgNetPayFloor {
+            emp_id,
+            net_pay_floor,
+        })
+}
+#[cfg(test)]
+mod test_chg_net_pay_floor {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 NetPayFloor 50.0"#;
+        let result = chg_net_pay_floor().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgNetPayFloor {
+                    emp_id: 1,
+                    net_pay_floor: 50.0
+                },
+                ""
+            ))
+        );
+    }
+}
+
 fn chg_hourly() -> impl Parser<Item = Command> {
     let prefix = keyword("ChgEmp").skip(spaces());
     let emp_id = uint32().with(spaces());
@@ -792,6 +1438,41 @@ mod test_chg_commissioned {
     }
 }
 
+fn chg_composite() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let components = keyword("Composite").skip(components());
+
+    prefix
+        .skip(emp_id)
+        .join(components)
+        .map(|(emp_id, components)| Command::ChgComposite { emp_id, components })
+}
+#[cfg(test)]
+mod test_chg_composite {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 Composite S 1000.0 H 12.5"#;
+        let result = chg_composite().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgComposite {
+                    emp_id: 1,
+                    components: vec![
+                        Component::Salaried { salary: 1000.0 },
+                        Component::Hourly { hourly_rate: 12.5 },
+                    ]
+                },
+                ""
+            ))
+        );
+    }
+}
+
 fn chg_hold() -> impl Parser<Item = Command> {
     let prefix = keyword("ChgEmp").skip(spaces());
     let emp_id = uint32().with(spaces());
@@ -896,7 +1577,7 @@ fn chg_member() -> impl Parser<Item = Command> {
         .skip(spaces())
         .skip(uint32())
         .with(spaces());
-    let dues = keyword("Dues").skip(spaces()).skip(float32());
+    let dues = dues_policy();
 
     prefix
         .skip(emp_id)
@@ -923,7 +1604,66 @@ mod test_chg_member {
                 Command::ChgMember {
                     emp_id: 1,
                     member_id: 2,
-                    dues: 100.0
+                    dues: DuesPolicy::PerFriday { dues: 100.0 }
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn dues_policy() -> impl Parser<Item = DuesPolicy> {
+    let dues = || keyword("Dues").skip(spaces()).skip(float32());
+    let percent_of_gross = dues()
+        .with(char('%'))
+        .with(spaces())
+        .join(keyword("Cap").skip(spaces()).skip(float32()))
+        .map(|(percent, cap)| DuesPolicy::PercentOfGross {
+            rate: percent / 100.0,
+            cap,
+        });
+    let per_pay_period = dues()
+        .with(spaces())
+        .with(keyword("PerPeriod"))
+        .map(|dues| DuesPolicy::PerPayPeriod { dues });
+    let per_month = dues()
+        .with(spaces())
+        .with(keyword("PerMonth"))
+        .map(|dues| DuesPolicy::PerMonth { dues });
+    let per_friday = dues().map(|dues| DuesPolicy::PerFriday { dues });
+
+    percent_of_gross
+        .or(per_pay_period)
+        .or(per_month)
+        .or(per_friday)
+}
+#[cfg(test)]
+mod test_dues_policy {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"Dues 9.45"#;
+        let result = dues_policy().parse(input);
+        assert_eq!(result, Ok((DuesPolicy::PerFriday { dues: 9.45 }, "")));
+
+        let input = r#"Dues 20.0 PerPeriod"#;
+        let result = dues_policy().parse(input);
+        assert_eq!(result, Ok((DuesPolicy::PerPayPeriod { dues: 20.0 }, "")));
+
+        let input = r#"Dues 40.0 PerMonth"#;
+        let result = dues_policy().parse(input);
+        assert_eq!(result, Ok((DuesPolicy::PerMonth { dues: 40.0 }, "")));
+
+        let input = r#"Dues 2.0% Cap 50.0"#;
+        let result = dues_policy().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                DuesPolicy::PercentOfGross {
+                    rate: 0.02,
+                    cap: 50.0
                 },
                 ""
             ))
@@ -954,28 +1694,688 @@ mod test_chg_no_member {
     }
 }
 
-fn payday() -> impl Parser<Item = Command> {
-    let prefix = keyword("Payday").skip(spaces());
-    let date = date();
+fn chg_dues() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let dated = dues_policy()
+        .with(spaces())
+        .join(keyword("From").skip(spaces()).skip(date()))
+        .map(|(dues, effective)| (dues, Some(effective)));
+    let undated = dues_policy().map(|dues| (dues, None));
 
     prefix
-        .skip(date)
-        .map(|pay_date| Command::Payday { pay_date })
+        .skip(emp_id)
+        .join(dated.or(undated))
+        .map(|(emp_id, (dues, effective))| Command::ChgDues {
+            emp_id,
+            dues,
+            effective,
+        })
 }
 #[cfg(test)]
-mod test_payday {
+mod test_chg_dues {
     use super::*;
     use parsec_rs::Parser;
 
     #[test]
     fn test() {
-        let input = r#"Payday 2021-01-01"#;
-        let result = payday().parse(input);
+        let input = r#"ChgEmp 1 Dues 12.5"#;
+        let result = chg_dues().parse(input);
         assert_eq!(
             result,
             Ok((
-                Command::Payday {
-                    pay_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()
+                Command::ChgDues {
+                    emp_id: 1,
+                    dues: DuesPolicy::PerFriday { dues: 12.5 },
+                    effective: None
+                },
+                ""
+            ))
+        );
+
+        let input = r#"ChgEmp 1 Dues 12.5 From 2024-09-01"#;
+        let result = chg_dues().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgDues {
+                    emp_id: 1,
+                    dues: DuesPolicy::PerFriday { dues: 12.5 },
+                    effective: NaiveDate::from_ymd_opt(2024, 9, 1)
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_member_id() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let member_id = keyword("MemberId").skip(spaces()).skip(uint32());
+
+    prefix
+        .skip(emp_id)
+        .join(member_id)
+        .map(|(emp_id, member_id)| Command::ChgMemberId { emp_id, member_id })
+}
+#[cfg(test)]
+mod test_chg_member_id {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 MemberId 2"#;
+        let result = chg_member_id().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgMemberId {
+                    emp_id: 1,
+                    member_id: 2
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_deduction() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let name = keyword("Deduction")
+        .skip(spaces())
+        .skip(string())
+        .with(spaces());
+    let amount = float32();
+
+    prefix
+        .skip(emp_id)
+        .join(name)
+        .join(amount)
+        .map(|((emp_id, name), amount)| Command::ChgDeduction {
+            emp_id,
+            name,
+            amount,
+        })
+}
+#[cfg(test)]
+mod test_chg_deduction {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 Deduction "Loan" 50.0"#;
+        let result = chg_deduction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgDeduction {
+                    emp_id: 1,
+                    name: "Loan".to_string(),
+                    amount: 50.0
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_no_deduction() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let name = keyword("NoDeduction").skip(spaces()).skip(string());
+
+    prefix
+        .skip(emp_id)
+        .join(name)
+        .map(|(emp_id, name)| Command::ChgNoDeduction { emp_id, name })
+}
+#[cfg(test)]
+mod test_chg_no_deduction {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 NoDeduction "Loan""#;
+        let result = chg_no_deduction().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgNoDeduction {
+                    emp_id: 1,
+                    name: "Loan".to_string()
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_income_tax() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let table_path = keyword("IncomeTax")
+        .skip(spaces())
+        .skip(string())
+        .with(spaces());
+    let allowances = keyword("Allowances").skip(spaces()).skip(uint32());
+
+    prefix.skip(emp_id).join(table_path).join(allowances).map(
+        |((emp_id, table_path), allowances)| Command::ChgIncomeTax {
+            emp_id,
+            table_path,
+            allowances,
+        },
+    )
+}
+#[cfg(test)]
+mod test_chg_income_tax {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 IncomeTax "tax.tbl" Allowances 0"#;
+        let result = chg_income_tax().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgIncomeTax {
+                    emp_id: 1,
+                    table_path: "tax.tbl".to_string(),
+                    allowances: 0
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn withholding_column() -> impl Parser<Item = (WithholdingColumn, u32)> {
+    let kou = keyword("Kou").map(|_| WithholdingColumn::Kou);
+    let otsu = keyword("Otsu").map(|_| WithholdingColumn::Otsu);
+    let dependents = keyword("Dependents").skip(spaces()).skip(uint32());
+
+    kou.or(otsu).with(spaces()).join(dependents)
+}
+#[cfg(test)]
+mod test_withholding_column {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"Kou Dependents 3"#;
+        let result = withholding_column().parse(input);
+        assert_eq!(result, Ok(((WithholdingColumn::Kou, 3), "")));
+
+        let input = r#"Otsu Dependents 0"#;
+        let result = withholding_column().parse(input);
+        assert_eq!(result, Ok(((WithholdingColumn::Otsu, 0), "")));
+    }
+}
+
+fn chg_monthly_tax() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let table_path = keyword("MonthlyTax")
+        .skip(spaces())
+        .skip(string())
+        .with(spaces());
+
+    prefix
+        .skip(emp_id)
+        .join(table_path)
+        .join(withholding_column())
+        .map(
+            |((emp_id, table_path), (column, dependents))| Command::ChgMonthlyTax {
+                emp_id,
+                table_path,
+                column,
+                dependents,
+            },
+        )
+}
+#[cfg(test)]
+mod test_chg_monthly_tax {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 MonthlyTax "tax.csv" Otsu Dependents 1"#;
+        let result = chg_monthly_tax().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgMonthlyTax {
+                    emp_id: 1,
+                    table_path: "tax.csv".to_string(),
+                    column: WithholdingColumn::Otsu,
+                    dependents: 1
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_tax_column() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let column = keyword("MonthlyTax")
+        .skip(spaces())
+        .skip(withholding_column());
+
+    prefix
+        .skip(emp_id)
+        .join(column)
+        .map(|(emp_id, (column, dependents))| Command::ChgTaxColumn {
+            emp_id,
+            column,
+            dependents,
+        })
+}
+#[cfg(test)]
+mod test_chg_tax_column {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 MonthlyTax Kou Dependents 4"#;
+        let result = chg_tax_column().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgTaxColumn {
+                    emp_id: 1,
+                    column: WithholdingColumn::Kou,
+                    dependents: 4
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_social_insurance() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let table_path = keyword("SocialInsurance")
+        .skip(spaces())
+        .skip(string())
+        .with(spaces());
+    let prefecture = string().with(spaces());
+    let grade = keyword("Grade").skip(spaces()).skip(uint32());
+
+    prefix
+        .skip(emp_id)
+        .join(table_path)
+        .join(prefecture)
+        .join(grade)
+        .map(
+            |(((emp_id, table_path), prefecture), grade)| Command::ChgSocialInsurance {
+                emp_id,
+                table_path,
+                prefecture,
+                grade,
+            },
+        )
+}
+#[cfg(test)]
+mod test_chg_social_insurance {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 SocialInsurance "si.tbl" "Osaka" Grade 5"#;
+        let result = chg_social_insurance().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgSocialInsurance {
+                    emp_id: 1,
+                    table_path: "si.tbl".to_string(),
+                    prefecture: "Osaka".to_string(),
+                    grade: 5
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_grade() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let grade = keyword("Grade").skip(spaces()).skip(uint32());
+
+    prefix
+        .skip(emp_id)
+        .join(grade)
+        .map(|(emp_id, grade)| Command::ChgGrade { emp_id, grade })
+}
+#[cfg(test)]
+mod test_chg_grade {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 Grade 30"#;
+        let result = chg_grade().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgGrade {
+                    emp_id: 1,
+                    grade: 30
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn import_resident_tax() -> impl Parser<Item = Command> {
+    let prefix = keyword("ImportResidentTax").skip(spaces());
+    let notices_path = string();
+
+    prefix
+        .skip(notices_path)
+        .map(|notices_path| Command::ImportResidentTax { notices_path })
+}
+#[cfg(test)]
+mod test_import_resident_tax {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ImportResidentTax "notices.csv""#;
+        let result = import_resident_tax().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ImportResidentTax {
+                    notices_path: "notices.csv".to_string()
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn chg_retirement() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let percent = || float32().with(char('%')).map(|percent| percent / 100.0);
+    let cap = || keyword("Cap").skip(spaces()).skip(float32());
+    let employee = keyword("Retirement")
+        .skip(spaces())
+        .skip(percent())
+        .with(spaces())
+        .join(cap())
+        .with(spaces());
+    let employer = keyword("Match")
+        .skip(spaces())
+        .skip(percent())
+        .with(spaces())
+        .join(keyword("UpTo").skip(spaces()).skip(percent()))
+        .with(spaces())
+        .join(cap());
+
+    prefix.skip(emp_id).join(employee).join(employer).map(
+        |((emp_id, (rate, annual_cap)), ((match_rate, match_limit), employer_annual_cap))| {
+            Command::ChgRetirement {
+                emp_id,
+                election: RetirementElection {
+                    rate,
+                    annual_cap,
+                    match_rate,
+                    match_limit,
+                    employer_annual_cap,
+                },
+            }
+        },
+    )
+}
+#[cfg(test)]
+mod test_chg_retirement {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 Retirement 3.0% Cap 1000.0 Match 100.0% UpTo 3.0% Cap 500.0"#;
+        let result = chg_retirement().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ChgRetirement {
+                    emp_id: 1,
+                    election: RetirementElection {
+                        rate: 0.03,
+                        annual_cap: 1000.0,
+                        match_rate: 1.0,
+                        match_limit: 0.03,
+                        employer_annual_cap: 500.0
+                    }
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn add_garnishment() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let case_id = keyword("Garnishment")
+        .skip(spaces())
+        .skip(string())
+        .with(spaces());
+    let priority = keyword("Priority")
+        .skip(spaces())
+        .skip(uint32())
+        .with(spaces());
+    let limit_rate = float32()
+        .with(char('%'))
+        .map(|percent| percent / 100.0)
+        .with(spaces());
+    let total = keyword("Total").skip(spaces()).skip(float32());
+
+    prefix
+        .skip(emp_id)
+        .join(case_id)
+        .join(priority)
+        .join(limit_rate)
+        .join(total)
+        .map(
+            |((((emp_id, case_id), priority), limit_rate), total)| Command::AddGarnishment {
+                emp_id,
+                case_id,
+                priority,
+                limit_rate,
+                total,
+            },
+        )
+}
+#[cfg(test)]
+mod test_add_garnishment {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 Garnishment "A-1" Priority 2 50.0% Total 1200.0"#;
+        let result = add_garnishment().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::AddGarnishment {
+                    emp_id: 1,
+                    case_id: "A-1".to_string(),
+                    priority: 2,
+                    limit_rate: 0.5,
+                    total: 1200.0
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn release_garnishment() -> impl Parser<Item = Command> {
+    let prefix = keyword("ChgEmp").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let case_id = keyword("NoGarnishment").skip(spaces()).skip(string());
+
+    prefix
+        .skip(emp_id)
+        .join(case_id)
+        .map(|(emp_id, case_id)| Command::ReleaseGarnishment { emp_id, case_id })
+}
+#[cfg(test)]
+mod test_release_garnishment {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"ChgEmp 1 NoGarnishment "A-1""#;
+        let result = release_garnishment().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::ReleaseGarnishment {
+                    emp_id: 1,
+                    case_id: "A-1".to_string()
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn advance() -> impl Parser<Item = Command> {
+    let prefix = keyword("Advance").skip(spaces());
+    let emp_id = uint32().with(spaces());
+    let advance_id = string().with(spaces());
+    let date = date().with(spaces());
+    let principal = float32().with(spaces());
+    let installment = keyword("Installment").skip(spaces()).skip(float32());
+
+    prefix
+        .skip(emp_id)
+        .join(advance_id)
+        .join(date)
+        .join(principal)
+        .join(installment)
+        .map(
+            |((((emp_id, advance_id), date), principal), installment)| Command::Advance {
+                emp_id,
+                advance_id,
+                date,
+                principal,
+                installment,
+            },
+        )
+}
+#[cfg(test)]
+mod test_advance {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"Advance 1 "A-1" 2021-01-04 500.0 Installment 125.5"#;
+        let result = advance().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::Advance {
+                    emp_id: 1,
+                    advance_id: "A-1".to_string(),
+                    date: NaiveDate::from_ymd_opt(2021, 1, 4).unwrap(),
+                    principal: 500.0,
+                    installment: 125.5,
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn payday() -> impl Parser<Item = Command> {
+    let prefix = keyword("Payday").skip(spaces());
+    let date = date();
+
+    prefix
+        .skip(date)
+        .map(|pay_date| Command::Payday { pay_date })
+}
+#[cfg(test)]
+mod test_payday {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"Payday 2021-01-01"#;
+        let result = payday().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::Payday {
+                    pay_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()
+                },
+                ""
+            ))
+        );
+    }
+}
+
+fn year_end_adjustment() -> impl Parser<Item = Command> {
+    let prefix = keyword("YearEndAdjustment").skip(spaces());
+    let year = int32().with(spaces());
+    let table_path = string().with(spaces());
+    let declarations_path = string();
+
+    prefix
+        .skip(year)
+        .join(table_path)
+        .join(declarations_path)
+        .map(
+            |((year, table_path), declarations_path)| Command::YearEndAdjustment {
+                year,
+                table_path,
+                declarations_path,
+            },
+        )
+}
+#[cfg(test)]
+mod test_year_end_adjustment {
+    use super::*;
+    use parsec_rs::Parser;
+
+    #[test]
+    fn test() {
+        let input = r#"YearEndAdjustment 2021 "table.tbl" "declarations.csv""#;
+        let result = year_end_adjustment().parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                Command::YearEndAdjustment {
+                    year: 2021,
+                    table_path: "table.tbl".to_string(),
+                    declarations_path: "declarations.csv".to_string()
                 },
                 ""
             ))
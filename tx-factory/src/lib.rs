@@ -1,6 +1,10 @@
 use chrono::NaiveDate;
 
-use payroll_domain::{EmployeeId, MemberId};
+use dao::EmployeeQuery;
+use payroll_domain::{
+    Component, DuesPolicy, EmployeeId, MemberId, RetirementElection, ServiceChargeId,
+    WithholdingColumn,
+};
 use tx_app::Transaction;
 
 pub trait TransactionFactory<Ctx> {
@@ -26,7 +30,15 @@ pub trait TransactionFactory<Ctx> {
         salary: f32,
         commission_rate: f32,
     ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_add_composite_employee_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+        address: String,
+        components: Vec<Component>,
+    ) -> Box<dyn Transaction<Ctx>>;
     fn mk_delete_employee_tx(&self, emp_id: EmployeeId) -> Box<dyn Transaction<Ctx>>;
+    fn mk_list_employees_tx(&self, query: EmployeeQuery) -> Box<dyn Transaction<Ctx>>;
     fn mk_timecard_tx(
         &self,
         emp_id: EmployeeId,
@@ -45,6 +57,11 @@ pub trait TransactionFactory<Ctx> {
         emp_id: EmployeeId,
         address: String,
     ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_net_pay_floor_tx(
+        &self,
+        emp_id: EmployeeId,
+        net_pay_floor: f32,
+    ) -> Box<dyn Transaction<Ctx>>;
     fn mk_change_salaried_tx(&self, emp_id: EmployeeId, salary: f32) -> Box<dyn Transaction<Ctx>>;
     fn mk_change_hourly_tx(
         &self,
@@ -57,6 +74,11 @@ pub trait TransactionFactory<Ctx> {
         salary: f32,
         commission_rate: f32,
     ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_composite_tx(
+        &self,
+        emp_id: EmployeeId,
+        components: Vec<Component>,
+    ) -> Box<dyn Transaction<Ctx>>;
     fn mk_change_direct_tx(
         &self,
         emp_id: EmployeeId,
@@ -69,14 +91,102 @@ pub trait TransactionFactory<Ctx> {
         &self,
         emp_id: EmployeeId,
         member_id: MemberId,
-        dues: f32,
+        dues: DuesPolicy,
     ) -> Box<dyn Transaction<Ctx>>;
     fn mk_change_unaffiliated_tx(&self, emp_id: EmployeeId) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_union_dues_tx(
+        &self,
+        emp_id: EmployeeId,
+        dues: DuesPolicy,
+        effective: Option<NaiveDate>,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_member_id_tx(
+        &self,
+        emp_id: EmployeeId,
+        member_id: MemberId,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_deduction_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+        amount: f32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_no_deduction_tx(
+        &self,
+        emp_id: EmployeeId,
+        name: String,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_income_tax_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        allowances: u32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_monthly_tax_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_tax_column_tx(
+        &self,
+        emp_id: EmployeeId,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_social_insurance_tx(
+        &self,
+        emp_id: EmployeeId,
+        table_path: String,
+        prefecture: String,
+        grade: u32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_grade_tx(&self, emp_id: EmployeeId, grade: u32) -> Box<dyn Transaction<Ctx>>;
+    fn mk_import_resident_tax_tx(&self, notices_path: String) -> Box<dyn Transaction<Ctx>>;
+    fn mk_change_retirement_tx(
+        &self,
+        emp_id: EmployeeId,
+        election: RetirementElection,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_add_garnishment_tx(
+        &self,
+        emp_id: EmployeeId,
+        case_id: String,
+        priority: u32,
+        limit_rate: f32,
+        total: f32,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_release_garnishment_tx(
+        &self,
+        emp_id: EmployeeId,
+        case_id: String,
+    ) -> Box<dyn Transaction<Ctx>>;
     fn mk_service_charge_tx(
         &self,
         member_id: MemberId,
         date: NaiveDate,
         amount: f32,
     ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_reverse_service_charge_tx(
+        &self,
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+        date: NaiveDate,
+    ) -> Box<dyn Transaction<Ctx>>;
+    fn mk_advance_tx(
+        &self,
+        emp_id: EmployeeId,
+        advance_id: String,
+        date: NaiveDate,
+        principal: f32,
+        installment: f32,
+    ) -> Box<dyn Transaction<Ctx>>;
     fn mk_payday_tx(&self, pay_date: NaiveDate) -> Box<dyn Transaction<Ctx>>;
+    fn mk_year_end_adjustment_tx(
+        &self,
+        year: i32,
+        table_path: String,
+        declarations_path: String,
+    ) -> Box<dyn Transaction<Ctx>>;
 }
@@ -1,7 +1,35 @@
+mod add_garnishment_tx;
+mod change_deduction_tx;
+mod change_grade_tx;
+mod change_income_tax_tx;
+mod change_member_id_tx;
+mod change_monthly_tax_tx;
+mod change_no_deduction_tx;
+mod change_retirement_tx;
+mod change_social_insurance_tx;
+mod change_tax_column_tx;
 mod change_unaffiliated_tx;
+mod change_union_dues_tx;
 mod change_union_member_tx;
+mod import_resident_tax_tx;
+mod release_garnishment_tx;
+mod reverse_service_charge_tx;
 mod service_charge_tx;
 
+pub use add_garnishment_tx::AddGarnishmentTransaction;
+pub use change_deduction_tx::ChangeDeductionTransaction;
+pub use change_grade_tx::ChangeGradeTransaction;
+pub use change_income_tax_tx::ChangeIncomeTaxTransaction;
+pub use change_member_id_tx::ChangeMemberIdTransaction;
+pub use change_monthly_tax_tx::ChangeMonthlyTaxTransaction;
+pub use change_no_deduction_tx::ChangeNoDeductionTransaction;
+pub use change_retirement_tx::ChangeRetirementTransaction;
+pub use change_social_insurance_tx::ChangeSocialInsuranceTransaction;
+pub use change_tax_column_tx::ChangeTaxColumnTransaction;
 pub use change_unaffiliated_tx::ChangeUnaffiliatedTransaction;
+pub use change_union_dues_tx::ChangeUnionDuesTransaction;
 pub use change_union_member_tx::ChangeUnionMemberTransaction;
+pub use import_resident_tax_tx::ImportResidentTaxTransaction;
+pub use release_garnishment_tx::ReleaseGarnishmentTransaction;
+pub use reverse_service_charge_tx::ReverseServiceChargeTransaction;
 pub use service_charge_tx::ServiceChargeTransaction;
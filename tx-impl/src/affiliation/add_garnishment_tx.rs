@@ -0,0 +1,48 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::affiliation::{GarnishmentOrder, Garnishments};
+
+pub trait AddGarnishmentTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        case_id: &'a str,
+        priority: u32,
+        limit_rate: f32,
+        total: f32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let affiliation = match emp.get_affiliation(Garnishments::NAME) {
+                Some(a) => a,
+                None => {
+                    let a = Arc::new(RwLock::new(Garnishments::new()));
+                    emp.add_affiliation(a.clone());
+                    a
+                }
+            };
+            let mut affiliation = affiliation.write().unwrap();
+            let garnishments = affiliation
+                .as_any_mut()
+                .downcast_mut::<Garnishments>()
+                .ok_or(UsecaseError::AffiliationConflict {
+                    emp_id,
+                    name: Garnishments::NAME.to_string(),
+                })?;
+            if garnishments.get_order(case_id).is_some() {
+                return Err(UsecaseError::GarnishmentOrderConflict {
+                    emp_id,
+                    case_id: case_id.to_string(),
+                });
+            }
+            garnishments.add_order(GarnishmentOrder::new(case_id, priority, limit_rate, total));
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> AddGarnishmentTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,33 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::affiliation::GeneralDeduction;
+
+pub trait ChangeDeductionTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        name: &str,
+        amount: f32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            // only another general deduction may be replaced by this one
+            if let Some(a) = emp.get_affiliation(name) {
+                if !a.read().unwrap().as_any().is::<GeneralDeduction>() {
+                    return Err(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: name.to_string(),
+                    });
+                }
+            }
+            emp.add_affiliation(Arc::new(RwLock::new(GeneralDeduction::new(name, amount))));
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeDeductionTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
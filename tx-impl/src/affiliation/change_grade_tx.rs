@@ -0,0 +1,36 @@
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::insurance::SocialInsurance;
+
+pub trait ChangeGradeTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        grade: u32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let not_found = || UsecaseError::AffiliationNotFound {
+                emp_id,
+                name: SocialInsurance::NAME.to_string(),
+            };
+            let affiliation = emp
+                .get_affiliation(SocialInsurance::NAME)
+                .ok_or_else(not_found)?;
+            let mut affiliation = affiliation.write().unwrap();
+            let insurance = affiliation
+                .as_any_mut()
+                .downcast_mut::<SocialInsurance>()
+                .ok_or_else(not_found)?;
+            if insurance.get_table().get_remuneration(grade).is_none() {
+                return Err(UsecaseError::GradeNotFound { emp_id, grade });
+            }
+            insurance.set_grade(grade);
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeGradeTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,41 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::{
+    table::SharedTable,
+    tax::{IncomeTaxWithholding, MonthlyWithholding, TaxTable},
+};
+
+pub trait ChangeIncomeTaxTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        table: SharedTable<TaxTable>,
+        allowances: u32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            // one withholding rule may replace another
+            if let Some(a) = emp.get_affiliation(IncomeTaxWithholding::NAME) {
+                let a = a.read().unwrap();
+                if !(a.as_any().is::<IncomeTaxWithholding>()
+                    || a.as_any().is::<MonthlyWithholding>())
+                {
+                    return Err(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: IncomeTaxWithholding::NAME.to_string(),
+                    });
+                }
+            }
+            emp.add_affiliation(Arc::new(RwLock::new(IncomeTaxWithholding::new(
+                table, allowances,
+            ))));
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeIncomeTaxTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,31 @@
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::{EmployeeId, MemberId};
+use payroll_impl::affiliation::UnionAffiliation;
+
+pub trait ChangeMemberIdTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        member_id: MemberId,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let affiliation = emp
+                .get_affiliation(UnionAffiliation::NAME)
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            let mut affiliation = affiliation.write().unwrap();
+            let union = affiliation
+                .as_any_mut()
+                .downcast_mut::<UnionAffiliation>()
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            // service charges recorded so far stay with the membership; the dao
+            // reindexes the member id, refusing one held by another employee
+            union.set_member_id(member_id);
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeMemberIdTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,144 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::{EmployeeId, WithholdingColumn};
+use payroll_impl::{
+    table::SharedTable,
+    tax::{IncomeTaxWithholding, MonthlyWithholding, MonthlyWithholdingTable},
+};
+
+pub trait ChangeMonthlyTaxTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        table: SharedTable<MonthlyWithholdingTable>,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            if !MonthlyWithholding::is_applicable(&*emp.get_schedule().read().unwrap()) {
+                return Err(UsecaseError::NotPaidMonthly { emp_id });
+            }
+            // one withholding rule may replace another
+            if let Some(a) = emp.get_affiliation(IncomeTaxWithholding::NAME) {
+                let a = a.read().unwrap();
+                if !(a.as_any().is::<IncomeTaxWithholding>()
+                    || a.as_any().is::<MonthlyWithholding>())
+                {
+                    return Err(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: IncomeTaxWithholding::NAME.to_string(),
+                    });
+                }
+            }
+            emp.add_affiliation(Arc::new(RwLock::new(MonthlyWithholding::new(
+                table, column, dependents,
+            ))));
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeMonthlyTaxTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
+#[cfg(test)]
+mod test_change_monthly_tax_tx {
+    use super::*;
+    use chrono::NaiveDate;
+    use tx_rs::Tx;
+
+    use abstract_tx::ChangeClassificationTransaction;
+    use dao::{DaoError, EmployeeDao, HaveEmployeeDao};
+    use mock_db::{MockDb, MockTx};
+    use payroll_domain::{Employee, Paycheck, PaymentClassification, PaymentSchedule};
+    use payroll_impl::{
+        classification::{HourlyClassification, SalariedClassification},
+        method::HoldMethod,
+        schedule::{MonthlySchedule, WeeklySchedule},
+    };
+
+    struct Db(MockDb);
+    impl HaveEmployeeDao<MockTx> for Db {
+        fn dao(&self) -> Box<&impl EmployeeDao<MockTx>> {
+            Box::new(&self.0)
+        }
+    }
+
+    fn employee(
+        emp_id: EmployeeId,
+        classification: Arc<RwLock<dyn PaymentClassification>>,
+        schedule: Arc<RwLock<dyn PaymentSchedule>>,
+    ) -> Employee {
+        Employee::new(
+            emp_id,
+            "Bob",
+            "Home",
+            classification,
+            schedule,
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_paid_monthly_only() {
+        let db = Db(MockDb::new());
+        db.0.with_transaction(|tx| {
+            let salaried = Arc::new(RwLock::new(SalariedClassification::new(100000.0)));
+            db.0.insert(employee(
+                1,
+                salaried,
+                Arc::new(RwLock::new(MonthlySchedule)),
+            ))
+            .run(tx)?;
+            let hourly = Arc::new(RwLock::new(HourlyClassification::new(1000.0)));
+            db.0.insert(employee(2, hourly, Arc::new(RwLock::new(WeeklySchedule))))
+                .run(tx)?;
+            Ok::<_, DaoError>(())
+        })
+        .unwrap();
+        // 10% of the whole pay of the month
+        let table = SharedTable::insert(
+            "test_change_monthly_tax_tx/monthly.csv",
+            "0,,0,0,0,0,0,0,0,0,0,10%,20%"
+                .parse::<MonthlyWithholdingTable>()
+                .unwrap(),
+        );
+        let change = |emp_id| {
+            db.0.with_transaction(|tx| {
+                ChangeMonthlyTaxTransaction::execute(
+                    &db,
+                    emp_id,
+                    table.clone(),
+                    WithholdingColumn::Kou,
+                    0,
+                )
+                .run(tx)
+            })
+        };
+        // the weekly pay is not withheld by the monthly table
+        assert_eq!(change(2), Err(UsecaseError::NotPaidMonthly { emp_id: 2 }));
+        assert_eq!(change(1), Ok(()));
+
+        // nor may the employee withheld by it be paid weekly from now on
+        let result = db.0.with_transaction(|tx| {
+            ChangeClassificationTransaction::execute(
+                &db,
+                1,
+                Arc::new(RwLock::new(HourlyClassification::new(1000.0))),
+                Arc::new(RwLock::new(WeeklySchedule)),
+            )
+            .run(tx)
+        });
+        assert_eq!(result, Err(UsecaseError::NotPaidMonthly { emp_id: 1 }));
+
+        let mut emp = db.0.with_transaction(|tx| db.0.fetch(1).run(tx)).unwrap();
+        let first = NaiveDate::from_ymd_opt(2024, 8, 1).unwrap();
+        let last = NaiveDate::from_ymd_opt(2024, 8, 31).unwrap();
+        let mut pc = Paycheck::new(first..=last);
+        emp.make_out(&mut pc);
+        assert_eq!(pc.get_deductions(), 10000.0);
+    }
+}
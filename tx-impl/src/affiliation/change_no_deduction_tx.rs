@@ -0,0 +1,18 @@
+use abstract_tx::{RemoveAffiliationTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+
+pub trait ChangeNoDeductionTransaction<Ctx>: RemoveAffiliationTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        name: &str,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        // leaving the union by name drops the member id from the dao's index too
+        RemoveAffiliationTransaction::execute(self, emp_id, name)
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeNoDeductionTransaction<Ctx> for T where T: RemoveAffiliationTransaction<Ctx> {}
@@ -0,0 +1,41 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::{EmployeeId, RetirementElection};
+use payroll_impl::affiliation::RetirementContribution;
+
+pub trait ChangeRetirementTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        election: RetirementElection,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            match emp.get_affiliation(RetirementContribution::NAME) {
+                // a new election keeps the contributions of the year
+                Some(a) => {
+                    a.write()
+                        .unwrap()
+                        .as_any_mut()
+                        .downcast_mut::<RetirementContribution>()
+                        .ok_or(UsecaseError::AffiliationConflict {
+                            emp_id,
+                            name: RetirementContribution::NAME.to_string(),
+                        })?
+                        .set_election(election);
+                }
+                None => {
+                    emp.add_affiliation(Arc::new(RwLock::new(RetirementContribution::new(
+                        election,
+                    ))));
+                }
+            }
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeRetirementTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
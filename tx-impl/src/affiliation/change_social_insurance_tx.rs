@@ -0,0 +1,49 @@
+use std::sync::{Arc, RwLock};
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::{
+    insurance::{SocialInsurance, SocialInsuranceTable},
+    table::SharedTable,
+};
+
+pub trait ChangeSocialInsuranceTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        table: SharedTable<SocialInsuranceTable>,
+        prefecture: &'a str,
+        grade: u32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        let grade_found = table.get().get_remuneration(grade).is_some();
+        let prefecture_found = table.get().has_prefecture(prefecture);
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            if !grade_found {
+                return Err(UsecaseError::GradeNotFound { emp_id, grade });
+            }
+            if !prefecture_found {
+                return Err(UsecaseError::PrefectureNotFound {
+                    emp_id,
+                    prefecture: prefecture.to_string(),
+                });
+            }
+            if let Some(a) = emp.get_affiliation(SocialInsurance::NAME) {
+                if !a.read().unwrap().as_any().is::<SocialInsurance>() {
+                    return Err(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: SocialInsurance::NAME.to_string(),
+                    });
+                }
+            }
+            emp.add_affiliation(Arc::new(RwLock::new(SocialInsurance::new(
+                table, prefecture, grade,
+            ))));
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeSocialInsuranceTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,38 @@
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::{EmployeeId, WithholdingColumn};
+use payroll_impl::tax::{IncomeTaxWithholding, MonthlyWithholding};
+
+pub trait ChangeTaxColumnTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        column: WithholdingColumn,
+        dependents: u32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let not_found = || UsecaseError::AffiliationNotFound {
+                emp_id,
+                name: IncomeTaxWithholding::NAME.to_string(),
+            };
+            let affiliation = emp
+                .get_affiliation(IncomeTaxWithholding::NAME)
+                .ok_or_else(not_found)?;
+            let mut affiliation = affiliation.write().unwrap();
+            let withholding = affiliation
+                .as_any_mut()
+                .downcast_mut::<MonthlyWithholding>()
+                .ok_or_else(not_found)?;
+            if !MonthlyWithholding::is_applicable(&*emp.get_schedule().read().unwrap()) {
+                return Err(UsecaseError::NotPaidMonthly { emp_id });
+            }
+            withholding.set_column(column);
+            withholding.set_dependents(dependents);
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeTaxColumnTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
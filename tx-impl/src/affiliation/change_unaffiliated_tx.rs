@@ -1,12 +1,8 @@
-use std::{cell::RefCell, rc::Rc};
-use tx_rs::Tx;
-
-use abstract_tx::{ChangeAffiliationTransaction, UsecaseError};
-use dao::EmployeeDao;
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
-use payroll_impl::affiliation::{NoAffiliation, UnionAffiliation};
+use payroll_impl::affiliation::UnionAffiliation;
 
-pub trait ChangeUnaffiliatedTransaction<Ctx>: ChangeAffiliationTransaction<Ctx> {
+pub trait ChangeUnaffiliatedTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
     fn execute<'a>(
         &'a self,
         emp_id: EmployeeId,
@@ -14,27 +10,14 @@ pub trait ChangeUnaffiliatedTransaction<Ctx>: ChangeAffiliationTransaction<Ctx>
     where
         Ctx: 'a,
     {
-        ChangeAffiliationTransaction::execute(
-            self,
-            emp_id,
-            move |ctx, emp| {
-                let member_id = emp
-                    .get_affiliation()
-                    .borrow()
-                    .as_any()
-                    .downcast_ref::<UnionAffiliation>()
-                    .map_or(
-                        Err(UsecaseError::NotUnionMember(format!("emp_id: {}", emp_id))),
-                        |a| Ok(a.get_member_id()),
-                    )?;
-                self.dao()
-                    .remove_union_member(member_id)
-                    .run(ctx)
-                    .map_err(UsecaseError::RemoveUnionMemberFailed)
-            },
-            Rc::new(RefCell::new(NoAffiliation)),
-        )
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            if UnionAffiliation::member_id_of(emp).is_none() {
+                return Err(UsecaseError::NotUnionMember { emp_id });
+            }
+            emp.remove_affiliation(UnionAffiliation::NAME);
+            Ok(())
+        })
     }
 }
 // blanket implementation
-impl<T, Ctx> ChangeUnaffiliatedTransaction<Ctx> for T where T: ChangeAffiliationTransaction<Ctx> {}
+impl<T, Ctx> ChangeUnaffiliatedTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::{DuesPolicy, EmployeeId};
+use payroll_impl::affiliation::UnionAffiliation;
+
+pub trait ChangeUnionDuesTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        dues: DuesPolicy,
+        effective: Option<NaiveDate>,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let affiliation = emp
+                .get_affiliation(UnionAffiliation::NAME)
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            affiliation
+                .write()
+                .unwrap()
+                .as_any_mut()
+                .downcast_mut::<UnionAffiliation>()
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?
+                .change_dues(dues, effective);
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeUnionDuesTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
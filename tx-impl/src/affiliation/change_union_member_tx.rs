@@ -1,9 +1,7 @@
-use std::{cell::RefCell, rc::Rc};
-use tx_rs::Tx;
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeAffiliationTransaction, UsecaseError};
-use dao::EmployeeDao;
-use payroll_domain::{EmployeeId, MemberId};
+use payroll_domain::{DuesPolicy, EmployeeId, MemberId};
 use payroll_impl::affiliation::UnionAffiliation;
 
 pub trait ChangeUnionMemberTransaction<Ctx>: ChangeAffiliationTransaction<Ctx> {
@@ -11,21 +9,16 @@ pub trait ChangeUnionMemberTransaction<Ctx>: ChangeAffiliationTransaction<Ctx> {
         &'a self,
         emp_id: EmployeeId,
         member_id: MemberId,
-        dues: f32,
+        dues: DuesPolicy,
     ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
     where
         Ctx: 'a,
     {
+        // re-joining replaces the previous membership; the dao reindexes the member id
         ChangeAffiliationTransaction::execute(
             self,
             emp_id,
-            move |ctx, _emp| {
-                self.dao()
-                    .add_union_member(member_id, emp_id)
-                    .run(ctx)
-                    .map_err(UsecaseError::AddUnionMemberFailed)
-            },
-            Rc::new(RefCell::new(UnionAffiliation::new(member_id, dues))),
+            Arc::new(RwLock::new(UnionAffiliation::new(member_id, dues))),
         )
     }
 }
@@ -0,0 +1,171 @@
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, RwLock},
+};
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{EmployeeDao, HaveEmployeeDao};
+use payroll_domain::EmployeeId;
+use payroll_impl::tax::{ResidentTax, ResidentTaxNotice};
+
+pub trait ImportResidentTaxTransaction<Ctx>: HaveEmployeeDao<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        notices: &'a [ResidentTaxNotice],
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        tx_rs::with_tx(move |ctx| {
+            // an employee notified more than once is changed by all of them in order
+            let mut notified: BTreeMap<EmployeeId, Vec<&ResidentTaxNotice>> = BTreeMap::new();
+            for notice in notices {
+                notified
+                    .entry(notice.get_emp_id())
+                    .or_default()
+                    .push(notice);
+            }
+            // every notified employee must exist before any of them is updated
+            let mut employees = vec![];
+            for (emp_id, notices) in notified {
+                let mut emp = self
+                    .dao()
+                    .fetch(emp_id)
+                    .run(ctx)
+                    .map_err(UsecaseError::fetch_failed)?;
+                let affiliation = emp.get_affiliation(ResidentTax::NAME).unwrap_or_else(|| {
+                    let affiliation = Arc::new(RwLock::new(ResidentTax::new()));
+                    emp.add_affiliation(affiliation.clone());
+                    affiliation
+                });
+                let mut affiliation = affiliation.write().unwrap();
+                let resident_tax = affiliation
+                    .as_any_mut()
+                    .downcast_mut::<ResidentTax>()
+                    .ok_or(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: ResidentTax::NAME.to_string(),
+                    })?;
+                for notice in notices {
+                    resident_tax.set_schedule(notice.get_fiscal_year(), notice.get_amounts());
+                }
+                drop(affiliation);
+                employees.push(emp);
+            }
+            for emp in employees {
+                self.dao()
+                    .update(emp)
+                    .run(ctx)
+                    .map_err(UsecaseError::update_failed)?;
+            }
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ImportResidentTaxTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
+#[cfg(test)]
+mod test_import_resident_tax_tx {
+    use super::*;
+
+    use dao::DaoError;
+    use mock_db::{MockDb, MockTx};
+    use payroll_domain::Employee;
+    use payroll_impl::{
+        affiliation::GeneralDeduction, classification::SalariedClassification, method::HoldMethod,
+        schedule::MonthlySchedule,
+    };
+
+    struct Db(MockDb);
+    impl HaveEmployeeDao<MockTx> for Db {
+        fn dao(&self) -> Box<&impl EmployeeDao<MockTx>> {
+            Box::new(&self.0)
+        }
+    }
+
+    fn employee(emp_id: EmployeeId) -> Employee {
+        Employee::new(
+            emp_id,
+            "Hanako",
+            "Tokyo",
+            Arc::new(RwLock::new(SalariedClassification::new(300000.0))),
+            Arc::new(RwLock::new(MonthlySchedule)),
+            Arc::new(RwLock::new(HoldMethod)),
+            vec![],
+        )
+    }
+    fn schedule(db: &Db, emp_id: EmployeeId, fiscal_year: i32) -> Option<[f32; 12]> {
+        let emp =
+            db.0.with_transaction(|tx| db.0.fetch(emp_id).run(tx))
+                .unwrap();
+        let affiliation = emp.get_affiliation(ResidentTax::NAME)?;
+        let affiliation = affiliation.read().unwrap();
+        affiliation
+            .as_any()
+            .downcast_ref::<ResidentTax>()?
+            .get_schedule(fiscal_year)
+    }
+
+    #[test]
+    fn test_import() {
+        let db = Db(MockDb::new());
+        db.0.with_transaction(|tx| {
+            db.0.insert(employee(1)).run(tx)?;
+            db.0.insert(employee(2)).run(tx)?;
+            Ok::<_, DaoError>(())
+        })
+        .unwrap();
+        let notices = [
+            ResidentTaxNotice::new(1, 2024, [1000.0; 12]),
+            ResidentTaxNotice::new(2, 2024, [2000.0; 12]),
+            ResidentTaxNotice::new(1, 2025, [3000.0; 12]),
+            // corrects the first one
+            ResidentTaxNotice::new(1, 2024, [4000.0; 12]),
+        ];
+        db.0.with_transaction(|tx| ImportResidentTaxTransaction::execute(&db, &notices).run(tx))
+            .unwrap();
+        assert_eq!(schedule(&db, 1, 2024), Some([4000.0; 12]));
+        assert_eq!(schedule(&db, 1, 2025), Some([3000.0; 12]));
+        assert_eq!(schedule(&db, 2, 2024), Some([2000.0; 12]));
+        assert_eq!(schedule(&db, 2, 2025), None);
+    }
+    #[test]
+    fn test_all_or_nothing() {
+        let db = Db(MockDb::new());
+        db.0.with_transaction(|tx| {
+            db.0.insert(employee(1)).run(tx)?;
+            let mut emp = employee(2);
+            // not the resident tax, though named so
+            emp.add_affiliation(Arc::new(RwLock::new(GeneralDeduction::new(
+                ResidentTax::NAME,
+                100.0,
+            ))));
+            db.0.insert(emp).run(tx)
+        })
+        .unwrap();
+        let notices = [
+            ResidentTaxNotice::new(1, 2024, [1000.0; 12]),
+            ResidentTaxNotice::new(3, 2024, [2000.0; 12]),
+        ];
+        let result = db
+            .0
+            .with_transaction(|tx| ImportResidentTaxTransaction::execute(&db, &notices).run(tx));
+        assert!(matches!(result, Err(UsecaseError::NotFound(_))));
+        let notices = [
+            ResidentTaxNotice::new(1, 2024, [1000.0; 12]),
+            ResidentTaxNotice::new(2, 2024, [2000.0; 12]),
+        ];
+        let result = db
+            .0
+            .with_transaction(|tx| ImportResidentTaxTransaction::execute(&db, &notices).run(tx));
+        assert_eq!(
+            result,
+            Err(UsecaseError::AffiliationConflict {
+                emp_id: 2,
+                name: ResidentTax::NAME.to_string()
+            })
+        );
+        assert_eq!(schedule(&db, 1, 2024), None);
+    }
+}
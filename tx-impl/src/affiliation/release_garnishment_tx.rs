@@ -0,0 +1,39 @@
+use abstract_tx::{ChangeEmployeeTransaction, UsecaseError};
+use payroll_domain::EmployeeId;
+use payroll_impl::affiliation::Garnishments;
+
+pub trait ReleaseGarnishmentTransaction<Ctx>: ChangeEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        case_id: &'a str,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        ChangeEmployeeTransaction::execute(self, emp_id, move |_ctx, emp| {
+            let not_found = || UsecaseError::GarnishmentOrderNotFound {
+                emp_id,
+                case_id: case_id.to_string(),
+            };
+            let affiliation = emp
+                .get_affiliation(Garnishments::NAME)
+                .ok_or_else(not_found)?;
+            let no_orders_left = {
+                let mut affiliation = affiliation.write().unwrap();
+                let garnishments = affiliation
+                    .as_any_mut()
+                    .downcast_mut::<Garnishments>()
+                    .ok_or_else(not_found)?;
+                garnishments.remove_order(case_id).ok_or_else(not_found)?;
+                garnishments.is_empty()
+            };
+            if no_orders_left {
+                emp.remove_affiliation(Garnishments::NAME);
+            }
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ReleaseGarnishmentTransaction<Ctx> for T where T: ChangeEmployeeTransaction<Ctx> {}
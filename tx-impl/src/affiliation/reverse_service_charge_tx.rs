@@ -0,0 +1,58 @@
+use chrono::NaiveDate;
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{MemberId, ServiceChargeId};
+use payroll_impl::affiliation::UnionAffiliation;
+
+pub trait ReverseServiceChargeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
+    fn execute(
+        &self,
+        member_id: MemberId,
+        charge_id: ServiceChargeId,
+        date: NaiveDate,
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError> {
+        tx_rs::with_tx(move |ctx| {
+            let emp_id = self
+                .dao()
+                .find_union_member(member_id)
+                .run(ctx)
+                .map_err(UsecaseError::UnionMemberNotFound)?;
+            let emp = self
+                .dao()
+                .fetch(emp_id)
+                .run(ctx)
+                .map_err(UsecaseError::fetch_failed)?;
+            let affiliation = emp
+                .get_affiliation(UnionAffiliation::NAME)
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            let mut affiliation = affiliation.write().unwrap();
+            let union = affiliation
+                .as_any_mut()
+                .downcast_mut::<UnionAffiliation>()
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            let charge =
+                union
+                    .get_service_charge(charge_id)
+                    .ok_or(UsecaseError::ServiceChargeNotFound {
+                        member_id,
+                        charge_id,
+                    })?;
+            if charge.get_reversed_on().is_some() {
+                return Err(UsecaseError::ServiceChargeAlreadyReversed {
+                    member_id,
+                    charge_id,
+                });
+            }
+            union.reverse_service_charge(charge_id, date);
+            drop(affiliation);
+            self.dao()
+                .update(emp)
+                .run(ctx)
+                .map_err(UsecaseError::update_failed)
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ReverseServiceChargeTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
@@ -3,7 +3,7 @@ use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
 use dao::{EmployeeDao, HaveEmployeeDao};
-use payroll_domain::MemberId;
+use payroll_domain::{MemberId, ServiceChargeId};
 use payroll_impl::affiliation::UnionAffiliation;
 
 pub trait ServiceChargeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
@@ -12,30 +12,36 @@ pub trait ServiceChargeTransaction<Ctx>: HaveEmployeeDao<Ctx> {
         member_id: MemberId,
         date: NaiveDate,
         amount: f32,
-    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError> {
+    ) -> impl tx_rs::Tx<Ctx, Item = ServiceChargeId, Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
         tx_rs::with_tx(move |ctx| {
             let emp_id = self
                 .dao()
                 .find_union_member(member_id)
                 .run(ctx)
-                .map_err(UsecaseError::NotFound)?;
+                .map_err(UsecaseError::UnionMemberNotFound)?;
             let emp = self
                 .dao()
                 .fetch(emp_id)
                 .run(ctx)
-                .map_err(UsecaseError::NotFound)?;
-            emp.get_affiliation()
-                .borrow_mut()
+                .map_err(UsecaseError::fetch_failed)?;
+            let affiliation = emp
+                .get_affiliation(UnionAffiliation::NAME)
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?;
+            let charge_id = affiliation
+                .write()
+                .unwrap()
                 .as_any_mut()
                 .downcast_mut::<UnionAffiliation>()
-                .ok_or(UsecaseError::NotUnionMember(
-                    format!("emp_id: {0}", emp_id,),
-                ))?
+                .ok_or(UsecaseError::NotUnionMember { emp_id })?
                 .add_service_charge(date, amount);
             self.dao()
                 .update(emp)
                 .run(ctx)
-                .map_err(UsecaseError::UpdateEmployeeFailed)
+                .map_err(UsecaseError::update_failed)?;
+            Ok(charge_id)
         })
     }
 }
@@ -1,7 +1,9 @@
 mod change_commissioned_tx;
+mod change_composite_tx;
 mod change_hourly_tx;
 mod change_salaried_tx;
 
 pub use change_commissioned_tx::ChangeCommissionedTransaction;
+pub use change_composite_tx::ChangeCompositeTransaction;
 pub use change_hourly_tx::ChangeHourlyTransaction;
 pub use change_salaried_tx::ChangeSalariedTransaction;
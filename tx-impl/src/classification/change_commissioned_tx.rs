@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeClassificationTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -17,11 +17,11 @@ pub trait ChangeCommissionedTransaction<Ctx>: ChangeClassificationTransaction<Ct
         ChangeClassificationTransaction::execute(
             self,
             emp_id,
-            Rc::new(RefCell::new(CommissionedClassification::new(
+            Arc::new(RwLock::new(CommissionedClassification::new(
                 salary,
                 commission_rate,
             ))),
-            Rc::new(RefCell::new(BiweeklySchedule)),
+            Arc::new(RwLock::new(BiweeklySchedule)),
         )
     }
 }
@@ -0,0 +1,35 @@
+use std::sync::{Arc, RwLock};
+use tx_rs::Tx;
+
+use abstract_tx::{ChangeClassificationTransaction, UsecaseError};
+use payroll_domain::{Component, EmployeeId};
+use payroll_impl::classification::{
+    component_classification, schedule_of, CompositeClassification,
+};
+
+pub trait ChangeCompositeTransaction<Ctx>: ChangeClassificationTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        components: &[Component],
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        tx_rs::with_tx(move |ctx| {
+            let schedule = schedule_of(components)
+                .map_err(|reason| UsecaseError::InvalidComposite { emp_id, reason })?;
+            ChangeClassificationTransaction::execute(
+                self,
+                emp_id,
+                Arc::new(RwLock::new(CompositeClassification::new(
+                    components.iter().map(component_classification).collect(),
+                ))),
+                schedule,
+            )
+            .run(ctx)
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> ChangeCompositeTransaction<Ctx> for T where T: ChangeClassificationTransaction<Ctx> {}
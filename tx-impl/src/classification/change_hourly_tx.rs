@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeClassificationTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -16,8 +16,8 @@ pub trait ChangeHourlyTransaction<Ctx>: ChangeClassificationTransaction<Ctx> {
         ChangeClassificationTransaction::execute(
             self,
             emp_id,
-            Rc::new(RefCell::new(HourlyClassification::new(hourly_rate))),
-            Rc::new(RefCell::new(WeeklySchedule)),
+            Arc::new(RwLock::new(HourlyClassification::new(hourly_rate))),
+            Arc::new(RwLock::new(WeeklySchedule)),
         )
     }
 }
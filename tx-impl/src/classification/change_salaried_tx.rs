@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeClassificationTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -16,8 +16,8 @@ pub trait ChangeSalariedTransaction<Ctx>: ChangeClassificationTransaction<Ctx> {
         ChangeClassificationTransaction::execute(
             self,
             emp_id,
-            Rc::new(RefCell::new(SalariedClassification::new(salary))),
-            Rc::new(RefCell::new(MonthlySchedule)),
+            Arc::new(RwLock::new(SalariedClassification::new(salary))),
+            Arc::new(RwLock::new(MonthlySchedule)),
         )
     }
 }
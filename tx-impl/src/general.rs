@@ -1,19 +1,29 @@
 mod add_commissioned_employee_tx;
+mod add_composite_employee_tx;
 mod add_hourly_employee_tx;
 mod add_salary_employee_tx;
+mod advance_tx;
 mod change_address_tx;
 mod change_name_tx;
+mod change_net_pay_floor_tx;
 mod delete_employee_tx;
+mod list_employees_tx;
 mod payday_tx;
 mod sales_receipt_tx;
 mod timecard_tx;
+mod year_end_adjustment_tx;
 
 pub use add_commissioned_employee_tx::AddCommissionedEmployeeTransaction;
+pub use add_composite_employee_tx::AddCompositeEmployeeTransaction;
 pub use add_hourly_employee_tx::AddHourlyEmployeeTransaction;
 pub use add_salary_employee_tx::AddSalaryEmployeeTransaction;
+pub use advance_tx::AdvanceTransaction;
 pub use change_address_tx::ChangeAddressTransaction;
 pub use change_name_tx::ChangeNameTransaction;
+pub use change_net_pay_floor_tx::ChangeNetPayFloorTransaction;
 pub use delete_employee_tx::DeleteEmployeeTransaction;
-pub use payday_tx::PaydayTransaction;
+pub use list_employees_tx::ListEmployeesTransaction;
+pub use payday_tx::{PaydayTransaction, Payout};
 pub use sales_receipt_tx::SalesReceiptTransaction;
 pub use timecard_tx::TimeCardTransaction;
+pub use year_end_adjustment_tx::YearEndAdjustmentTransaction;
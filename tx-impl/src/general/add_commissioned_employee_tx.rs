@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{AddEmployeeTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -21,11 +21,11 @@ pub trait AddCommissionedEmployeeTransaction<Ctx>: AddEmployeeTransaction<Ctx> {
             emp_id,
             name,
             address,
-            Rc::new(RefCell::new(CommissionedClassification::new(
+            Arc::new(RwLock::new(CommissionedClassification::new(
                 salary,
                 commission_rate,
             ))),
-            Rc::new(RefCell::new(BiweeklySchedule)),
+            Arc::new(RwLock::new(BiweeklySchedule)),
         )
     }
 }
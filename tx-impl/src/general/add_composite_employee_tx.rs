@@ -0,0 +1,39 @@
+use std::sync::{Arc, RwLock};
+use tx_rs::Tx;
+
+use abstract_tx::{AddEmployeeTransaction, UsecaseError};
+use payroll_domain::{Component, EmployeeId};
+use payroll_impl::classification::{
+    component_classification, schedule_of, CompositeClassification,
+};
+
+pub trait AddCompositeEmployeeTransaction<Ctx>: AddEmployeeTransaction<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        name: &str,
+        address: &str,
+        components: &[Component],
+    ) -> impl tx_rs::Tx<Ctx, Item = EmployeeId, Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        tx_rs::with_tx(move |ctx| {
+            let schedule = schedule_of(components)
+                .map_err(|reason| UsecaseError::InvalidComposite { emp_id, reason })?;
+            AddEmployeeTransaction::execute(
+                self,
+                emp_id,
+                name,
+                address,
+                Arc::new(RwLock::new(CompositeClassification::new(
+                    components.iter().map(component_classification).collect(),
+                ))),
+                schedule,
+            )
+            .run(ctx)
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> AddCompositeEmployeeTransaction<Ctx> for T where T: AddEmployeeTransaction<Ctx> {}
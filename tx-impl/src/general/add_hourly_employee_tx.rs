@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{AddEmployeeTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -20,8 +20,8 @@ pub trait AddHourlyEmployeeTransaction<Ctx>: AddEmployeeTransaction<Ctx> {
             emp_id,
             name,
             address,
-            Rc::new(RefCell::new(HourlyClassification::new(hourly_rate))),
-            Rc::new(RefCell::new(WeeklySchedule)),
+            Arc::new(RwLock::new(HourlyClassification::new(hourly_rate))),
+            Arc::new(RwLock::new(WeeklySchedule)),
         )
     }
 }
@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{AddEmployeeTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -20,8 +20,8 @@ pub trait AddSalaryEmployeeTransaction<Ctx>: AddEmployeeTransaction<Ctx> {
             emp_id,
             name,
             address,
-            Rc::new(RefCell::new(SalariedClassification::new(salary))),
-            Rc::new(RefCell::new(MonthlySchedule)),
+            Arc::new(RwLock::new(SalariedClassification::new(salary))),
+            Arc::new(RwLock::new(MonthlySchedule)),
         )
     }
 }
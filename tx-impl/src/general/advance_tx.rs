@@ -0,0 +1,71 @@
+use chrono::NaiveDate;
+use std::sync::{Arc, RwLock};
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{EmployeeDao, HaveEmployeeDao};
+use payroll_domain::{Employee, EmployeeId, Paycheck};
+use payroll_impl::affiliation::{Advance, Advances};
+
+pub trait AdvanceTransaction<Ctx>: HaveEmployeeDao<Ctx> {
+    // the paycheck of the disbursement is returned to be paid out once the
+    // transaction has been committed
+    fn execute<'a>(
+        &'a self,
+        emp_id: EmployeeId,
+        advance_id: &'a str,
+        date: NaiveDate,
+        principal: f32,
+        installment: f32,
+    ) -> impl tx_rs::Tx<Ctx, Item = (Employee, Paycheck), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        tx_rs::with_tx(move |ctx| {
+            let advance =
+                Advance::new(advance_id, date, principal, installment).map_err(|reason| {
+                    UsecaseError::InvalidAdvance {
+                        emp_id,
+                        advance_id: advance_id.to_string(),
+                        reason,
+                    }
+                })?;
+            let mut emp = self
+                .dao()
+                .fetch(emp_id)
+                .run(ctx)
+                .map_err(UsecaseError::fetch_failed)?;
+            let affiliation = match emp.get_affiliation(Advances::NAME) {
+                Some(a) => a,
+                None => {
+                    let a = Arc::new(RwLock::new(Advances::new()));
+                    emp.add_affiliation(a.clone());
+                    a
+                }
+            };
+            {
+                let mut affiliation = affiliation.write().unwrap();
+                let advances = affiliation.as_any_mut().downcast_mut::<Advances>().ok_or(
+                    UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: Advances::NAME.to_string(),
+                    },
+                )?;
+                if advances.get_advance(advance_id).is_some() {
+                    return Err(UsecaseError::AdvanceConflict {
+                        emp_id,
+                        advance_id: advance_id.to_string(),
+                    });
+                }
+                advances.add_advance(advance.clone());
+            }
+            self.dao()
+                .update(emp.clone())
+                .run(ctx)
+                .map_err(UsecaseError::update_failed)?;
+            Ok((emp, advance.disbursement()))
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> AdvanceTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
@@ -0,0 +1,21 @@
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{EmployeeDao, EmployeeQuery, HaveEmployeeDao};
+use payroll_domain::Employee;
+
+pub trait ListEmployeesTransaction<Ctx>: HaveEmployeeDao<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        query: EmployeeQuery,
+    ) -> impl tx_rs::Tx<Ctx, Item = Vec<Employee>, Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        self.dao()
+            .find_employees(query)
+            .map_err(UsecaseError::FindEmployeesFailed)
+    }
+}
+// blanket implementation
+impl<Ctx, T> ListEmployeesTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
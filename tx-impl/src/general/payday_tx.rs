@@ -1,35 +1,162 @@
 use chrono::NaiveDate;
+use std::{
+    sync::{Arc, RwLock},
+    thread,
+};
 use tx_rs::Tx;
 
 use abstract_tx::UsecaseError;
 use dao::{EmployeeDao, HaveEmployeeDao};
-use payroll_domain::Paycheck;
+use payroll_domain::{Employee, EmployeeId, Paycheck, PaymentMethod};
+
+// the employees are paid a batch at a time, not all of them fetched at once
+const PAYDAY_BATCH_SIZE: usize = 1000;
+
+// a paycheck with the method it is to be paid by; the employee is not cloned for it
+pub type Payout = (Arc<RwLock<dyn PaymentMethod>>, Paycheck);
 
 pub trait PaydayTransaction<Ctx>: HaveEmployeeDao<Ctx> {
-    fn execute<'a>(&self, pay_date: NaiveDate) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    // pays the batch of employees after `after`, none for the first, returning the
+    // last of them, none at the end of the payday, with the payouts; each batch is
+    // to be committed and then paid out on its own, so that no transaction holds more
+    // than a batch. the paychecks of a batch are made out on as many threads, 1 for none
+    fn execute<'a>(
+        &self,
+        pay_date: NaiveDate,
+        after: Option<EmployeeId>,
+        threads: usize,
+    ) -> impl tx_rs::Tx<Ctx, Item = (Option<EmployeeId>, Vec<Payout>), Err = UsecaseError>
     where
         Ctx: 'a,
     {
         tx_rs::with_tx(move |ctx| {
             let mut employees = self
                 .dao()
-                .get_all()
+                .get_payable(pay_date, after, PAYDAY_BATCH_SIZE)
                 .run(ctx)
-                .map_err(UsecaseError::GetAllFailed)?;
-            for emp in employees.iter_mut() {
-                if emp.is_pay_date(pay_date) {
-                    let period = emp.get_pay_period(pay_date);
-                    let mut pc = Paycheck::new(period);
-                    emp.payday(&mut pc);
-                    self.dao()
-                        .record_paycheck(emp.get_emp_id(), pc)
-                        .run(ctx)
-                        .map_err(UsecaseError::UpdateEmployeeFailed)?;
+                .map_err(UsecaseError::GetPayableFailed)?;
+            let last = employees.last().map(|emp| emp.get_emp_id());
+            // those already paid for the period by a payday stopped halfway are skipped
+            let mut unpaid = Vec::with_capacity(employees.len());
+            for emp in employees.drain(..) {
+                let paid = self
+                    .dao()
+                    .has_paycheck(emp.get_emp_id(), emp.get_pay_period(pay_date))
+                    .run(ctx)
+                    .map_err(UsecaseError::GetPaychecksFailed)?;
+                if !paid {
+                    unpaid.push(emp);
                 }
             }
-            Ok(())
+            let paychecks = make_out_all(&mut unpaid, pay_date, threads);
+            let mut paid = Vec::with_capacity(unpaid.len());
+            // recorded in the order of emp_id, whatever the threads
+            for (emp, pc) in unpaid.into_iter().zip(paychecks) {
+                self.dao()
+                    .record_paycheck(emp.get_emp_id(), pc.clone())
+                    .run(ctx)
+                    .map_err(UsecaseError::RecordPaycheckFailed)?;
+                // affiliations keep track of what has been paid
+                let method = emp.get_method();
+                self.dao()
+                    .update(emp)
+                    .run(ctx)
+                    .map_err(UsecaseError::update_failed)?;
+                paid.push((method, pc));
+            }
+            Ok((last, paid))
         })
     }
 }
 // blanket implementation
 impl<Ctx, T> PaydayTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
+
+// the paychecks in the order of the employees
+fn make_out_all(employees: &mut [Employee], pay_date: NaiveDate, threads: usize) -> Vec<Paycheck> {
+    let make_out = |emp: &mut Employee| {
+        let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+        emp.make_out(&mut pc);
+        pc
+    };
+    // none at the end of the payday, or when all of a batch have been paid already
+    if threads <= 1 || employees.is_empty() {
+        return employees.iter_mut().map(make_out).collect();
+    }
+    let chunk_size = employees.len().div_ceil(threads);
+    thread::scope(|s| {
+        let handles = employees
+            .chunks_mut(chunk_size)
+            .map(|chunk| s.spawn(move || chunk.iter_mut().map(make_out).collect::<Vec<_>>()))
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .flat_map(|h| h.join().expect("making out paychecks panicked"))
+            .collect()
+    })
+}
+#[cfg(test)]
+mod test_payday_tx {
+    use super::*;
+    use chrono::Days;
+    use payroll_domain::{DuesPolicy, PaymentClassification};
+    use std::sync::{Arc, RwLock};
+
+    use payroll_impl::{
+        affiliation::UnionAffiliation,
+        classification::{CommissionedClassification, HourlyClassification},
+        method::HoldMethod,
+        record::EmployeeRecord,
+        schedule::WeeklySchedule,
+    };
+
+    // the employees are made anew for each run, as clones would share their affiliations
+    fn employees(pay_date: NaiveDate) -> Vec<Employee> {
+        (1..=25)
+            .map(|emp_id| {
+                let mut affiliation =
+                    UnionAffiliation::new(emp_id, DuesPolicy::PerFriday { dues: 9.42 });
+                affiliation.add_service_charge(pay_date - Days::new(emp_id as u64 % 7), 1.5);
+                let classification: Arc<RwLock<dyn PaymentClassification>> = if emp_id % 3 == 0 {
+                    let mut c = CommissionedClassification::new(100.0, 0.1);
+                    c.add_sales_receipt(pay_date, emp_id as f32 * 100.0);
+                    Arc::new(RwLock::new(c))
+                } else {
+                    let mut c = HourlyClassification::new(15.25);
+                    for days in 0..5 {
+                        c.add_timecard(pay_date - Days::new(days), 8.0 + (emp_id % 4) as f32);
+                    }
+                    Arc::new(RwLock::new(c))
+                };
+                Employee::new(
+                    emp_id,
+                    &format!("Emp{}", emp_id),
+                    "Home",
+                    classification,
+                    Arc::new(RwLock::new(WeeklySchedule)),
+                    Arc::new(RwLock::new(HoldMethod)),
+                    vec![Arc::new(RwLock::new(affiliation))],
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_threads() {
+        let pay_date = NaiveDate::from_ymd_opt(2024, 8, 2).unwrap();
+        let mut sequential = employees(pay_date);
+        let expected = make_out_all(&mut sequential, pay_date, 1);
+        for threads in [2, 4, 8, 32] {
+            let mut parallel = employees(pay_date);
+            assert_eq!(make_out_all(&mut parallel, pay_date, threads), expected);
+            // the affiliations keep track of the same payments
+            let records = |employees: &[Employee]| {
+                employees
+                    .iter()
+                    .map(|emp| EmployeeRecord::from_employee(emp).unwrap())
+                    .collect::<Vec<_>>()
+            };
+            assert_eq!(records(&parallel), records(&sequential));
+        }
+        assert_eq!(make_out_all(&mut [], pay_date, 4), vec![]);
+    }
+}
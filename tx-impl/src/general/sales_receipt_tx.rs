@@ -4,7 +4,7 @@ use tx_rs::Tx;
 use abstract_tx::UsecaseError;
 use dao::{EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
-use payroll_impl::classification::CommissionedClassification;
+use payroll_impl::classification::{find_classification_mut, CommissionedClassification};
 
 pub trait SalesReceiptTransaction<Ctx>: HaveEmployeeDao<Ctx> {
     fn execute<'a>(
@@ -18,20 +18,16 @@ pub trait SalesReceiptTransaction<Ctx>: HaveEmployeeDao<Ctx> {
                 .dao()
                 .fetch(emp_id)
                 .run(ctx)
-                .map_err(UsecaseError::NotFound)?;
-            emp.get_classification()
-                .borrow_mut()
-                .as_any_mut()
-                .downcast_mut::<CommissionedClassification>()
-                .ok_or(UsecaseError::NotCommissionedSalary(format!(
-                    "emp_id: {}",
-                    emp_id
-                )))?
-                .add_sales_receipt(date, amount);
+                .map_err(UsecaseError::fetch_failed)?;
+            find_classification_mut::<CommissionedClassification>(
+                &mut *emp.get_classification().write().unwrap(),
+            )
+            .ok_or(UsecaseError::NotCommissionedSalary { emp_id })?
+            .add_sales_receipt(date, amount);
             self.dao()
                 .update(emp)
                 .run(ctx)
-                .map_err(UsecaseError::UpdateEmployeeFailed)
+                .map_err(UsecaseError::update_failed)
         })
     }
 }
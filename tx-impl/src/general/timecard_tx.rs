@@ -4,7 +4,7 @@ use tx_rs::Tx;
 use abstract_tx::UsecaseError;
 use dao::{EmployeeDao, HaveEmployeeDao};
 use payroll_domain::EmployeeId;
-use payroll_impl::classification::HourlyClassification;
+use payroll_impl::classification::{find_classification_mut, HourlyClassification};
 
 pub trait TimeCardTransaction<Ctx>: HaveEmployeeDao<Ctx> {
     fn execute<'a>(
@@ -18,17 +18,16 @@ pub trait TimeCardTransaction<Ctx>: HaveEmployeeDao<Ctx> {
                 .dao()
                 .fetch(emp_id)
                 .run(ctx)
-                .map_err(UsecaseError::NotFound)?;
-            emp.get_classification()
-                .borrow_mut()
-                .as_any_mut()
-                .downcast_mut::<HourlyClassification>()
-                .ok_or(UsecaseError::NotHourlySalary(format!("emp_id: {}", emp_id)))?
-                .add_timecard(date, hours);
+                .map_err(UsecaseError::fetch_failed)?;
+            find_classification_mut::<HourlyClassification>(
+                &mut *emp.get_classification().write().unwrap(),
+            )
+            .ok_or(UsecaseError::NotHourlySalary { emp_id })?
+            .add_timecard(date, hours);
             self.dao()
                 .update(emp)
                 .run(ctx)
-                .map_err(UsecaseError::UpdateEmployeeFailed)
+                .map_err(UsecaseError::update_failed)
         })
     }
 }
@@ -0,0 +1,230 @@
+use chrono::{Datelike, Days, NaiveDate};
+use std::sync::{Arc, RwLock};
+use tx_rs::Tx;
+
+use abstract_tx::UsecaseError;
+use dao::{EmployeeDao, HaveEmployeeDao};
+use payroll_domain::Employee;
+use payroll_impl::{
+    table::SharedTable,
+    tax::{YearEndAdjustment, YearEndDeclaration, YearEndSettlements, YearEndTable, YearToDate},
+};
+
+// the first pay date of the employee from `date` on, searched within a year
+fn next_pay_date(emp: &Employee, date: NaiveDate) -> Option<NaiveDate> {
+    date.iter_days().take(366).find(|d| emp.is_pay_date(*d))
+}
+fn last_pay_date(emp: &Employee, year: i32) -> Option<NaiveDate> {
+    let new_year = NaiveDate::from_ymd_opt(year, 1, 1)?;
+    NaiveDate::from_ymd_opt(year, 12, 31)?
+        .iter_days()
+        .rev()
+        .take_while(|d| *d >= new_year)
+        .find(|d| emp.is_pay_date(*d))
+}
+
+pub trait YearEndAdjustmentTransaction<Ctx>: HaveEmployeeDao<Ctx> {
+    fn execute<'a>(
+        &'a self,
+        year: i32,
+        table: &'a SharedTable<YearEndTable>,
+        declarations: &'a [YearEndDeclaration],
+    ) -> impl tx_rs::Tx<Ctx, Item = (), Err = UsecaseError>
+    where
+        Ctx: 'a,
+    {
+        tx_rs::with_tx(move |ctx| {
+            let mut emps = vec![];
+            for declaration in declarations {
+                let emp_id = declaration.get_emp_id();
+                let mut emp = self
+                    .dao()
+                    .fetch(emp_id)
+                    .run(ctx)
+                    .map_err(UsecaseError::fetch_failed)?;
+                let pay_date = last_pay_date(&emp, year)
+                    .ok_or(UsecaseError::PayDateNotFound { emp_id, year })?;
+                let paychecks = self
+                    .dao()
+                    .get_paychecks(emp_id)
+                    .run(ctx)
+                    .map_err(UsecaseError::GetPaychecksFailed)?
+                    .into_iter()
+                    .filter(|pc| pc.get_pay_period().end().year() == year)
+                    .collect::<Vec<_>>();
+                let affiliation = emp
+                    .get_affiliation(YearEndSettlements::NAME)
+                    .unwrap_or_else(|| {
+                        // added after the withholding, to see the tax withheld from the last paycheck
+                        let affiliation = Arc::new(RwLock::new(YearEndSettlements::new()));
+                        emp.add_affiliation(affiliation.clone());
+                        affiliation
+                    });
+                let mut affiliation = affiliation.write().unwrap();
+                let settlements = affiliation
+                    .as_any_mut()
+                    .downcast_mut::<YearEndSettlements>()
+                    .ok_or(UsecaseError::AffiliationConflict {
+                        emp_id,
+                        name: YearEndSettlements::NAME.to_string(),
+                    })?;
+                if paychecks
+                    .iter()
+                    .any(|pc| *pc.get_pay_period().end() == pay_date)
+                {
+                    // the year's last paycheck has been made out, the correction goes to the next one
+                    let next = pay_date
+                        .checked_add_days(Days::new(1))
+                        .and_then(|d| next_pay_date(&emp, d))
+                        .ok_or(UsecaseError::PayDateNotFound {
+                            emp_id,
+                            year: year + 1,
+                        })?;
+                    let adjustment = YearEndAdjustment::calculate(
+                        &table.get(),
+                        declaration,
+                        &YearToDate::of(&paychecks),
+                    );
+                    settlements.settle(&adjustment, year, next);
+                } else {
+                    settlements.prepare(
+                        year,
+                        pay_date,
+                        table.clone(),
+                        declaration.clone(),
+                        &paychecks,
+                    );
+                }
+                drop(affiliation);
+                emps.push(emp);
+            }
+            // the adjustment is calculated when the year's last paycheck is made out
+            for emp in emps {
+                self.dao()
+                    .update(emp)
+                    .run(ctx)
+                    .map_err(UsecaseError::update_failed)?;
+            }
+            Ok(())
+        })
+    }
+}
+// blanket implementation
+impl<T, Ctx> YearEndAdjustmentTransaction<Ctx> for T where T: HaveEmployeeDao<Ctx> {}
+#[cfg(test)]
+mod test_year_end_adjustment_tx {
+    use super::*;
+
+    use dao::DaoError;
+    use mock_db::{MockDb, MockTx};
+    use payroll_domain::{DeductionKind, DeductionLine, Paycheck};
+    use payroll_impl::{
+        classification::SalariedClassification, method::HoldMethod, schedule::MonthlySchedule,
+    };
+
+    struct Db(MockDb);
+    impl HaveEmployeeDao<MockTx> for Db {
+        fn dao(&self) -> Box<&impl EmployeeDao<MockTx>> {
+            Box::new(&self.0)
+        }
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+    fn table() -> SharedTable<YearEndTable> {
+        SharedTable::insert(
+            "test_year_end_adjustment_tx/table.tbl",
+            "BasicDeduction 480000\nDependentDeduction 380000\nBracket 0 10%"
+                .parse::<YearEndTable>()
+                .unwrap(),
+        )
+    }
+    fn adjust(db: &Db, dependents: u32) {
+        let declarations = [YearEndDeclaration::new(1, dependents, vec![])];
+        db.0.with_transaction(|tx| {
+            YearEndAdjustmentTransaction::execute(db, 2024, &table(), &declarations).run(tx)
+        })
+        .unwrap();
+    }
+    fn payday(db: &Db, pay_date: NaiveDate) -> Paycheck {
+        db.0.with_transaction(|tx| {
+            let mut emp = db.0.fetch(1).run(tx)?;
+            let mut pc = Paycheck::new(emp.get_pay_period(pay_date));
+            emp.make_out(&mut pc);
+            db.0.record_paycheck(1, pc.clone()).run(tx)?;
+            db.0.update(emp).run(tx)?;
+            Ok::<_, DaoError>(pc)
+        })
+        .unwrap()
+    }
+    fn settlements(db: &Db) -> Vec<(NaiveDate, Option<f32>, bool)> {
+        let emp = db.0.with_transaction(|tx| db.0.fetch(1).run(tx)).unwrap();
+        let affiliation = emp.get_affiliation(YearEndSettlements::NAME).unwrap();
+        let affiliation = affiliation.read().unwrap();
+        affiliation
+            .as_any()
+            .downcast_ref::<YearEndSettlements>()
+            .unwrap()
+            .get_settlements()
+            .iter()
+            .map(|s| (s.get_pay_date(), s.get_amount(), s.is_settled()))
+            .collect()
+    }
+    fn adjustment_lines(pc: &Paycheck) -> Vec<f32> {
+        pc.get_deduction_lines()
+            .iter()
+            .filter(|l| l.get_description() == "Year-end adjustment 2024")
+            .map(|l| l.get_amount())
+            .collect()
+    }
+
+    #[test]
+    fn test_on_last_paycheck() {
+        let db = Db(MockDb::new());
+        let mut november = Paycheck::new(date(2024, 11, 1)..=date(2024, 11, 30));
+        november.set_gross_pay(1000000.0);
+        november.add_deduction_line(DeductionLine::new(
+            DeductionKind::IncomeTax,
+            "Income tax",
+            20000.0,
+        ));
+        db.0.with_transaction(|tx| {
+            db.0.insert(Employee::new(
+                1,
+                "Hanako",
+                "Tokyo",
+                Arc::new(RwLock::new(SalariedClassification::new(300000.0))),
+                Arc::new(RwLock::new(MonthlySchedule)),
+                Arc::new(RwLock::new(HoldMethod)),
+                vec![],
+            ))
+            .run(tx)?;
+            db.0.record_paycheck(1, november).run(tx)
+        })
+        .unwrap();
+
+        // pending until the year's last paycheck is made out
+        adjust(&db, 0);
+        assert_eq!(settlements(&db), vec![(date(2024, 12, 31), None, false)]);
+        // 1300000 less 480000 of basic deduction, 20000 withheld
+        let pc = payday(&db, date(2024, 12, 31));
+        assert_eq!(adjustment_lines(&pc), vec![62000.0]);
+        assert_eq!(
+            settlements(&db),
+            vec![(date(2024, 12, 31), Some(62000.0), true)]
+        );
+
+        // made again after it, the difference goes to the next paycheck
+        adjust(&db, 1);
+        let pc = payday(&db, date(2025, 1, 31));
+        assert_eq!(adjustment_lines(&pc), vec![-38000.0]);
+        assert_eq!(
+            settlements(&db),
+            vec![
+                (date(2024, 12, 31), Some(62000.0), true),
+                (date(2025, 1, 31), Some(-38000.0), true),
+            ]
+        );
+    }
+}
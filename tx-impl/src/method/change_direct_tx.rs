@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeMethodTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -17,7 +17,7 @@ pub trait ChangeDirectTransaction<Ctx>: ChangeMethodTransaction<Ctx> {
         ChangeMethodTransaction::execute(
             self,
             emp_id,
-            Rc::new(RefCell::new(DirectMethod::new(bank, account))),
+            Arc::new(RwLock::new(DirectMethod::new(bank, account))),
         )
     }
 }
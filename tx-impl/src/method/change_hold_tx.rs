@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeMethodTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -12,7 +12,7 @@ pub trait ChangeHoldTransaction<Ctx>: ChangeMethodTransaction<Ctx> {
     where
         Ctx: 'a,
     {
-        ChangeMethodTransaction::execute(self, emp_id, Rc::new(RefCell::new(HoldMethod)))
+        ChangeMethodTransaction::execute(self, emp_id, Arc::new(RwLock::new(HoldMethod)))
     }
 }
 // blanket implementation
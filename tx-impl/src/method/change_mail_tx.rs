@@ -1,4 +1,4 @@
-use std::{cell::RefCell, rc::Rc};
+use std::sync::{Arc, RwLock};
 
 use abstract_tx::{ChangeMethodTransaction, UsecaseError};
 use payroll_domain::EmployeeId;
@@ -16,7 +16,7 @@ pub trait ChangeMailTransaction<Ctx>: ChangeMethodTransaction<Ctx> {
         ChangeMethodTransaction::execute(
             self,
             emp_id,
-            Rc::new(RefCell::new(MailMethod::new(address))),
+            Arc::new(RwLock::new(MailMethod::new(address))),
         )
     }
 }